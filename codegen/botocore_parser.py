@@ -2,12 +2,15 @@
 import json
 import re
 import sys
+from collections import OrderedDict
 from html2text import html2text
 from pprint import pprint
 
 shapes = {}
 operations = {}
 metadata = {}
+# operations whose methods are written by hand next to the generated code
+handwritten = []
 
 # map botocore primitives to rust primitives
 primitive_types = {
@@ -26,6 +29,7 @@ primitive_parsers = {
 	'string': 'try!(characters(stack))',
 	'timestamp': 'try!(characters(stack))',
 	'integer': 'i32::from_str(try!(characters(stack)).as_ref()).unwrap()',
+	'long': 'i64::from_str(try!(characters(stack)).as_ref()).unwrap()',
 	'double': 'f32::from_str(try!(characters(stack)).as_ref()).unwrap()',
 	'blob': 'try!(characters(stack)).into_bytes()',
 	'boolean': 'bool::from_str(try!(characters(stack)).as_ref()).unwrap()'
@@ -36,6 +40,7 @@ primitive_writers = {
 	'string': 'obj',
 	'timestamp': 'obj',
 	'integer': '&obj.to_string()',
+	'long': '&obj.to_string()',
 	'double': '&obj.to_string()',
 	'blob': 'str::from_utf8(&obj).unwrap()',
 	'boolean': '&obj.to_string()',
@@ -60,6 +65,13 @@ def documentation(shape, indent=""):
 		print indent + "/// " + markdown
 
 # generate a rust declaration for a botocore shape
+# the name of a shape in Rust: the service prefix keeps the ones named like std types apart
+def rust_name(shape_name):
+	# don't pass in reserved Rust keywords.
+	if shape_name == 'Message' or shape_name == 'Error':
+		return sys.argv[2] + shape_name
+	return shape_name
+
 def rust_type(name, shape):
 	shape_type = shape['type']
 
@@ -71,9 +83,9 @@ def rust_type(name, shape):
 		if shape_type in primitive_types:
 			rust_type = primitive_types[shape_type]
 		elif shape_type == 'map':
-			rust_type = "HashMap<" + shape['key']['shape'] + "," + shape['value']['shape'] + ">"
+			rust_type = "HashMap<" + rust_name(shape['key']['shape']) + "," + rust_name(shape['value']['shape']) + ">"
 		elif shape_type == 'list':
-			rust_type = "Vec<" + shape['member']['shape'] + ">"
+			rust_type = "Vec<" + rust_name(shape['member']['shape']) + ">"
                 else:
 			raise Exception("unrecognised type %s. supported types are %s" % (shape_type, primitive_types.keys() + ['map', 'list', 'struct']))
 		# a String is already a String in rust
@@ -89,13 +101,11 @@ def rust_struct(name, shape):
 		for (mname, member) in shape['members'].iteritems():
 			if 'documentation' in member:
 				documentation(member,"\t")
-			rust_type =  member['shape']
-
-			if rust_type == 'Message':
-				# print "foooo shape"
-				rust_type = sys.argv[2] + rust_type
+			rust_type = rust_name(member['shape'])
 
-			if not is_required(shape, mname):
+			if is_header_timestamp(name, member):
+				rust_type = "Option<DateTime<UTC>>"
+			elif not is_required(shape, mname):
 				rust_type = "Option<" + rust_type + ">"
 			print "\tpub " + c_to_s(mname) + ": " + rust_type + ","
 		print "}\n"
@@ -123,19 +133,156 @@ def param_writer(name, shape):
 	print '\t}'
 	print '}'
 
+# rust code to write primitive types as XML element contents
+primitive_xml_writers = {
+	'string': 'escape_xml(obj)',
+	'timestamp': 'escape_xml(obj)',
+	'integer': 'obj.to_string()',
+	'long': 'obj.to_string()',
+	'float': 'obj.to_string()',
+	'double': 'obj.to_string()',
+	'blob': 'obj.to_base64(STANDARD)',
+	'boolean': 'obj.to_string()',
+}
+
+# generate rust code to serialize a botocore shape as an XML element, for rest-xml request payloads
+def xml_writer(name, shape):
+	shape_type = shape['type']
+
+	print "/// Write " + name + " contents as XML"
+	print 'impl ' + name + 'Writer {'
+	print '\tfn write_xml(name: &str, obj: &' + name + ') -> String {'
+
+	if shape_type in primitive_xml_writers:
+		print '\t\tformat!("<{}>{}</{}>", name, ' + primitive_xml_writers[shape_type] + ', name)'
+	elif shape_type == 'list':
+		list_xml_writer(shape)
+	elif shape_type == 'map':
+		map_xml_writer(shape)
+	elif shape_type == 'structure':
+		struct_xml_writer(shape)
+
+	print '\t}'
+	print '}'
+
+# guts of the xml_writer for struct shapes
+def struct_xml_writer(shape):
+	print '\t\tlet mut xml = format!("<{}", name);'
+	if 'xmlNamespace' in shape and 'prefix' in shape['xmlNamespace']:
+		namespace = shape['xmlNamespace']
+		print '\t\txml.push_str(" xmlns:' + namespace['prefix'] + '=\\"' + namespace['uri'] + '\\"");'
+
+	# attributes have to go in the start tag, before any child elements
+	for (name, member) in shape['members'].iteritems():
+		if not member.get('xmlAttribute'):
+			continue
+		attribute = member.get('locationName', name)
+		if not is_required(shape, name):
+			print "\t\tif let Some(ref obj) = obj." + c_to_s(name) + " {"
+			print '\t\t\txml.push_str(&format!(" ' + attribute + '=\\"{}\\"", escape_xml(obj)));'
+			print "\t\t}"
+		else:
+			print '\t\txml.push_str(&format!(" ' + attribute + '=\\"{}\\"", escape_xml(&obj.' + c_to_s(name) + ')));'
+	print '\t\txml.push_str(">");'
+
+	for (name, member) in shape['members'].iteritems():
+		if member.get('xmlAttribute'):
+			continue
+		element = member.get('locationName', name)
+		member_type = shapes[member['shape']]['type']
+		if not is_required(shape, name):
+			print "\t\tif let Some(ref obj) = obj." + c_to_s(name) + " {"
+			print '\t\t\txml.push_str(&' + rust_name(member['shape']) + 'Writer::write_xml("' + element + '", obj));'
+			print "\t\t}"
+		elif not 'required' in shape and member_type in primitive_xml_writers:
+			# without a required list nothing is, so a member left at its default wasn't set
+			print "\t\tif obj." + c_to_s(name) + " != " + rust_name(member['shape']) + "::default() {"
+			print '\t\t\txml.push_str(&' + rust_name(member['shape']) + 'Writer::write_xml("' + element + '", &obj.' + c_to_s(name) + '));'
+			print "\t\t}"
+		elif not 'required' in shape and member_type in ['list', 'map']:
+			print "\t\tif !obj." + c_to_s(name) + ".is_empty() {"
+			print '\t\t\txml.push_str(&' + rust_name(member['shape']) + 'Writer::write_xml("' + element + '", &obj.' + c_to_s(name) + '));'
+			print "\t\t}"
+		else:
+			print '\t\txml.push_str(&' + rust_name(member['shape']) + 'Writer::write_xml("' + element + '", &obj.' + c_to_s(name) + '));'
+
+	print '\t\txml.push_str(&format!("</{}>", name));'
+	print '\t\txml'
+
+# guts of the xml_writer for list shapes.  Flattened lists repeat the parent's element name for each item.
+def list_xml_writer(shape):
+	member = shape['member']
+	if shape.get('flattened'):
+		print '\t\tlet mut xml = String::new();'
+		print '\t\tfor element in obj.iter() {'
+		print '\t\t\txml.push_str(&' + rust_name(member['shape']) + 'Writer::write_xml(name, element));'
+		print '\t\t}'
+	else:
+		print '\t\tlet mut xml = format!("<{}>", name);'
+		print '\t\tfor element in obj.iter() {'
+		print '\t\t\txml.push_str(&' + rust_name(member['shape']) + 'Writer::write_xml("' + member.get('locationName', 'member') + '", element));'
+		print '\t\t}'
+		print '\t\txml.push_str(&format!("</{}>", name));'
+	print '\t\txml'
+
+# guts of the xml_writer for map shapes
+def map_xml_writer(shape):
+	if shape.get('flattened'):
+		print '\t\tlet mut xml = String::new();'
+	else:
+		print '\t\tlet mut xml = format!("<{}>", name);'
+	print '\t\tfor (key, value) in obj.iter() {'
+	print '\t\t\txml.push_str("<entry>");'
+	print '\t\t\txml.push_str(&' + rust_name(shape['key']['shape']) + 'Writer::write_xml("' + shape['key'].get('locationName', 'key') + '", key));'
+	print '\t\t\txml.push_str(&' + rust_name(shape['value']['shape']) + 'Writer::write_xml("' + shape['value'].get('locationName', 'value') + '", value));'
+	print '\t\t\txml.push_str("</entry>");'
+	print '\t\t}'
+	if not shape.get('flattened'):
+		print '\t\txml.push_str(&format!("</{}>", name));'
+	print '\t\txml'
+
+# names of the shapes that end up in an XML request payload, which need an xml_writer
+def payload_shapes():
+	found = set()
+
+	def visit(name):
+		if name in found:
+			return
+		found.add(name)
+		shape = shapes[name]
+		if shape['type'] == 'structure':
+			for member in shape['members'].values():
+				visit(member['shape'])
+		elif shape['type'] == 'list':
+			visit(shape['member']['shape'])
+		elif shape['type'] == 'map':
+			visit(shape['key']['shape'])
+			visit(shape['value']['shape'])
+
+	for operation in operations.values():
+		if 'input' in operation:
+			input_shape = shapes[operation['input']['shape']]
+			if 'payload' in input_shape:
+				payload = input_shape['members'][input_shape['payload']]
+				if shapes[payload['shape']]['type'] == 'structure':
+					visit(payload['shape'])
+	return found
+
 # guts of the param_writer for struct shapes
 def struct_writer(shape):
 	print '\t\tlet mut prefix = name.to_string();'
 	print '\t\tif prefix != "" { prefix.push_str("."); }'
 
 	for (name, member) in shape['members'].iteritems():
+		if is_external(member):
+			continue
 		location_name = get_location_name(name, member)
 		if not is_required(shape, name):
 			print "\t\tif let Some(ref obj) = obj." + c_to_s(name) + " {"
-			print '\t\t\t' + member['shape'] + 'Writer::write_params(params, &(prefix.to_string() + "' + location_name + '"), obj);'
+			print '\t\t\t' + rust_name(member['shape']) + 'Writer::write_params(params, &(prefix.to_string() + "' + location_name + '"), obj);'
 			print "\t\t}"
 		else:
-			print '\t\t' + member['shape'] + 'Writer::write_params(params, &(prefix.to_string() + "' + location_name + '"), &obj.' + c_to_s(name) + ');'
+			print '\t\t' + rust_name(member['shape']) + 'Writer::write_params(params, &(prefix.to_string() + "' + location_name + '"), &obj.' + c_to_s(name) + ');'
 
 
 
@@ -144,7 +291,7 @@ def list_writer(shape):
 	print "\t\tlet mut index = 1;"
 	print "\t\tfor element in obj.iter() {"
 	print "\t\t\tlet key = &format!(\"{}.{}\", name, index);"
-	print "\t\t\t" + shape['member']['shape'] + "Writer::write_params(params, key, &element);"
+	print "\t\t\t" + rust_name(shape['member']['shape']) + "Writer::write_params(params, key, &element);"
 	print "\t\t\tindex += 1;"
 	print "\t\t}"
 
@@ -153,8 +300,8 @@ def map_writer(shape):
 	print "\t\tlet mut index = 1;"
 	print "\t\tfor (key,value) in obj {"
 	print "\t\t\tlet prefix = &format!(\"{}.{}\", name, index);"
-	print "\t\t\t" + shape['key']['shape'] + "Writer::write_params(params, &format!(\"{}.{}\", prefix, \"" + shape_name(shape['key']) + "\"), &key);"
-	print "\t\t\t" + shape['value']['shape'] + "Writer::write_params(params, &format!(\"{}.{}\", prefix, \"" + shape_name(shape['value'])	 + "\"), &value);"
+	print "\t\t\t" + rust_name(shape['key']['shape']) + "Writer::write_params(params, &format!(\"{}.{}\", prefix, \"" + shape_name(shape['key']) + "\"), &key);"
+	print "\t\t\t" + rust_name(shape['value']['shape']) + "Writer::write_params(params, &format!(\"{}.{}\", prefix, \"" + shape_name(shape['value'])	 + "\"), &value);"
 	print "\t\t\tindex += 1;"
 	print "\t\t}"
 
@@ -195,8 +342,8 @@ def map_parser(shape):
 	print "\t\tlet mut obj = HashMap::new();"
 	print "\t\twhile try!(peek_at_name(stack)) == tag_name {"
 	print "\t\t\ttry!(start_element(tag_name, stack));"
-	print "\t\t\tlet key = try!(" + shape['key']['shape'] + "Parser::parse_xml(\"" + shape_name(shape['key']) + "\", stack));"
-	print "\t\t\tlet value = try!(" + shape['value']['shape'] + "Parser::parse_xml(\"" +shape_name(shape['value']) + "\", stack));"
+	print "\t\t\tlet key = try!(" + rust_name(shape['key']['shape']) + "Parser::parse_xml(\"" + shape_name(shape['key']) + "\", stack));"
+	print "\t\t\tlet value = try!(" + rust_name(shape['value']['shape']) + "Parser::parse_xml(\"" +shape_name(shape['value']) + "\", stack));"
 	print "\t\t\tobj.insert(key, value);"
 	print "\t\t\ttry!(end_element(tag_name, stack));"
 	print "\t\t}"
@@ -205,7 +352,7 @@ def map_parser(shape):
 def list_parser(shape):
 	print "\t\tlet mut obj = Vec::new();";
 	print "\t\twhile try!(peek_at_name(stack)) == \"" + shape_name(shape['member']) + "\" {"
-	print "\t\t\tobj.push(try!(" + shape['member']['shape'] + "Parser::parse_xml(\"" + shape_name(shape['member']) + "\", stack)));"
+	print "\t\t\tobj.push(try!(" + rust_name(shape['member']['shape']) + "Parser::parse_xml(\"" + shape_name(shape['member']) + "\", stack)));"
 	print "\t\t}"
 
 def is_required(shape, field_name):
@@ -222,9 +369,16 @@ def struct_parser(name, shape):
 		print '\t\tloop {'
 		print '\t\t\tlet current_name = try!(peek_at_name(stack));'
 		for (cname, child) in children.iteritems():
+			if is_header_timestamp(name, child) or is_external(child):
+				continue
 			parse_struct_child(cname, child, is_required(shape, cname))
 		print '\t\t\tbreak;\n\t\t}'
 
+# whether a member's type is declared by hand outside the generated code, EG: S3's CannedAcl.
+# Those have no generated parser or writer, so only request methods use them, through Display.
+def is_external(member):
+	return shapes[member['shape']]['type'] == 'external'
+
 # get the name that should be used for a child element when encoding/decoding
 def get_location_name(name, child):
 	child_shape = shapes[child['shape']]
@@ -243,8 +397,19 @@ def get_location_name(name, child):
 
 # XML parser code to pull a single struct element from XML
 def parse_struct_child(name, child, required):
+	child_shape = shapes[child['shape']]
+
+	# rest-xml flattened lists repeat the member's own element, possibly interleaved with others
+	if metadata['protocol'] == 'rest-xml' and child_shape['type'] == 'list' and child_shape.get('flattened'):
+		tag_name = child.get('locationName', name)
+		print '\t\t\tif current_name == "' + tag_name + '" {'
+		print '\t\t\t\tobj.' + c_to_s(name) + '.push(try!(' + rust_name(child_shape['member']['shape']) + 'Parser::parse_xml("' + tag_name + '", stack)));'
+		print '\t\t\t\tcontinue;'
+		print '\t\t\t}'
+		return
+
 	tag_name = get_location_name(name, child)
-	parse_stmt = 'try!(' + child['shape'] + 'Parser::parse_xml("' + tag_name + '", stack))'
+	parse_stmt = 'try!(' + rust_name(child['shape']) + 'Parser::parse_xml("' + tag_name + '", stack))'
 
 	if not required:
 		parse_stmt = "Some(" + parse_stmt + ")"
@@ -261,8 +426,291 @@ def get_output_type(operation):
 	else:
 		return "()"
 
+# rust expression turning a member value into the string sent in a URI, query string or header
+def string_value(shape_name, expr):
+	if shapes[shape_name]['type'] in ['string', 'timestamp']:
+		return expr
+	else:
+		return '&' + expr + '.to_string()'
+
+# rust statement(s) placing an input member outside the payload, according to its location
+def located_member_writer(input_type, name, member, indent):
+	location = member['location']
+	location_name = member.get('locationName', name)
+
+	if location == 'querystring':
+		print indent + 'params.put("' + location_name + '", ' + string_value(member['shape'], 'obj') + ');'
+	elif location == 'header':
+		print indent + 'request.add_header("' + location_name + '", ' + string_value(member['shape'], 'obj') + ');'
+	elif location == 'headers':
+		print indent + 'for (key, value) in obj.iter() {'
+		print indent + '\trequest.add_header(&format!("' + location_name + '{}", key), value);'
+		print indent + '}'
+
+# generate rust code to sign and execute an HTTP request for a rest-xml operation, such as S3's.
+# Members are placed by location: URI labels, query string, headers or the request payload.
+def rest_xml_request_method(operation):
+	http = operation['http']
+	output_type = get_output_type(operation)
+	documentation(operation,"\t")
+
+	input_name = ''
+	input_type = {'members': {}}
+
+	if not ('input' in operation):
+		print "\tpub fn " + c_to_s(operation['name']) + "(&mut self) -> Result<" + output_type + ", AWSError> {"
+	else:
+		input_name = operation['input']['shape']
+		input_type = shapes[input_name]
+		print "\tpub fn " + c_to_s(operation['name']) + "(&mut self, input: &" + input_name + ") -> Result<" + output_type + ", AWSError> {"
+
+	# "/{Bucket}/{Key+}?acl" has the path, with labels to fill in, and fixed query parameters
+	request_uri = http['requestUri'].split('?', 1)
+	path = request_uri[0]
+	members = input_type['members']
+
+	labels = [(name, member) for (name, member) in members.iteritems() if member.get('location') == 'uri']
+	if labels:
+		print '\t\tlet mut uri = "' + path + '".to_string();'
+		for (name, member) in labels:
+			location_name = member.get('locationName', name)
+			label = string_value(member['shape'], 'input.' + c_to_s(name))
+			if not label.startswith('&'):
+				label = '&' + label
+			if ('{' + location_name + '+}') in path:
+				print '\t\turi = uri.replace("{' + location_name + '+}", &encode_uri_path(' + label + '));'
+			else:
+				print '\t\turi = uri.replace("{' + location_name + '}", &encode_uri_strict(' + label + '));'
+	else:
+		print '\t\tlet uri = "' + path + '".to_string();'
+
+	# the payload has to outlive the request borrowing it
+	payload_expr = None
+	if 'payload' in input_type:
+		payload_name = input_type['payload']
+		payload_member = members[payload_name]
+		payload_shape = shapes[payload_member['shape']]
+		required = is_required(input_type, payload_name)
+		field = 'input.' + c_to_s(payload_name)
+
+		if payload_shape['type'] == 'structure':
+			element = payload_member.get('locationName', payload_name)
+			xml = rust_name(payload_member['shape']) + 'Writer::write_xml("' + element + '", ' + ('&' + field if required else 'obj') + ')'
+			if 'xmlNamespace' in payload_member:
+				xml = 'with_xml_namespace(&' + xml + ', "' + payload_member['xmlNamespace']['uri'] + '")'
+			if required:
+				print '\t\tlet payload = ' + xml + ';'
+				payload_expr = ('payload', 'payload.as_bytes()', True)
+			else:
+				print '\t\tlet payload = match ' + field + ' {'
+				print '\t\t\tSome(ref obj) => Some(' + xml + '),'
+				print '\t\t\tNone => None,'
+				print '\t\t};'
+				payload_expr = ('payload', 'payload.as_bytes()', False)
+		elif payload_shape['type'] == 'blob':
+			payload_expr = (field, '&obj[..]' if not required else '&' + field + '[..]', required)
+		else:
+			payload_expr = (field, 'obj.as_bytes()' if not required else field + '.as_bytes()', required)
+
+	print '\t\tlet mut request = SignedRequest::new("' + http['method'] + '", "' + metadata['endpointPrefix'] + '", &self.region, &uri);'
+	# the client's S3Endpoint picks the host, and moves the bucket there for virtual-hosted requests
+	bucket_label = [name for (name, member) in labels if member.get('locationName', name) == 'Bucket']
+	if bucket_label:
+		print '\t\ttry!(self.endpoint.address(&mut request, &input.bucket));'
+	elif not labels:
+		print '\t\trequest.set_hostname(Some(self.endpoint.service_hostname(self.region)));'
+	if len(request_uri) > 1 or [m for m in members.values() if m.get('location') == 'querystring']:
+		print "\t\tlet mut params = Params::new();"
+	else:
+		print "\t\tlet params = Params::new();"
+
+	if len(request_uri) > 1:
+		for parameter in request_uri[1].split('&'):
+			key_value = parameter.split('=', 1)
+			print '\t\tparams.put("' + key_value[0] + '", "' + (key_value[1] if len(key_value) > 1 else '') + '");'
+
+	for (name, member) in members.iteritems():
+		if not member.get('location') in ['querystring', 'header', 'headers']:
+			continue
+		if not is_required(input_type, name):
+			print "\t\tif let Some(ref obj) = input." + c_to_s(name) + " {"
+			located_member_writer(input_type, name, member, '\t\t\t')
+			print "\t\t}"
+		else:
+			print "\t\t{"
+			print "\t\t\tlet obj = &input." + c_to_s(name) + ";"
+			located_member_writer(input_type, name, member, '\t\t\t')
+			print "\t\t}"
+
+	if payload_expr:
+		(source, as_bytes, required) = payload_expr
+		checksum = operation.get('httpChecksumRequired') or operation.get('httpChecksum', {}).get('requestChecksumRequired')
+		indent = '\t\t'
+		if not required:
+			print '\t\tif let Some(ref ' + ('payload' if source == 'payload' else 'obj') + ') = ' + source + ' {'
+			indent = '\t\t\t'
+		print indent + 'request.set_payload(Some(' + as_bytes + '));'
+		if checksum:
+			# S3 refuses these requests without a Content-MD5 header
+			if 'ContentMD5' in members and not is_required(input_type, 'ContentMD5'):
+				print indent + 'if input.content_md5.is_none() {'
+				print indent + '\trequest.add_header("Content-MD5", &hash(MD5, ' + as_bytes + ').to_base64(STANDARD));'
+				print indent + '}'
+			elif not 'ContentMD5' in members:
+				print indent + 'request.add_header("Content-MD5", &hash(MD5, ' + as_bytes + ').to_base64(STANDARD));'
+		if not required:
+			print '\t\t}'
+
+	print '\t\trequest.set_params(params);'
+	print '\t\trequest.set_hooks(&self.hooks);'
+	if bucket_label:
+		# follows the bucket to its region
+		print '\t\tlet result = try!(self.send(&mut request, &input.bucket));'
+	else:
+		print '\t\tlet result = try!(request.sign_and_execute(try!(self.creds.get_credentials())));'
+	print '\t\tlet status = result.status.to_u16();'
+	print '\t\tmatch status {'
+	print '\t\t\t200...299 => {'
+	rest_xml_response_parser(output_type)
+	print '\t\t\t}'
+	print '\t\t\t_ => { Err(AWSError::new("error")) }'
+	print '\t\t}'
+	print "\t}"
+
+# rust code to build a rest-xml operation's output from a successful response in `result`
+def rest_xml_response_parser(output_type):
+	indent = '\t\t\t\t'
+	if output_type == '()':
+		print indent + 'Ok(())'
+		return
+
+	output_shape = shapes[output_type]
+	body_members = [(name, member) for (name, member) in output_shape['members'].iteritems() if not 'location' in member]
+	parse_headers = has_header_members(output_shape)
+
+	if 'payload' in output_shape:
+		payload_name = output_shape['payload']
+		payload_member = output_shape['members'][payload_name]
+		payload_type = shapes[payload_member['shape']]['type']
+		print indent + 'let mut obj = ' + output_type + '::default();'
+		if parse_headers:
+			print indent + 'try!(' + output_type + 'Parser::parse_headers(&result.headers, &mut obj));'
+		if payload_type == 'blob':
+			print indent + 'let mut result = result;'
+			print indent + 'let mut body = Vec::new();'
+			print indent + 'if let Err(why) = result.read_to_end(&mut body) {'
+			print indent + '\treturn Err(AWSError::new(format!("Couldn\'t read response body: {}", why)));'
+			print indent + '}'
+		elif payload_type == 'structure':
+			print indent + 'let mut reader = EventReader::new(result);'
+			print indent + 'let mut stack = XmlResponseFromAws::new(reader.events().peekable());'
+			print indent + 'stack.next(); // xml start tag'
+			print indent + 'let body = try!(' + rust_name(payload_member['shape']) + 'Parser::parse_xml("' + payload_member.get('locationName', payload_name) + '", &mut stack));'
+		else:
+			print indent + 'let mut result = result;'
+			print indent + 'let mut body = String::new();'
+			print indent + 'if let Err(why) = result.read_to_string(&mut body) {'
+			print indent + '\treturn Err(AWSError::new(format!("Couldn\'t read response body: {}", why)));'
+			print indent + '}'
+		if is_required(output_shape, payload_name):
+			print indent + 'obj.' + c_to_s(payload_name) + ' = body;'
+		else:
+			print indent + 'obj.' + c_to_s(payload_name) + ' = Some(body);'
+		print indent + 'Ok(obj)'
+	elif body_members:
+		if parse_headers:
+			# the XML reader takes the response, so hold on to the headers
+			print indent + 'let headers = result.headers.clone();'
+		print indent + 'let mut reader = EventReader::new(result);'
+		print indent + 'let mut stack = XmlResponseFromAws::new(reader.events().peekable());'
+		print indent + 'stack.next(); // xml start tag'
+		if 'locationName' in output_shape:
+			root = '"' + output_shape['locationName'] + '"'
+		else:
+			# the root element is named after the response, EG: ListBucketResult
+			print indent + 'let root = try!(peek_at_name(&mut stack));'
+			root = '&root'
+		if parse_headers:
+			print indent + 'let mut obj = try!(' + output_type + 'Parser::parse_xml(' + root + ', &mut stack));'
+			print indent + 'try!(' + output_type + 'Parser::parse_headers(&headers, &mut obj));'
+			print indent + 'Ok(obj)'
+		else:
+			print indent + 'Ok(try!(' + output_type + 'Parser::parse_xml(' + root + ', &mut stack)))'
+	elif parse_headers:
+		print indent + 'let mut obj = ' + output_type + '::default();'
+		print indent + 'try!(' + output_type + 'Parser::parse_headers(&result.headers, &mut obj));'
+		print indent + 'Ok(obj)'
+	else:
+		print indent + 'Ok(' + output_type + '::default())'
+
+def has_header_members(shape):
+	return shape['type'] == 'structure' and [m for m in shape['members'].values() if m.get('location') in ['header', 'headers']]
+
+# rust code to convert the String `value` of a response header to a member's type
+header_value_parsers = {
+	'string': 'value',
+	'integer': 'try!(i32::from_str(&value))',
+	'long': 'try!(i64::from_str(&value))',
+	'boolean': 'value == "true"',
+}
+
+# generate rust code to fill in the header-bound members of a rest-xml output shape from response headers
+def header_parser(name, shape):
+	print "/// Parse " + name + " members bound to response headers"
+	print 'impl ' + name + 'Parser {'
+	print '\tfn parse_headers(headers: &Headers, obj: &mut ' + name + ') -> Result<(), XmlParseError> {'
+
+	for (mname, member) in shape['members'].iteritems():
+		location = member.get('location')
+		location_name = member.get('locationName', mname)
+		field = 'obj.' + c_to_s(mname)
+		required = is_required(shape, mname)
+
+		if is_header_timestamp(name, member):
+			# lenient, as S3 echoes back whatever Expires an object was uploaded with
+			print '\t\tif let Some(value) = header_value(headers, "' + location_name + '") {'
+			print '\t\t\t' + field + ' = conditional::parse_http_date(&value).ok();'
+			print '\t\t}'
+		elif location == 'header':
+			member_type = shapes[member['shape']]['type']
+			if not member_type in header_value_parsers:
+				raise Exception("unsupported header type %s for %s.%s" % (member_type, name, mname))
+			value = header_value_parsers[member_type]
+			print '\t\tif let Some(value) = header_value(headers, "' + location_name + '") {'
+			print '\t\t\t' + field + ' = ' + (value if required else 'Some(' + value + ')') + ';'
+			print '\t\t}'
+		elif location == 'headers':
+			# EG: user metadata sent as x-amz-meta-*
+			if required:
+				print '\t\t' + field + ' = headers_with_prefix(headers, "' + location_name + '");'
+			else:
+				print '\t\tlet ' + c_to_s(mname) + ' = headers_with_prefix(headers, "' + location_name + '");'
+				print '\t\tif !' + c_to_s(mname) + '.is_empty() {'
+				print '\t\t\t' + field + ' = Some(' + c_to_s(mname) + ');'
+				print '\t\t}'
+
+	print '\t\tOk(())'
+	print '\t}'
+	print '}'
+
+# whether a member is an HTTP date from a response header, which is parsed rather than kept as text
+def is_header_timestamp(shape_name, member):
+	return member.get('location') == 'header' and shapes[member['shape']]['type'] == 'timestamp' and shape_name in header_shapes()
+
+# names of the operation output shapes with members bound to response headers
+def header_shapes():
+	found = set()
+	for operation in operations.values():
+		if 'output' in operation and has_header_members(shapes[operation['output']['shape']]):
+			found.add(operation['output']['shape'])
+	return found
+
 # generate rust code to sign and execute an HTTP request for a botocore operation
 def request_method(operation):
+	if metadata['protocol'] == 'rest-xml':
+		rest_xml_request_method(operation)
+		return
+
 	http = operation['http']
 
 	output_type = get_output_type(operation)
@@ -273,11 +721,11 @@ def request_method(operation):
 	input_type = ''
 
 	if not ('input' in operation):
-		print "\tpub fn " + c_to_s(operation['name']) + "(&self"") -> Result<" + output_type + ", AWSError> {"
+		print "\tpub fn " + c_to_s(operation['name']) + "(&mut self) -> Result<" + output_type + ", AWSError> {"
 	else:
 		input_name = operation['input']['shape']
 		input_type = shapes[input_name]
-		print "\tpub fn " + c_to_s(operation['name']) + "(&self, input: &" + input_name + ") -> Result<" + output_type + ", AWSError> {"
+		print "\tpub fn " + c_to_s(operation['name']) + "(&mut self, input: &" + input_name + ") -> Result<" + output_type + ", AWSError> {"
 
 	print '\t\tlet mut request = SignedRequest::new("' + http['method'] + '", "' + metadata['endpointPrefix'] + '", &self.region, "' + http['requestUri'] + '");'
 	print "\t\tlet mut params = Params::new();"
@@ -287,7 +735,8 @@ def request_method(operation):
 		print '\t\t' + input_name + 'Writer::write_params(&mut params, \"\", &input);'
 
 	print '\t\trequest.set_params(params);'
-	print '\t\tlet result = request.sign_and_execute(&self.creds);'
+	print '\t\trequest.set_hooks(&self.hooks);'
+	print '\t\tlet result = try!(request.sign_and_execute(try!(self.creds.get_credentials())));'
 	print '\t\tlet status = result.status.to_u16();'
 #	print '\t\tprintln!("{}", output);'
 	print '\t\tlet mut reader = EventReader::new(result);'
@@ -308,47 +757,115 @@ def request_method(operation):
 	print '\t\t}'
 	print "\t}"
 
+# S3Client settings for the endpoint requests go to
+def endpoint_setters():
+	print "\t/// Chooses between virtual-hosted and path-style requests."
+	print "\tpub fn set_addressing_style(&mut self, addressing_style: AddressingStyle) {"
+	print "\t\tself.endpoint.addressing_style = addressing_style;"
+	print "\t}"
+	print "\t/// Sends requests to the dual-stack IPv4/IPv6 endpoints."
+	print "\tpub fn set_dualstack(&mut self, dualstack: bool) {"
+	print "\t\tself.endpoint.dualstack = dualstack;"
+	print "\t}"
+	print "\t/// Sends requests through S3 Transfer Acceleration."
+	print "\tpub fn set_accelerate(&mut self, accelerate: bool) {"
+	print "\t\tself.endpoint.accelerate = accelerate;"
+	print "\t}"
+	print "\tpub fn set_endpoint(&mut self, endpoint: S3Endpoint) {"
+	print "\t\tself.endpoint = endpoint;"
+	print "\t}"
+	print "\tpub fn get_endpoint(&self) -> &S3Endpoint {"
+	print "\t\t&self.endpoint"
+	print "\t}"
+
 def generate_client():
 	client_name = sys.argv[2]
 
 	print "pub struct " + client_name  + "<'a> {"
-	print "\tcreds: &'a AWSCredentials,"
-	print "\tregion: &'a str"
+	print "\tcreds: Box<AWSCredentialsProvider + 'a>,"
+	print "\tregion: &'a Region,"
+	print "\thooks: RequestHooks,"
+	if metadata['protocol'] == 'rest-xml':
+		print "\tendpoint: S3Endpoint,"
+		print "\tbucket_regions: HashMap<String, Region>,"
 	print "}\n"
 
-	print "impl<'a> " + client_name + "<'a> { "
-	print "\tpub fn new(creds: &'a AWSCredentials, region: &'a str) -> " + client_name + "<'a> {"
-	print "\t\t" + client_name + " { creds: creds, region: region }"
+	print "impl<'a> " + client_name + "<'a> {"
+	print "\tpub fn new<P: AWSCredentialsProvider + 'a>(creds: P, region: &'a Region) -> " + client_name + "<'a> {"
+	if metadata['protocol'] == 'rest-xml':
+		print "\t\t" + client_name + " { creds: Box::new(creds), region: region, hooks: default_hooks(), endpoint: S3Endpoint::default(), bucket_regions: HashMap::new() }"
+	else:
+		print "\t\t" + client_name + " { creds: Box::new(creds), region: region, hooks: default_hooks() }"
+	print "\t}"
+	if metadata['protocol'] == 'rest-xml':
+		endpoint_setters()
+	print "\t/// Adds a hook called around every request this client sends."
+	print "\tpub fn add_hook(&mut self, hook: Arc<RequestHook>) {"
+	print "\t\tself.hooks.push(hook);"
+	print "\t}"
+	print "\t/// Removes all hooks, including the default logging hook."
+	print "\tpub fn clear_hooks(&mut self) {"
+	print "\t\tself.hooks.clear();"
 	print "\t}"
 
 	for (name, operation) in operations.iteritems():
+		if name in handwritten:
+			continue
 		request_method(operation)
 
 	print "}"
 
 
+# apply a customizations file to the service definition:
+#   "shapes": shapes to add, or members to add to or replace in existing structures
+#   "operations": operations to add
+#   "external": shapes declared by hand, which aren't generated
+#   "handwritten": operations whose methods are written by hand, though their shapes are generated
+def customize(service, customizations):
+	shapes = service['shapes']
+	for (name, shape) in customizations.get('shapes', {}).iteritems():
+		if name in shapes and shapes[name]['type'] == 'structure' and shape.get('type', 'structure') == 'structure':
+			shapes[name]['members'].update(shape.get('members', {}))
+			if 'required' in shape:
+				shapes[name].setdefault('required', []).extend(shape['required'])
+		else:
+			shapes[name] = shape
+	for name in customizations.get('external', []):
+		shapes[name] = {'type': 'external'}
+	service['operations'].update(customizations.get('operations', {}))
+	return customizations.get('handwritten', [])
+
 def main():
 	with open(sys.argv[1]) as data_file:
-		service = json.load(data_file)
+		service = json.load(data_file, object_pairs_hook=OrderedDict)
+
+		global handwritten
+		if len(sys.argv) > 3:
+			with open(sys.argv[3]) as customizations_file:
+				handwritten = customize(service, json.load(customizations_file, object_pairs_hook=OrderedDict))
 
 		print "use std::collections::HashMap;"
 		print "use std::str;"
-		print "use std::error::Error;"
 		global shapes
 		global metadata
 		global operations
 		shapes = service['shapes']
 		metadata = service['metadata']
 		operations = service['operations']
+		xml_shapes = payload_shapes()
+		response_header_shapes = header_shapes()
 
 		for (name, shape) in shapes.iteritems():
-			# don't pass in reserved Rust keywords.
-			if name == 'Message' or name == 'Error':
-				# print "REASSIGNING"
-				name = sys.argv[2] + name
+			if shape['type'] == 'external':
+				continue
+			name = rust_name(name)
 			rust_type(name, shape)
 			type_parser(name, shape)
 			param_writer(name, shape)
+			if metadata['protocol'] == 'rest-xml' and name in xml_shapes:
+				xml_writer(name, shape)
+			if metadata['protocol'] == 'rest-xml' and name in response_header_shapes:
+				header_parser(name, shape)
 
 		generate_client()
 
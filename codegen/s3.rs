@@ -43,6 +43,21 @@ impl LifecycleExpirationWriter {
 		DaysWriter::write_params(params, &(prefix.to_string() + "Days"), &obj.days);
 	}
 }
+/// Write LifecycleExpiration contents as XML
+impl LifecycleExpirationWriter {
+	fn write_xml(name: &str, obj: &LifecycleExpiration) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		if obj.date != Date::default() {
+			xml.push_str(&DateWriter::write_xml("Date", &obj.date));
+		}
+		if obj.days != Days::default() {
+			xml.push_str(&DaysWriter::write_xml("Days", &obj.days));
+		}
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 #[derive(Debug, Default)]
 pub struct PutBucketNotificationRequest {
 	pub notification_configuration: NotificationConfigurationDeprecated,
@@ -90,26 +105,17 @@ impl PutBucketNotificationRequestWriter {
 	}
 }
 
-pub type Errors = Vec<String>;
+pub type Errors = Vec<S3ClientError>;
 /// Parse Errors from XML
 struct ErrorsParser;
 impl ErrorsParser {
 	fn parse_xml<'a, T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<Errors, XmlParseError> {
-		let mut obj : Vec<String> = Vec::new();
+		let mut obj = Vec::new();
 		while try!(peek_at_name(stack)) == "Error" {
-			obj.push(try!(ErrorsParser::parse_single_error(stack)));
+			obj.push(try!(S3ClientErrorParser::parse_xml("Error", stack)));
 		}
 		Ok(obj)
 	}
-	// hand crafted:
-	fn parse_single_error<'a, T: Peek + Next>(stack: &mut T) -> Result<String, XmlParseError> {
-		// TODO: go back to try!
-
-		match characters(stack) {
-			Err(why) => Err(why),
-			Ok(val) => Ok(val),
-		}
-	}
 }
 /// Write Errors contents to a SignedRequest
 struct ErrorsWriter;
@@ -118,14 +124,10 @@ impl ErrorsWriter {
 		let mut index = 1;
 		for element in obj.iter() {
 			let key = &format!("{}.{}", name, index);
-			ErrorsWriter::write_param(params, key, &element);
+			S3ClientErrorWriter::write_params(params, key, &element);
 			index += 1;
 		}
 	}
-	// hand crafted:
-	fn write_param(params: &mut Params, key: &str, value: &str) {
-		params.put(key, value);
-	}
 }
 #[derive(Debug, Default)]
 pub struct PutBucketVersioningRequest {
@@ -251,6 +253,20 @@ impl TopicConfigurationWriter {
 		EventListWriter::write_params(params, &(prefix.to_string() + "Event"), &obj.events);
 	}
 }
+/// Write TopicConfiguration contents as XML
+impl TopicConfigurationWriter {
+	fn write_xml(name: &str, obj: &TopicConfiguration) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		if let Some(ref obj) = obj.id {
+			xml.push_str(&NotificationIdWriter::write_xml("Id", obj));
+		}
+		xml.push_str(&TopicArnWriter::write_xml("Topic", &obj.topic_arn));
+		xml.push_str(&EventListWriter::write_xml("Event", &obj.events));
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 #[derive(Debug, Default)]
 pub struct Destination {
 	/// Amazon resource name (ARN) of the bucket where you want Amazon S3 to store
@@ -285,6 +301,16 @@ impl DestinationWriter {
 		BucketNameWriter::write_params(params, &(prefix.to_string() + "Bucket"), &obj.bucket);
 	}
 }
+/// Write Destination contents as XML
+impl DestinationWriter {
+	fn write_xml(name: &str, obj: &Destination) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		xml.push_str(&BucketNameWriter::write_xml("Bucket", &obj.bucket));
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 pub type IfNoneMatch = String;
 /// Parse IfNoneMatch from XML
 struct IfNoneMatchParser;
@@ -379,6 +405,12 @@ impl RoleWriter {
 		params.put(name, obj);
 	}
 }
+/// Write Role contents as XML
+impl RoleWriter {
+	fn write_xml(name: &str, obj: &Role) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 pub type WebsiteRedirectLocation = String;
 /// Parse WebsiteRedirectLocation from XML
 struct WebsiteRedirectLocationParser;
@@ -415,6 +447,12 @@ impl BucketVersioningStatusWriter {
 		params.put(name, obj);
 	}
 }
+/// Write BucketVersioningStatus contents as XML
+impl BucketVersioningStatusWriter {
+	fn write_xml(name: &str, obj: &BucketVersioningStatus) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 #[derive(Debug, Default)]
 pub struct PutBucketReplicationRequest {
 	pub replication_configuration: ReplicationConfiguration,
@@ -588,6 +626,21 @@ impl NoncurrentVersionTransitionWriter {
 		TransitionStorageClassWriter::write_params(params, &(prefix.to_string() + "StorageClass"), &obj.storage_class);
 	}
 }
+/// Write NoncurrentVersionTransition contents as XML
+impl NoncurrentVersionTransitionWriter {
+	fn write_xml(name: &str, obj: &NoncurrentVersionTransition) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		if obj.noncurrent_days != Days::default() {
+			xml.push_str(&DaysWriter::write_xml("NoncurrentDays", &obj.noncurrent_days));
+		}
+		if obj.storage_class != TransitionStorageClass::default() {
+			xml.push_str(&TransitionStorageClassWriter::write_xml("StorageClass", &obj.storage_class));
+		}
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 #[derive(Debug, Default)]
 pub struct GetBucketTaggingOutput {
 	pub tag_set: TagSet,
@@ -691,6 +744,14 @@ pub struct PutObjectOutput {
 	/// If present, specifies the ID of the AWS Key Management Service (KMS) master
 	/// encryption key that was used for the object.
 	pub ssekms_key_id: SSEKMSKeyId,
+	/// The base64-encoded, 32-bit CRC32 checksum of the object.
+	pub checksum_crc32: ChecksumCRC32,
+	/// The base64-encoded, 32-bit CRC32C checksum of the object.
+	pub checksum_crc32_c: ChecksumCRC32C,
+	/// The base64-encoded, 160-bit SHA-1 digest of the object.
+	pub checksum_sha1: ChecksumSHA1,
+	/// The base64-encoded, 256-bit SHA-256 digest of the object.
+	pub checksum_sha256: ChecksumSHA256,
 }
 
 /// Parse PutObjectOutput from XML
@@ -733,12 +794,70 @@ impl PutObjectOutputParser {
 				obj.ssekms_key_id = try!(SSEKMSKeyIdParser::parse_xml("x-amz-server-side-encryption-aws-kms-key-id", stack));
 				continue;
 			}
+			if current_name == "x-amz-checksum-crc32" {
+				obj.checksum_crc32 = try!(ChecksumCRC32Parser::parse_xml("x-amz-checksum-crc32", stack));
+				continue;
+			}
+			if current_name == "x-amz-checksum-crc32c" {
+				obj.checksum_crc32_c = try!(ChecksumCRC32CParser::parse_xml("x-amz-checksum-crc32c", stack));
+				continue;
+			}
+			if current_name == "x-amz-checksum-sha1" {
+				obj.checksum_sha1 = try!(ChecksumSHA1Parser::parse_xml("x-amz-checksum-sha1", stack));
+				continue;
+			}
+			if current_name == "x-amz-checksum-sha256" {
+				obj.checksum_sha256 = try!(ChecksumSHA256Parser::parse_xml("x-amz-checksum-sha256", stack));
+				continue;
+			}
 			break;
 		}
 		try!(end_element(tag_name, stack));
 		Ok(obj)
 	}
 }
+/// Parse PutObjectOutput members bound to response headers
+impl PutObjectOutputParser {
+	fn parse_headers(headers: &Headers, obj: &mut PutObjectOutput) -> Result<(), XmlParseError> {
+		if let Some(value) = header_value(headers, "x-amz-expiration") {
+			obj.expiration = value;
+		}
+		if let Some(value) = header_value(headers, "ETag") {
+			obj.e_tag = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-server-side-encryption") {
+			obj.server_side_encryption = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-version-id") {
+			obj.version_id = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-server-side-encryption-customer-algorithm") {
+			obj.sse_customer_algorithm = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-server-side-encryption-customer-key-MD5") {
+			obj.sse_customer_key_md5 = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-server-side-encryption-aws-kms-key-id") {
+			obj.ssekms_key_id = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-request-charged") {
+			obj.request_charged = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-checksum-crc32") {
+			obj.checksum_crc32 = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-checksum-crc32c") {
+			obj.checksum_crc32_c = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-checksum-sha1") {
+			obj.checksum_sha1 = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-checksum-sha256") {
+			obj.checksum_sha256 = value;
+		}
+		Ok(())
+	}
+}
 /// Write PutObjectOutput contents to a SignedRequest
 struct PutObjectOutputWriter;
 impl PutObjectOutputWriter {
@@ -753,6 +872,10 @@ impl PutObjectOutputWriter {
 		ServerSideEncryptionWriter::write_params(params, &(prefix.to_string() + "x-amz-server-side-encryption"), &obj.server_side_encryption);
 		SSECustomerKeyMD5Writer::write_params(params, &(prefix.to_string() + "x-amz-server-side-encryption-customer-key-MD5"), &obj.sse_customer_key_md5);
 		SSEKMSKeyIdWriter::write_params(params, &(prefix.to_string() + "x-amz-server-side-encryption-aws-kms-key-id"), &obj.ssekms_key_id);
+		ChecksumCRC32Writer::write_params(params, &(prefix.to_string() + "x-amz-checksum-crc32"), &obj.checksum_crc32);
+		ChecksumCRC32CWriter::write_params(params, &(prefix.to_string() + "x-amz-checksum-crc32c"), &obj.checksum_crc32_c);
+		ChecksumSHA1Writer::write_params(params, &(prefix.to_string() + "x-amz-checksum-sha1"), &obj.checksum_sha1);
+		ChecksumSHA256Writer::write_params(params, &(prefix.to_string() + "x-amz-checksum-sha256"), &obj.checksum_sha256);
 	}
 }
 #[derive(Debug, Default)]
@@ -778,6 +901,15 @@ impl PutObjectAclOutputParser {
 		Ok(obj)
 	}
 }
+/// Parse PutObjectAclOutput members bound to response headers
+impl PutObjectAclOutputParser {
+	fn parse_headers(headers: &Headers, obj: &mut PutObjectAclOutput) -> Result<(), XmlParseError> {
+		if let Some(value) = header_value(headers, "x-amz-request-charged") {
+			obj.request_charged = value;
+		}
+		Ok(())
+	}
+}
 /// Write PutObjectAclOutput contents to a SignedRequest
 struct PutObjectAclOutputWriter;
 impl PutObjectAclOutputWriter {
@@ -831,6 +963,17 @@ impl ReplicationConfigurationWriter {
 		RoleWriter::write_params(params, &(prefix.to_string() + "Role"), &obj.role);
 	}
 }
+/// Write ReplicationConfiguration contents as XML
+impl ReplicationConfigurationWriter {
+	fn write_xml(name: &str, obj: &ReplicationConfiguration) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		xml.push_str(&RoleWriter::write_xml("Role", &obj.role));
+		xml.push_str(&ReplicationRulesWriter::write_xml("Rule", &obj.rules));
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 /// Confirms that the requester knows that she or he will be charged for the
 /// request. Bucket owners need not specify this parameter in their requests.
 /// Documentation on downloading objects from requester pays buckets can be found
@@ -910,6 +1053,16 @@ impl RulesWriter {
 		}
 	}
 }
+/// Write Rules contents as XML
+impl RulesWriter {
+	fn write_xml(name: &str, obj: &Rules) -> String {
+		let mut xml = String::new();
+		for element in obj.iter() {
+			xml.push_str(&RuleWriter::write_xml(name, element));
+		}
+		xml
+	}
+}
 #[derive(Debug, Default)]
 pub struct CompleteMultipartUploadRequest <'a> {
 	pub multipart_upload: Option<&'a [u8]>,
@@ -942,6 +1095,15 @@ impl CreateBucketOutputParser {
 		Ok(obj)
 	}
 }
+/// Parse CreateBucketOutput members bound to response headers
+impl CreateBucketOutputParser {
+	fn parse_headers(headers: &Headers, obj: &mut CreateBucketOutput) -> Result<(), XmlParseError> {
+		if let Some(value) = header_value(headers, "Location") {
+			obj.location = value;
+		}
+		Ok(())
+	}
+}
 /// Write CreateBucketOutput contents to a SignedRequest
 struct CreateBucketOutputWriter;
 impl CreateBucketOutputWriter {
@@ -1030,6 +1192,12 @@ impl TargetBucketWriter {
 		params.put(name, obj);
 	}
 }
+/// Write TargetBucket contents as XML
+impl TargetBucketWriter {
+	fn write_xml(name: &str, obj: &TargetBucket) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 pub type MFADeleteStatus = String;
 /// Parse MFADeleteStatus from XML
 struct MFADeleteStatusParser;
@@ -1145,6 +1313,17 @@ impl TargetGrantsWriter {
 		}
 	}
 }
+/// Write TargetGrants contents as XML
+impl TargetGrantsWriter {
+	fn write_xml(name: &str, obj: &TargetGrants) -> String {
+		let mut xml = format!("<{}>", name);
+		for element in obj.iter() {
+			xml.push_str(&TargetGrantWriter::write_xml("Grant", element));
+		}
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 #[derive(Debug, Default)]
 pub struct ListMultipartUploadsRequest {
 	/// Together with key-marker, specifies the multipart upload after which listing
@@ -1286,6 +1465,12 @@ impl CloudFunctionWriter {
 		params.put(name, obj);
 	}
 }
+/// Write CloudFunction contents as XML
+impl CloudFunctionWriter {
+	fn write_xml(name: &str, obj: &CloudFunction) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 #[derive(Debug, Default)]
 pub struct DeleteBucketWebsiteRequest {
 	pub bucket: BucketName,
@@ -1361,6 +1546,21 @@ impl VersioningConfigurationWriter {
 		MFADeleteWriter::write_params(params, &(prefix.to_string() + "MfaDelete"), &obj.mfa_delete);
 	}
 }
+/// Write VersioningConfiguration contents as XML
+impl VersioningConfigurationWriter {
+	fn write_xml(name: &str, obj: &VersioningConfiguration) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		if obj.mfa_delete != MFADelete::default() {
+			xml.push_str(&MFADeleteWriter::write_xml("MfaDelete", &obj.mfa_delete));
+		}
+		if obj.status != BucketVersioningStatus::default() {
+			xml.push_str(&BucketVersioningStatusWriter::write_xml("Status", &obj.status));
+		}
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 #[derive(Debug, Default)]
 pub struct GetBucketCorsRequest {
 	pub bucket: BucketName,
@@ -1430,6 +1630,16 @@ impl IndexDocumentWriter {
 		SuffixWriter::write_params(params, &(prefix.to_string() + "Suffix"), &obj.suffix);
 	}
 }
+/// Write IndexDocument contents as XML
+impl IndexDocumentWriter {
+	fn write_xml(name: &str, obj: &IndexDocument) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		xml.push_str(&SuffixWriter::write_xml("Suffix", &obj.suffix));
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 pub type TopicConfigurationList = Vec<TopicConfiguration>;
 /// Parse TopicConfigurationList from XML
 struct TopicConfigurationListParser;
@@ -1454,6 +1664,16 @@ impl TopicConfigurationListWriter {
 		}
 	}
 }
+/// Write TopicConfigurationList contents as XML
+impl TopicConfigurationListWriter {
+	fn write_xml(name: &str, obj: &TopicConfigurationList) -> String {
+		let mut xml = String::new();
+		for element in obj.iter() {
+			xml.push_str(&TopicConfigurationWriter::write_xml(name, element));
+		}
+		xml
+	}
+}
 pub type ReplaceKeyPrefixWith = String;
 /// Parse ReplaceKeyPrefixWith from XML
 struct ReplaceKeyPrefixWithParser;
@@ -1472,6 +1692,12 @@ impl ReplaceKeyPrefixWithWriter {
 		params.put(name, obj);
 	}
 }
+/// Write ReplaceKeyPrefixWith contents as XML
+impl ReplaceKeyPrefixWithWriter {
+	fn write_xml(name: &str, obj: &ReplaceKeyPrefixWith) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 #[derive(Debug, Default)]
 pub struct CreateMultipartUploadRequest {
 	pub request_payer: Option<RequestPayer>,
@@ -1531,6 +1757,9 @@ pub struct CreateMultipartUploadRequest {
 	/// Amazon S3 uses this header for a message integrity check to ensure the
 	/// encryption key was transmitted without error.
 	pub sse_customer_key_md5: Option<SSECustomerKeyMD5>,
+	/// The tag-set for the object, encoded as URL query parameters (for example,
+	/// "Key1=Value1").
+	pub tagging: Option<TaggingHeader>,
 }
 
 /// Parse CreateMultipartUploadRequest from XML
@@ -1629,6 +1858,10 @@ impl CreateMultipartUploadRequestParser {
 				obj.sse_customer_key_md5 = Some(try!(SSECustomerKeyMD5Parser::parse_xml("x-amz-server-side-encryption-customer-key-MD5", stack)));
 				continue;
 			}
+			if current_name == "x-amz-tagging" {
+				obj.tagging = Some(try!(TaggingHeaderParser::parse_xml("x-amz-tagging", stack)));
+				continue;
+			}
 			break;
 		}
 		try!(end_element(tag_name, stack));
@@ -1703,6 +1936,9 @@ impl CreateMultipartUploadRequestWriter {
 		if let Some(ref obj) = obj.sse_customer_key_md5 {
 			SSECustomerKeyMD5Writer::write_params(params, &(prefix.to_string() + "x-amz-server-side-encryption-customer-key-MD5"), obj);
 		}
+		if let Some(ref obj) = obj.tagging {
+			TaggingHeaderWriter::write_params(params, &(prefix.to_string() + "x-amz-tagging"), obj);
+		}
 	}
 }
 #[derive(Debug, Default)]
@@ -1828,6 +2064,12 @@ impl DaysWriter {
 		params.put(name, &obj.to_string());
 	}
 }
+/// Write Days contents as XML
+impl DaysWriter {
+	fn write_xml(name: &str, obj: &Days) -> String {
+		format!("<{}>{}</{}>", name, obj.to_string(), name)
+	}
+}
 pub type Value = String;
 /// Parse Value from XML
 struct ValueParser;
@@ -1846,6 +2088,12 @@ impl ValueWriter {
 		params.put(name, obj);
 	}
 }
+/// Write Value contents as XML
+impl ValueWriter {
+	fn write_xml(name: &str, obj: &Value) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 pub type DeletedObjects = Vec<DeletedObject>;
 /// Parse DeletedObjects from XML
 struct DeletedObjectsParser;
@@ -1942,6 +2190,17 @@ impl TagWriter {
 		ObjectKeyWriter::write_params(params, &(prefix.to_string() + "Key"), &obj.key);
 	}
 }
+/// Write Tag contents as XML
+impl TagWriter {
+	fn write_xml(name: &str, obj: &Tag) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		xml.push_str(&ObjectKeyWriter::write_xml("Key", &obj.key));
+		xml.push_str(&ValueWriter::write_xml("Value", &obj.value));
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 pub type KeyMarker = String;
 /// Parse KeyMarker from XML
 struct KeyMarkerParser;
@@ -2205,6 +2464,12 @@ impl ProtocolWriter {
 		params.put(name, obj);
 	}
 }
+/// Write Protocol contents as XML
+impl ProtocolWriter {
+	fn write_xml(name: &str, obj: &Protocol) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 pub type Suffix = String;
 /// Parse Suffix from XML
 struct SuffixParser;
@@ -2223,6 +2488,12 @@ impl SuffixWriter {
 		params.put(name, obj);
 	}
 }
+/// Write Suffix contents as XML
+impl SuffixWriter {
+	fn write_xml(name: &str, obj: &Suffix) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 pub type AllowedMethod = String;
 /// Parse AllowedMethod from XML
 struct AllowedMethodParser;
@@ -2241,6 +2512,12 @@ impl AllowedMethodWriter {
 		params.put(name, obj);
 	}
 }
+/// Write AllowedMethod contents as XML
+impl AllowedMethodWriter {
+	fn write_xml(name: &str, obj: &AllowedMethod) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 /// Container for specifying an configuration when you want Amazon S3 to publish
 /// events to an Amazon Simple Queue Service (Amazon SQS) queue.
 #[derive(Debug, Default)]
@@ -2291,6 +2568,20 @@ impl QueueConfigurationWriter {
 		EventListWriter::write_params(params, &(prefix.to_string() + "Event"), &obj.events);
 	}
 }
+/// Write QueueConfiguration contents as XML
+impl QueueConfigurationWriter {
+	fn write_xml(name: &str, obj: &QueueConfiguration) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		if let Some(ref obj) = obj.id {
+			xml.push_str(&NotificationIdWriter::write_xml("Id", obj));
+		}
+		xml.push_str(&QueueArnWriter::write_xml("Queue", &obj.queue_arn));
+		xml.push_str(&EventListWriter::write_xml("Event", &obj.events));
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 
 pub type SSECustomerKey = String;
 /// Parse SSECustomerKey from XML
@@ -2328,6 +2619,12 @@ impl ObjectVersionIdWriter {
 		params.put(name, obj);
 	}
 }
+/// Write ObjectVersionId contents as XML
+impl ObjectVersionIdWriter {
+	fn write_xml(name: &str, obj: &ObjectVersionId) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 #[derive(Debug, Default)]
 pub struct HeadBucketRequest {
 	pub bucket: BucketName,
@@ -2412,13 +2709,13 @@ impl AbortMultipartUploadRequestWriter {
 		ObjectKeyWriter::write_params(params, &(prefix.to_string() + "Key"), &obj.key);
 	}
 }
-pub type Size = i32;
+pub type Size = i64;
 /// Parse Size from XML
 struct SizeParser;
 impl SizeParser {
 	fn parse_xml<'a, T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<Size, XmlParseError> {
 		try!(start_element(tag_name, stack));
-		let obj = i32::from_str(try!(characters(stack)).as_ref()).unwrap();
+		let obj = i64::from_str(try!(characters(stack)).as_ref()).unwrap();
 		try!(end_element(tag_name, stack));
 		Ok(obj)
 	}
@@ -2495,6 +2792,30 @@ impl UploadPartCopyOutputParser {
 		Ok(obj)
 	}
 }
+/// Parse UploadPartCopyOutput members bound to response headers
+impl UploadPartCopyOutputParser {
+	fn parse_headers(headers: &Headers, obj: &mut UploadPartCopyOutput) -> Result<(), XmlParseError> {
+		if let Some(value) = header_value(headers, "x-amz-copy-source-version-id") {
+			obj.copy_source_version_id = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-server-side-encryption") {
+			obj.server_side_encryption = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-server-side-encryption-customer-algorithm") {
+			obj.sse_customer_algorithm = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-server-side-encryption-customer-key-MD5") {
+			obj.sse_customer_key_md5 = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-server-side-encryption-aws-kms-key-id") {
+			obj.ssekms_key_id = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-request-charged") {
+			obj.request_charged = value;
+		}
+		Ok(())
+	}
+}
 /// Write UploadPartCopyOutput contents to a SignedRequest
 struct UploadPartCopyOutputWriter;
 impl UploadPartCopyOutputWriter {
@@ -2580,6 +2901,30 @@ impl RedirectWriter {
 		HttpRedirectCodeWriter::write_params(params, &(prefix.to_string() + "HttpRedirectCode"), &obj.http_redirect_code);
 	}
 }
+/// Write Redirect contents as XML
+impl RedirectWriter {
+	fn write_xml(name: &str, obj: &Redirect) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		if obj.host_name != HostName::default() {
+			xml.push_str(&HostNameWriter::write_xml("HostName", &obj.host_name));
+		}
+		if obj.http_redirect_code != HttpRedirectCode::default() {
+			xml.push_str(&HttpRedirectCodeWriter::write_xml("HttpRedirectCode", &obj.http_redirect_code));
+		}
+		if obj.protocol != Protocol::default() {
+			xml.push_str(&ProtocolWriter::write_xml("Protocol", &obj.protocol));
+		}
+		if obj.replace_key_prefix_with != ReplaceKeyPrefixWith::default() {
+			xml.push_str(&ReplaceKeyPrefixWithWriter::write_xml("ReplaceKeyPrefixWith", &obj.replace_key_prefix_with));
+		}
+		if obj.replace_key_with != ReplaceKeyWith::default() {
+			xml.push_str(&ReplaceKeyWithWriter::write_xml("ReplaceKeyWith", &obj.replace_key_with));
+		}
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 pub type CopySourceIfNoneMatch = String;
 /// Parse CopySourceIfNoneMatch from XML
 struct CopySourceIfNoneMatchParser;
@@ -2752,6 +3097,16 @@ impl AllowedMethodsWriter {
 		}
 	}
 }
+/// Write AllowedMethods contents as XML
+impl AllowedMethodsWriter {
+	fn write_xml(name: &str, obj: &AllowedMethods) -> String {
+		let mut xml = String::new();
+		for element in obj.iter() {
+			xml.push_str(&AllowedMethodWriter::write_xml(name, element));
+		}
+		xml
+	}
+}
 #[derive(Debug, Default)]
 pub struct DeleteObjectOutput {
 	/// Returns the version ID of the delete marker created as a result of the DELETE
@@ -2789,6 +3144,21 @@ impl DeleteObjectOutputParser {
 		Ok(obj)
 	}
 }
+/// Parse DeleteObjectOutput members bound to response headers
+impl DeleteObjectOutputParser {
+	fn parse_headers(headers: &Headers, obj: &mut DeleteObjectOutput) -> Result<(), XmlParseError> {
+		if let Some(value) = header_value(headers, "x-amz-delete-marker") {
+			obj.delete_marker = value == "true";
+		}
+		if let Some(value) = header_value(headers, "x-amz-version-id") {
+			obj.version_id = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-request-charged") {
+			obj.request_charged = value;
+		}
+		Ok(())
+	}
+}
 /// Write DeleteObjectOutput contents to a SignedRequest
 struct DeleteObjectOutputWriter;
 impl DeleteObjectOutputWriter {
@@ -2861,6 +3231,12 @@ impl BucketLogsPermissionWriter {
 		params.put(name, obj);
 	}
 }
+/// Write BucketLogsPermission contents as XML
+impl BucketLogsPermissionWriter {
+	fn write_xml(name: &str, obj: &BucketLogsPermission) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 #[derive(Debug, Default)]
 pub struct HeadObjectRequest {
 	/// Specifies the algorithm to use to when encrypting the object (e.g., AES256).
@@ -3018,6 +3394,12 @@ impl DisplayNameWriter {
 		params.put(name, obj);
 	}
 }
+/// Write DisplayName contents as XML
+impl DisplayNameWriter {
+	fn write_xml(name: &str, obj: &DisplayName) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 pub type GrantReadACP = String;
 /// Parse GrantReadACP from XML
 struct GrantReadACPParser;
@@ -3075,6 +3457,19 @@ impl GrantWriter {
 		PermissionWriter::write_params(params, &(prefix.to_string() + "Permission"), &obj.permission);
 	}
 }
+/// Write Grant contents as XML
+impl GrantWriter {
+	fn write_xml(name: &str, obj: &Grant) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		xml.push_str(&GranteeWriter::write_xml("Grantee", &obj.grantee));
+		if obj.permission != Permission::default() {
+			xml.push_str(&PermissionWriter::write_xml("Permission", &obj.permission));
+		}
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 #[derive(Debug, Default)]
 pub struct TopicConfigurationDeprecated {
 	/// Amazon SNS topic to which Amazon S3 will publish a message to report the
@@ -3128,6 +3523,27 @@ impl TopicConfigurationDeprecatedWriter {
 		EventListWriter::write_params(params, &(prefix.to_string() + "Event"), &obj.events);
 	}
 }
+/// Write TopicConfigurationDeprecated contents as XML
+impl TopicConfigurationDeprecatedWriter {
+	fn write_xml(name: &str, obj: &TopicConfigurationDeprecated) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		if obj.id != NotificationId::default() {
+			xml.push_str(&NotificationIdWriter::write_xml("Id", &obj.id));
+		}
+		if !obj.events.is_empty() {
+			xml.push_str(&EventListWriter::write_xml("Event", &obj.events));
+		}
+		if obj.event != Event::default() {
+			xml.push_str(&EventWriter::write_xml("Event", &obj.event));
+		}
+		if obj.topic != TopicArn::default() {
+			xml.push_str(&TopicArnWriter::write_xml("Topic", &obj.topic));
+		}
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 pub type CopySourceIfModifiedSince = String;
 /// Parse CopySourceIfModifiedSince from XML
 struct CopySourceIfModifiedSinceParser;
@@ -3205,6 +3621,12 @@ impl HttpRedirectCodeWriter {
 		params.put(name, obj);
 	}
 }
+/// Write HttpRedirectCode contents as XML
+impl HttpRedirectCodeWriter {
+	fn write_xml(name: &str, obj: &HttpRedirectCode) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 #[derive(Debug, Default)]
 pub struct ListObjectVersionsRequest {
 	pub bucket: BucketName,
@@ -3333,6 +3755,12 @@ impl TargetPrefixWriter {
 		params.put(name, obj);
 	}
 }
+/// Write TargetPrefix contents as XML
+impl TargetPrefixWriter {
+	fn write_xml(name: &str, obj: &TargetPrefix) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 #[derive(Debug, Default)]
 pub struct DeleteBucketPolicyRequest {
 	pub bucket: BucketName,
@@ -3383,10 +3811,16 @@ impl CloudFunctionInvocationRoleWriter {
 		params.put(name, obj);
 	}
 }
+/// Write CloudFunctionInvocationRole contents as XML
+impl CloudFunctionInvocationRoleWriter {
+	fn write_xml(name: &str, obj: &CloudFunctionInvocationRole) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 #[derive(Debug, Default)]
 pub struct HeadObjectOutput {
 	/// Last modified date of the object
-	pub last_modified: LastModified,
+	pub last_modified: Option<DateTime<UTC>>,
 	pub request_charged: RequestCharged,
 	/// Specifies what content encodings have been applied to the object and thus what
 	/// decoding mechanisms must be applied to obtain the media-type referenced by the
@@ -3410,7 +3844,7 @@ pub struct HeadObjectOutput {
 	/// the value of this header in the object metadata.
 	pub website_redirect_location: WebsiteRedirectLocation,
 	/// The date and time at which the object is no longer cacheable.
-	pub expires: Expires,
+	pub expires: Option<DateTime<UTC>>,
 	/// Specifies whether the object retrieved was (true) or was not (false) a Delete
 	/// Marker. If false, this response header does not appear in the response.
 	pub delete_marker: DeleteMarker,
@@ -3458,10 +3892,6 @@ impl HeadObjectOutputParser {
 		let mut obj = HeadObjectOutput::default();
 		loop {
 			let current_name = try!(peek_at_name(stack));
-			if current_name == "Last-Modified" {
-				obj.last_modified = try!(LastModifiedParser::parse_xml("Last-Modified", stack));
-				continue;
-			}
 			if current_name == "x-amz-request-charged" {
 				obj.request_charged = try!(RequestChargedParser::parse_xml("x-amz-request-charged", stack));
 				continue;
@@ -3502,10 +3932,6 @@ impl HeadObjectOutputParser {
 				obj.website_redirect_location = try!(WebsiteRedirectLocationParser::parse_xml("x-amz-website-redirect-location", stack));
 				continue;
 			}
-			if current_name == "Expires" {
-				obj.expires = try!(ExpiresParser::parse_xml("Expires", stack));
-				continue;
-			}
 			if current_name == "x-amz-delete-marker" {
 				obj.delete_marker = try!(DeleteMarkerParser::parse_xml("x-amz-delete-marker", stack));
 				continue;
@@ -3560,6 +3986,82 @@ impl HeadObjectOutputParser {
 		Ok(obj)
 	}
 }
+/// Parse HeadObjectOutput members bound to response headers
+impl HeadObjectOutputParser {
+	fn parse_headers(headers: &Headers, obj: &mut HeadObjectOutput) -> Result<(), XmlParseError> {
+		if let Some(value) = header_value(headers, "x-amz-delete-marker") {
+			obj.delete_marker = value == "true";
+		}
+		if let Some(value) = header_value(headers, "accept-ranges") {
+			obj.accept_ranges = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-expiration") {
+			obj.expiration = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-restore") {
+			obj.restore = value;
+		}
+		if let Some(value) = header_value(headers, "Last-Modified") {
+			obj.last_modified = conditional::parse_http_date(&value).ok();
+		}
+		if let Some(value) = header_value(headers, "Content-Length") {
+			obj.content_length = try!(i64::from_str(&value));
+		}
+		if let Some(value) = header_value(headers, "ETag") {
+			obj.e_tag = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-missing-meta") {
+			obj.missing_meta = try!(i32::from_str(&value));
+		}
+		if let Some(value) = header_value(headers, "x-amz-version-id") {
+			obj.version_id = value;
+		}
+		if let Some(value) = header_value(headers, "Cache-Control") {
+			obj.cache_control = value;
+		}
+		if let Some(value) = header_value(headers, "Content-Disposition") {
+			obj.content_disposition = value;
+		}
+		if let Some(value) = header_value(headers, "Content-Encoding") {
+			obj.content_encoding = value;
+		}
+		if let Some(value) = header_value(headers, "Content-Language") {
+			obj.content_language = value;
+		}
+		if let Some(value) = header_value(headers, "Content-Type") {
+			obj.content_type = value;
+		}
+		if let Some(value) = header_value(headers, "Expires") {
+			obj.expires = conditional::parse_http_date(&value).ok();
+		}
+		if let Some(value) = header_value(headers, "x-amz-website-redirect-location") {
+			obj.website_redirect_location = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-server-side-encryption") {
+			obj.server_side_encryption = value;
+		}
+		obj.metadata = headers_with_prefix(headers, "x-amz-meta-");
+		if let Some(value) = header_value(headers, "x-amz-server-side-encryption-customer-algorithm") {
+			obj.sse_customer_algorithm = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-server-side-encryption-customer-key-MD5") {
+			obj.sse_customer_key_md5 = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-server-side-encryption-aws-kms-key-id") {
+			obj.ssekms_key_id = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-storage-class") {
+			obj.storage_class = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-request-charged") {
+			obj.request_charged = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-replication-status") {
+			obj.replication_status = value;
+		}
+		Ok(())
+	}
+}
 /// Write HeadObjectOutput contents to a SignedRequest
 struct HeadObjectOutputWriter;
 impl HeadObjectOutputWriter {
@@ -3666,6 +4168,16 @@ impl ExposeHeadersWriter {
 		}
 	}
 }
+/// Write ExposeHeaders contents as XML
+impl ExposeHeadersWriter {
+	fn write_xml(name: &str, obj: &ExposeHeaders) -> String {
+		let mut xml = String::new();
+		for element in obj.iter() {
+			xml.push_str(&ExposeHeaderWriter::write_xml(name, element));
+		}
+		xml
+	}
+}
 #[derive(Debug, Default)]
 pub struct GetBucketLoggingOutput {
 	pub logging_enabled: LoggingEnabled,
@@ -3869,8 +4381,8 @@ impl ListMultipartUploadsOutputParser {
 				obj.upload_id_marker = try!(UploadIdMarkerParser::parse_xml("UploadIdMarker", stack));
 				continue;
 			}
-			if current_name == "CommonPrefix" {
-				obj.common_prefixes = try!(CommonPrefixListParser::parse_xml("CommonPrefix", stack));
+			if current_name == "CommonPrefixes" {
+				obj.common_prefixes.push(try!(CommonPrefixParser::parse_xml("CommonPrefixes", stack)));
 				continue;
 			}
 			if current_name == "NextKeyMarker" {
@@ -3979,6 +4491,12 @@ impl PermissionWriter {
 		params.put(name, obj);
 	}
 }
+/// Write Permission contents as XML
+impl PermissionWriter {
+	fn write_xml(name: &str, obj: &Permission) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 #[derive(Debug, Default)]
 pub struct RequestPaymentConfiguration {
 	/// Specifies who pays for the download and request fees.
@@ -4012,6 +4530,16 @@ impl RequestPaymentConfigurationWriter {
 		PayerWriter::write_params(params, &(prefix.to_string() + "Payer"), &obj.payer);
 	}
 }
+/// Write RequestPaymentConfiguration contents as XML
+impl RequestPaymentConfigurationWriter {
+	fn write_xml(name: &str, obj: &RequestPaymentConfiguration) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		xml.push_str(&PayerWriter::write_xml("Payer", &obj.payer));
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 pub type Grants = Vec<Grant>;
 /// Parse Grants from XML
 struct GrantsParser;
@@ -4036,6 +4564,17 @@ impl GrantsWriter {
 		}
 	}
 }
+/// Write Grants contents as XML
+impl GrantsWriter {
+	fn write_xml(name: &str, obj: &Grants) -> String {
+		let mut xml = format!("<{}>", name);
+		for element in obj.iter() {
+			xml.push_str(&GrantWriter::write_xml("Grant", element));
+		}
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 pub type ObjectStorageClass = String;
 /// Parse ObjectStorageClass from XML
 struct ObjectStorageClassParser;
@@ -4078,6 +4617,16 @@ impl EventListWriter {
 		}
 	}
 }
+/// Write EventList contents as XML
+impl EventListWriter {
+	fn write_xml(name: &str, obj: &EventList) -> String {
+		let mut xml = String::new();
+		for element in obj.iter() {
+			xml.push_str(&EventWriter::write_xml(name, element));
+		}
+		xml
+	}
+}
 pub type EmailAddress = String;
 /// Parse EmailAddress from XML
 struct EmailAddressParser;
@@ -4096,6 +4645,12 @@ impl EmailAddressWriter {
 		params.put(name, obj);
 	}
 }
+/// Write EmailAddress contents as XML
+impl EmailAddressWriter {
+	fn write_xml(name: &str, obj: &EmailAddress) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 #[derive(Debug, Default)]
 pub struct CreateMultipartUploadOutput {
 	/// If server-side encryption with a customer-provided encryption key was
@@ -4167,6 +4722,27 @@ impl CreateMultipartUploadOutputParser {
 		Ok(obj)
 	}
 }
+/// Parse CreateMultipartUploadOutput members bound to response headers
+impl CreateMultipartUploadOutputParser {
+	fn parse_headers(headers: &Headers, obj: &mut CreateMultipartUploadOutput) -> Result<(), XmlParseError> {
+		if let Some(value) = header_value(headers, "x-amz-server-side-encryption") {
+			obj.server_side_encryption = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-server-side-encryption-customer-algorithm") {
+			obj.sse_customer_algorithm = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-server-side-encryption-customer-key-MD5") {
+			obj.sse_customer_key_md5 = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-server-side-encryption-aws-kms-key-id") {
+			obj.ssekms_key_id = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-request-charged") {
+			obj.request_charged = value;
+		}
+		Ok(())
+	}
+}
 /// Write CreateMultipartUploadOutput contents to a SignedRequest
 struct CreateMultipartUploadOutputWriter;
 impl CreateMultipartUploadOutputWriter {
@@ -4291,6 +4867,12 @@ impl BucketNameWriter {
 		params.put(name, obj);
 	}
 }
+/// Write BucketName contents as XML
+impl BucketNameWriter {
+	fn write_xml(name: &str, obj: &BucketName) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 #[derive(Debug, Default)]
 pub struct TargetGrant {
 	pub grantee: Grantee,
@@ -4330,6 +4912,19 @@ impl TargetGrantWriter {
 		BucketLogsPermissionWriter::write_params(params, &(prefix.to_string() + "Permission"), &obj.permission);
 	}
 }
+/// Write TargetGrant contents as XML
+impl TargetGrantWriter {
+	fn write_xml(name: &str, obj: &TargetGrant) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		xml.push_str(&GranteeWriter::write_xml("Grantee", &obj.grantee));
+		if obj.permission != BucketLogsPermission::default() {
+			xml.push_str(&BucketLogsPermissionWriter::write_xml("Permission", &obj.permission));
+		}
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 pub type MetadataDirective = String;
 /// Parse MetadataDirective from XML
 struct MetadataDirectiveParser;
@@ -4560,11 +5155,35 @@ impl CloudFunctionConfigurationWriter {
 		EventWriter::write_params(params, &(prefix.to_string() + "Event"), &obj.event);
 	}
 }
-pub type LambdaFunctionArn = String;
-/// Parse LambdaFunctionArn from XML
-struct LambdaFunctionArnParser;
-impl LambdaFunctionArnParser {
-	fn parse_xml<'a, T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<LambdaFunctionArn, XmlParseError> {
+/// Write CloudFunctionConfiguration contents as XML
+impl CloudFunctionConfigurationWriter {
+	fn write_xml(name: &str, obj: &CloudFunctionConfiguration) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		if obj.id != NotificationId::default() {
+			xml.push_str(&NotificationIdWriter::write_xml("Id", &obj.id));
+		}
+		if obj.event != Event::default() {
+			xml.push_str(&EventWriter::write_xml("Event", &obj.event));
+		}
+		if !obj.events.is_empty() {
+			xml.push_str(&EventListWriter::write_xml("Event", &obj.events));
+		}
+		if obj.cloud_function != CloudFunction::default() {
+			xml.push_str(&CloudFunctionWriter::write_xml("CloudFunction", &obj.cloud_function));
+		}
+		if obj.invocation_role != CloudFunctionInvocationRole::default() {
+			xml.push_str(&CloudFunctionInvocationRoleWriter::write_xml("InvocationRole", &obj.invocation_role));
+		}
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
+pub type LambdaFunctionArn = String;
+/// Parse LambdaFunctionArn from XML
+struct LambdaFunctionArnParser;
+impl LambdaFunctionArnParser {
+	fn parse_xml<'a, T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<LambdaFunctionArn, XmlParseError> {
 		try!(start_element(tag_name, stack));
 		let obj = try!(characters(stack));
 		try!(end_element(tag_name, stack));
@@ -4578,6 +5197,12 @@ impl LambdaFunctionArnWriter {
 		params.put(name, obj);
 	}
 }
+/// Write LambdaFunctionArn contents as XML
+impl LambdaFunctionArnWriter {
+	fn write_xml(name: &str, obj: &LambdaFunctionArn) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 pub type Quiet = bool;
 /// Parse Quiet from XML
 struct QuietParser;
@@ -4596,6 +5221,12 @@ impl QuietWriter {
 		params.put(name, &obj.to_string());
 	}
 }
+/// Write Quiet contents as XML
+impl QuietWriter {
+	fn write_xml(name: &str, obj: &Quiet) -> String {
+		format!("<{}>{}</{}>", name, obj.to_string(), name)
+	}
+}
 #[derive(Debug, Default)]
 pub struct AccessControlPolicy {
 	pub owner: Owner,
@@ -4635,6 +5266,19 @@ impl AccessControlPolicyWriter {
 		GrantsWriter::write_params(params, &(prefix.to_string() + "Grant"), &obj.grants);
 	}
 }
+/// Write AccessControlPolicy contents as XML
+impl AccessControlPolicyWriter {
+	fn write_xml(name: &str, obj: &AccessControlPolicy) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		if !obj.grants.is_empty() {
+			xml.push_str(&GrantsWriter::write_xml("AccessControlList", &obj.grants));
+		}
+		xml.push_str(&OwnerWriter::write_xml("Owner", &obj.owner));
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 pub type Range = String;
 /// Parse Range from XML
 struct RangeParser;
@@ -4713,6 +5357,16 @@ impl ObjectIdentifierListWriter {
 		}
 	}
 }
+/// Write ObjectIdentifierList contents as XML
+impl ObjectIdentifierListWriter {
+	fn write_xml(name: &str, obj: &ObjectIdentifierList) -> String {
+		let mut xml = String::new();
+		for element in obj.iter() {
+			xml.push_str(&ObjectIdentifierWriter::write_xml(name, element));
+		}
+		xml
+	}
+}
 pub type Restore = String;
 /// Parse Restore from XML
 struct RestoreParser;
@@ -4791,6 +5445,19 @@ impl DeleteWriter {
 		}
 	}
 }
+/// Write Delete contents as XML
+impl DeleteWriter {
+	fn write_xml(name: &str, obj: &Delete) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		xml.push_str(&ObjectIdentifierListWriter::write_xml("Object", &obj.objects));
+		if let Some(ref obj) = obj.quiet {
+			xml.push_str(&QuietWriter::write_xml("Quiet", obj));
+		}
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 pub type ResponseContentLanguage = String;
 /// Parse ResponseContentLanguage from XML
 struct ResponseContentLanguageParser;
@@ -4873,12 +5540,12 @@ impl ListObjectsOutputParser {
 				obj.is_truncated = try!(IsTruncatedParser::parse_xml("IsTruncated", stack));
 				continue;
 			}
-			if current_name == "Object" {
-				obj.contents = try!(ObjectListParser::parse_xml("Object", stack));
+			if current_name == "Contents" {
+				obj.contents.push(try!(ObjectParser::parse_xml("Contents", stack)));
 				continue;
 			}
-			if current_name == "CommonPrefix" {
-				obj.common_prefixes = try!(CommonPrefixListParser::parse_xml("CommonPrefix", stack));
+			if current_name == "CommonPrefixes" {
+				obj.common_prefixes.push(try!(CommonPrefixParser::parse_xml("CommonPrefixes", stack)));
 				continue;
 			}
 			break;
@@ -4947,6 +5614,16 @@ impl CORSRulesWriter {
 		}
 	}
 }
+/// Write CORSRules contents as XML
+impl CORSRulesWriter {
+	fn write_xml(name: &str, obj: &CORSRules) -> String {
+		let mut xml = String::new();
+		for element in obj.iter() {
+			xml.push_str(&CORSRuleWriter::write_xml(name, element));
+		}
+		xml
+	}
+}
 pub type ContentLanguage = String;
 /// Parse ContentLanguage from XML
 struct ContentLanguageParser;
@@ -5209,6 +5886,20 @@ impl CORSRuleWriter {
 		AllowedOriginsWriter::write_params(params, &(prefix.to_string() + "AllowedOrigin"), &obj.allowed_origins);
 	}
 }
+/// Write CORSRule contents as XML
+impl CORSRuleWriter {
+	fn write_xml(name: &str, obj: &CORSRule) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		xml.push_str(&AllowedHeadersWriter::write_xml("AllowedHeader", &obj.allowed_headers));
+		xml.push_str(&AllowedMethodsWriter::write_xml("AllowedMethod", &obj.allowed_methods));
+		xml.push_str(&AllowedOriginsWriter::write_xml("AllowedOrigin", &obj.allowed_origins));
+		xml.push_str(&ExposeHeadersWriter::write_xml("ExposeHeader", &obj.expose_headers));
+		xml.push_str(&MaxAgeSecondsWriter::write_xml("MaxAgeSeconds", &obj.max_age_seconds));
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 #[derive(Debug, Default)]
 pub struct LoggingEnabled {
 	/// This element lets you specify a prefix for the keys that the log files will be
@@ -5261,6 +5952,18 @@ impl LoggingEnabledWriter {
 		TargetGrantsWriter::write_params(params, &(prefix.to_string() + "Grant"), &obj.target_grants);
 	}
 }
+/// Write LoggingEnabled contents as XML
+impl LoggingEnabledWriter {
+	fn write_xml(name: &str, obj: &LoggingEnabled) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		xml.push_str(&TargetBucketWriter::write_xml("TargetBucket", &obj.target_bucket));
+		xml.push_str(&TargetGrantsWriter::write_xml("TargetGrants", &obj.target_grants));
+		xml.push_str(&TargetPrefixWriter::write_xml("TargetPrefix", &obj.target_prefix));
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 pub type KeyPrefixEquals = String;
 /// Parse KeyPrefixEquals from XML
 struct KeyPrefixEqualsParser;
@@ -5279,6 +5982,12 @@ impl KeyPrefixEqualsWriter {
 		params.put(name, obj);
 	}
 }
+/// Write KeyPrefixEquals contents as XML
+impl KeyPrefixEqualsWriter {
+	fn write_xml(name: &str, obj: &KeyPrefixEquals) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 #[derive(Debug, Default)]
 pub struct RedirectAllRequestsTo {
 	/// Name of the host where requests will be redirected.
@@ -5322,6 +6031,19 @@ impl RedirectAllRequestsToWriter {
 		}
 	}
 }
+/// Write RedirectAllRequestsTo contents as XML
+impl RedirectAllRequestsToWriter {
+	fn write_xml(name: &str, obj: &RedirectAllRequestsTo) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		xml.push_str(&HostNameWriter::write_xml("HostName", &obj.host_name));
+		if let Some(ref obj) = obj.protocol {
+			xml.push_str(&ProtocolWriter::write_xml("Protocol", obj));
+		}
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 #[derive(Debug, Default)]
 pub struct Owner {
 	pub display_name: DisplayName,
@@ -5360,6 +6082,21 @@ impl OwnerWriter {
 		IDWriter::write_params(params, &(prefix.to_string() + "ID"), &obj.id);
 	}
 }
+/// Write Owner contents as XML
+impl OwnerWriter {
+	fn write_xml(name: &str, obj: &Owner) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		if obj.display_name != DisplayName::default() {
+			xml.push_str(&DisplayNameWriter::write_xml("DisplayName", &obj.display_name));
+		}
+		if obj.id != ID::default() {
+			xml.push_str(&IDWriter::write_xml("ID", &obj.id));
+		}
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 #[derive(Debug, Default)]
 pub struct CopyObjectResult {
 	pub last_modified: LastModified,
@@ -5491,6 +6228,12 @@ impl EventWriter {
 		params.put(name, obj);
 	}
 }
+/// Write Event contents as XML
+impl EventWriter {
+	fn write_xml(name: &str, obj: &Event) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 pub type ReplicationRules = Vec<ReplicationRule>;
 /// Parse ReplicationRules from XML
 struct ReplicationRulesParser;
@@ -5515,6 +6258,16 @@ impl ReplicationRulesWriter {
 		}
 	}
 }
+/// Write ReplicationRules contents as XML
+impl ReplicationRulesWriter {
+	fn write_xml(name: &str, obj: &ReplicationRules) -> String {
+		let mut xml = String::new();
+		for element in obj.iter() {
+			xml.push_str(&ReplicationRuleWriter::write_xml(name, element));
+		}
+		xml
+	}
+}
 /// Container for specifying the notification configuration of the bucket. If this
 /// element is empty, notifications are turned off on the bucket.
 #[derive(Debug, Default)]
@@ -5561,6 +6314,24 @@ impl NotificationConfigurationWriter {
 		TopicConfigurationListWriter::write_params(params, &(prefix.to_string() + "TopicConfiguration"), &obj.topic_configurations);
 	}
 }
+/// Write NotificationConfiguration contents as XML
+impl NotificationConfigurationWriter {
+	fn write_xml(name: &str, obj: &NotificationConfiguration) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		if !obj.topic_configurations.is_empty() {
+			xml.push_str(&TopicConfigurationListWriter::write_xml("TopicConfiguration", &obj.topic_configurations));
+		}
+		if !obj.queue_configurations.is_empty() {
+			xml.push_str(&QueueConfigurationListWriter::write_xml("QueueConfiguration", &obj.queue_configurations));
+		}
+		if !obj.lambda_function_configurations.is_empty() {
+			xml.push_str(&LambdaFunctionConfigurationListWriter::write_xml("CloudFunctionConfiguration", &obj.lambda_function_configurations));
+		}
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 #[derive(Debug, Default)]
 pub struct Object {
 	pub last_modified: LastModified,
@@ -5710,6 +6481,21 @@ impl WebsiteConfigurationWriter {
 		RoutingRulesWriter::write_params(params, &(prefix.to_string() + "RoutingRule"), &obj.routing_rules);
 	}
 }
+/// Write WebsiteConfiguration contents as XML
+impl WebsiteConfigurationWriter {
+	fn write_xml(name: &str, obj: &WebsiteConfiguration) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		xml.push_str(&ErrorDocumentWriter::write_xml("ErrorDocument", &obj.error_document));
+		xml.push_str(&IndexDocumentWriter::write_xml("IndexDocument", &obj.index_document));
+		xml.push_str(&RedirectAllRequestsToWriter::write_xml("RedirectAllRequestsTo", &obj.redirect_all_requests_to));
+		if !obj.routing_rules.is_empty() {
+			xml.push_str(&RoutingRulesWriter::write_xml("RoutingRules", &obj.routing_rules));
+		}
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 pub type MFADelete = String;
 /// Parse MFADelete from XML
 struct MFADeleteParser;
@@ -5728,6 +6514,12 @@ impl MFADeleteWriter {
 		params.put(name, obj);
 	}
 }
+/// Write MFADelete contents as XML
+impl MFADeleteWriter {
+	fn write_xml(name: &str, obj: &MFADelete) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 pub type CopySourceSSECustomerKey = String;
 /// Parse CopySourceSSECustomerKey from XML
 struct CopySourceSSECustomerKeyParser;
@@ -5835,6 +6627,27 @@ impl CompleteMultipartUploadOutputParser {
 		Ok(obj)
 	}
 }
+/// Parse CompleteMultipartUploadOutput members bound to response headers
+impl CompleteMultipartUploadOutputParser {
+	fn parse_headers(headers: &Headers, obj: &mut CompleteMultipartUploadOutput) -> Result<(), XmlParseError> {
+		if let Some(value) = header_value(headers, "x-amz-expiration") {
+			obj.expiration = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-server-side-encryption") {
+			obj.server_side_encryption = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-version-id") {
+			obj.version_id = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-server-side-encryption-aws-kms-key-id") {
+			obj.ssekms_key_id = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-request-charged") {
+			obj.request_charged = value;
+		}
+		Ok(())
+	}
+}
 /// Write CompleteMultipartUploadOutput contents to a SignedRequest
 struct CompleteMultipartUploadOutputWriter;
 impl CompleteMultipartUploadOutputWriter {
@@ -5870,6 +6683,12 @@ impl ExposeHeaderWriter {
 		params.put(name, obj);
 	}
 }
+/// Write ExposeHeader contents as XML
+impl ExposeHeaderWriter {
+	fn write_xml(name: &str, obj: &ExposeHeader) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 #[derive(Debug, Default)]
 pub struct CopyPartResult {
 	/// Date and time at which the object was uploaded.
@@ -5960,6 +6779,12 @@ impl HostNameWriter {
 		params.put(name, obj);
 	}
 }
+/// Write HostName contents as XML
+impl HostNameWriter {
+	fn write_xml(name: &str, obj: &HostName) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 pub type NextUploadIdMarker = String;
 /// Parse NextUploadIdMarker from XML
 struct NextUploadIdMarkerParser;
@@ -6054,6 +6879,33 @@ impl CopyObjectOutputParser {
 		Ok(obj)
 	}
 }
+/// Parse CopyObjectOutput members bound to response headers
+impl CopyObjectOutputParser {
+	fn parse_headers(headers: &Headers, obj: &mut CopyObjectOutput) -> Result<(), XmlParseError> {
+		if let Some(value) = header_value(headers, "x-amz-expiration") {
+			obj.expiration = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-copy-source-version-id") {
+			obj.copy_source_version_id = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-server-side-encryption") {
+			obj.server_side_encryption = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-server-side-encryption-customer-algorithm") {
+			obj.sse_customer_algorithm = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-server-side-encryption-customer-key-MD5") {
+			obj.sse_customer_key_md5 = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-server-side-encryption-aws-kms-key-id") {
+			obj.ssekms_key_id = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-request-charged") {
+			obj.request_charged = value;
+		}
+		Ok(())
+	}
+}
 /// Write CopyObjectOutput contents to a SignedRequest
 struct CopyObjectOutputWriter;
 impl CopyObjectOutputWriter {
@@ -6239,6 +7091,30 @@ impl UploadPartOutputParser {
 		Ok(obj)
 	}
 }
+/// Parse UploadPartOutput members bound to response headers
+impl UploadPartOutputParser {
+	fn parse_headers(headers: &Headers, obj: &mut UploadPartOutput) -> Result<(), XmlParseError> {
+		if let Some(value) = header_value(headers, "x-amz-server-side-encryption") {
+			obj.server_side_encryption = value;
+		}
+		if let Some(value) = header_value(headers, "ETag") {
+			obj.e_tag = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-server-side-encryption-customer-algorithm") {
+			obj.sse_customer_algorithm = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-server-side-encryption-customer-key-MD5") {
+			obj.sse_customer_key_md5 = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-server-side-encryption-aws-kms-key-id") {
+			obj.ssekms_key_id = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-request-charged") {
+			obj.request_charged = value;
+		}
+		Ok(())
+	}
+}
 /// Write UploadPartOutput contents to a SignedRequest
 struct UploadPartOutputWriter;
 impl UploadPartOutputWriter {
@@ -6295,6 +7171,16 @@ impl QueueConfigurationListWriter {
 		}
 	}
 }
+/// Write QueueConfigurationList contents as XML
+impl QueueConfigurationListWriter {
+	fn write_xml(name: &str, obj: &QueueConfigurationList) -> String {
+		let mut xml = String::new();
+		for element in obj.iter() {
+			xml.push_str(&QueueConfigurationWriter::write_xml(name, element));
+		}
+		xml
+	}
+}
 /// The source object of the COPY operation is not in the active tier and is only
 /// stored in Amazon Glacier.
 #[derive(Debug, Default)]
@@ -6336,6 +7222,12 @@ impl TransitionStorageClassWriter {
 		params.put(name, obj);
 	}
 }
+/// Write TransitionStorageClass contents as XML
+impl TransitionStorageClassWriter {
+	fn write_xml(name: &str, obj: &TransitionStorageClass) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 pub type DeleteMarker = bool;
 /// Parse DeleteMarker from XML
 struct DeleteMarkerParser;
@@ -6437,6 +7329,32 @@ impl RuleWriter {
 		}
 	}
 }
+/// Write Rule contents as XML
+impl RuleWriter {
+	fn write_xml(name: &str, obj: &Rule) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		if let Some(ref obj) = obj.expiration {
+			xml.push_str(&LifecycleExpirationWriter::write_xml("Expiration", obj));
+		}
+		if let Some(ref obj) = obj.id {
+			xml.push_str(&IDWriter::write_xml("ID", obj));
+		}
+		xml.push_str(&PrefixWriter::write_xml("Prefix", &obj.prefix));
+		xml.push_str(&ExpirationStatusWriter::write_xml("Status", &obj.status));
+		if let Some(ref obj) = obj.transition {
+			xml.push_str(&TransitionWriter::write_xml("Transition", obj));
+		}
+		if let Some(ref obj) = obj.noncurrent_version_transition {
+			xml.push_str(&NoncurrentVersionTransitionWriter::write_xml("NoncurrentVersionTransition", obj));
+		}
+		if let Some(ref obj) = obj.noncurrent_version_expiration {
+			xml.push_str(&NoncurrentVersionExpirationWriter::write_xml("NoncurrentVersionExpiration", obj));
+		}
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 pub type RoutingRules = Vec<RoutingRule>;
 /// Parse RoutingRules from XML
 struct RoutingRulesParser;
@@ -6461,6 +7379,17 @@ impl RoutingRulesWriter {
 		}
 	}
 }
+/// Write RoutingRules contents as XML
+impl RoutingRulesWriter {
+	fn write_xml(name: &str, obj: &RoutingRules) -> String {
+		let mut xml = format!("<{}>", name);
+		for element in obj.iter() {
+			xml.push_str(&RoutingRuleWriter::write_xml("RoutingRule", element));
+		}
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 #[derive(Debug, Default)]
 pub struct ReplicationRule {
 	/// The rule is ignored if status is not Enabled.
@@ -6519,6 +7448,21 @@ impl ReplicationRuleWriter {
 		}
 	}
 }
+/// Write ReplicationRule contents as XML
+impl ReplicationRuleWriter {
+	fn write_xml(name: &str, obj: &ReplicationRule) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		if let Some(ref obj) = obj.id {
+			xml.push_str(&IDWriter::write_xml("ID", obj));
+		}
+		xml.push_str(&PrefixWriter::write_xml("Prefix", &obj.prefix));
+		xml.push_str(&ReplicationRuleStatusWriter::write_xml("Status", &obj.status));
+		xml.push_str(&DestinationWriter::write_xml("Destination", &obj.destination));
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 pub type Date = String;
 /// Parse Date from XML
 struct DateParser;
@@ -6537,6 +7481,12 @@ impl DateWriter {
 		params.put(name, obj);
 	}
 }
+/// Write Date contents as XML
+impl DateWriter {
+	fn write_xml(name: &str, obj: &Date) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 pub type CacheControl = String;
 /// Parse CacheControl from XML
 struct CacheControlParser;
@@ -6573,6 +7523,12 @@ impl AllowedOriginWriter {
 		params.put(name, obj);
 	}
 }
+/// Write AllowedOrigin contents as XML
+impl AllowedOriginWriter {
+	fn write_xml(name: &str, obj: &AllowedOrigin) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 pub type IfModifiedSince = String;
 /// Parse IfModifiedSince from XML
 struct IfModifiedSinceParser;
@@ -6641,6 +7597,21 @@ impl ConditionWriter {
 		KeyPrefixEqualsWriter::write_params(params, &(prefix.to_string() + "KeyPrefixEquals"), &obj.key_prefix_equals);
 	}
 }
+/// Write Condition contents as XML
+impl ConditionWriter {
+	fn write_xml(name: &str, obj: &Condition) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		if obj.http_error_code_returned_equals != HttpErrorCodeReturnedEquals::default() {
+			xml.push_str(&HttpErrorCodeReturnedEqualsWriter::write_xml("HttpErrorCodeReturnedEquals", &obj.http_error_code_returned_equals));
+		}
+		if obj.key_prefix_equals != KeyPrefixEquals::default() {
+			xml.push_str(&KeyPrefixEqualsWriter::write_xml("KeyPrefixEquals", &obj.key_prefix_equals));
+		}
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 #[derive(Debug, Default)]
 pub struct DeleteObjectsOutput {
 	pub deleted: DeletedObjects,
@@ -6656,12 +7627,12 @@ impl DeleteObjectsOutputParser {
 		let mut obj = DeleteObjectsOutput::default();
 		loop {
 			let current_name = try!(peek_at_name(stack));
-			if current_name == "DeletedObject" {
-				obj.deleted = try!(DeletedObjectsParser::parse_xml("DeletedObject", stack));
+			if current_name == "Deleted" {
+				obj.deleted.push(try!(DeletedObjectParser::parse_xml("Deleted", stack)));
 				continue;
 			}
 			if current_name == "Error" {
-				obj.errors = try!(ErrorsParser::parse_xml("Error", stack));
+				obj.errors.push(try!(S3ClientErrorParser::parse_xml("Error", stack)));
 				continue;
 			}
 			if current_name == "x-amz-request-charged" {
@@ -6674,6 +7645,15 @@ impl DeleteObjectsOutputParser {
 		Ok(obj)
 	}
 }
+/// Parse DeleteObjectsOutput members bound to response headers
+impl DeleteObjectsOutputParser {
+	fn parse_headers(headers: &Headers, obj: &mut DeleteObjectsOutput) -> Result<(), XmlParseError> {
+		if let Some(value) = header_value(headers, "x-amz-request-charged") {
+			obj.request_charged = value;
+		}
+		Ok(())
+	}
+}
 /// Write DeleteObjectsOutput contents to a SignedRequest
 struct DeleteObjectsOutputWriter;
 impl DeleteObjectsOutputWriter {
@@ -6718,6 +7698,16 @@ impl ErrorDocumentWriter {
 		ObjectKeyWriter::write_params(params, &(prefix.to_string() + "Key"), &obj.key);
 	}
 }
+/// Write ErrorDocument contents as XML
+impl ErrorDocumentWriter {
+	fn write_xml(name: &str, obj: &ErrorDocument) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		xml.push_str(&ObjectKeyWriter::write_xml("Key", &obj.key));
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 pub type Payer = String;
 /// Parse Payer from XML
 struct PayerParser;
@@ -6736,6 +7726,12 @@ impl PayerWriter {
 		params.put(name, obj);
 	}
 }
+/// Write Payer contents as XML
+impl PayerWriter {
+	fn write_xml(name: &str, obj: &Payer) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 #[derive(Debug, Default)]
 pub struct PutBucketLifecycleRequest {
 	pub lifecycle_configuration: Option<LifecycleConfiguration>,
@@ -6812,6 +7808,15 @@ impl GetObjectTorrentOutputParser {
 		Ok(obj)
 	}
 }
+/// Parse GetObjectTorrentOutput members bound to response headers
+impl GetObjectTorrentOutputParser {
+	fn parse_headers(headers: &Headers, obj: &mut GetObjectTorrentOutput) -> Result<(), XmlParseError> {
+		if let Some(value) = header_value(headers, "x-amz-request-charged") {
+			obj.request_charged = value;
+		}
+		Ok(())
+	}
+}
 /// Write GetObjectTorrentOutput contents to a SignedRequest
 struct GetObjectTorrentOutputWriter;
 impl GetObjectTorrentOutputWriter {
@@ -6822,13 +7827,13 @@ impl GetObjectTorrentOutputWriter {
 		RequestChargedWriter::write_params(params, &(prefix.to_string() + "x-amz-request-charged"), &obj.request_charged);
 	}
 }
-pub type ContentLength = i32;
+pub type ContentLength = i64;
 /// Parse ContentLength from XML
 struct ContentLengthParser;
 impl ContentLengthParser {
 	fn parse_xml<'a, T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<ContentLength, XmlParseError> {
 		try!(start_element(tag_name, stack));
-		let obj = i32::from_str(try!(characters(stack)).as_ref()).unwrap();
+		let obj = i64::from_str(try!(characters(stack)).as_ref()).unwrap();
 		try!(end_element(tag_name, stack));
 		Ok(obj)
 	}
@@ -6889,6 +7894,24 @@ impl TransitionWriter {
 		TransitionStorageClassWriter::write_params(params, &(prefix.to_string() + "StorageClass"), &obj.storage_class);
 	}
 }
+/// Write Transition contents as XML
+impl TransitionWriter {
+	fn write_xml(name: &str, obj: &Transition) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		if obj.date != Date::default() {
+			xml.push_str(&DateWriter::write_xml("Date", &obj.date));
+		}
+		if obj.days != Days::default() {
+			xml.push_str(&DaysWriter::write_xml("Days", &obj.days));
+		}
+		if obj.storage_class != TransitionStorageClass::default() {
+			xml.push_str(&TransitionStorageClassWriter::write_xml("StorageClass", &obj.storage_class));
+		}
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 #[derive(Debug, Default)]
 pub struct QueueConfigurationDeprecated {
 	pub queue: QueueArn,
@@ -6939,10 +7962,31 @@ impl QueueConfigurationDeprecatedWriter {
 		EventWriter::write_params(params, &(prefix.to_string() + "Event"), &obj.event);
 	}
 }
+/// Write QueueConfigurationDeprecated contents as XML
+impl QueueConfigurationDeprecatedWriter {
+	fn write_xml(name: &str, obj: &QueueConfigurationDeprecated) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		if obj.id != NotificationId::default() {
+			xml.push_str(&NotificationIdWriter::write_xml("Id", &obj.id));
+		}
+		if obj.event != Event::default() {
+			xml.push_str(&EventWriter::write_xml("Event", &obj.event));
+		}
+		if !obj.events.is_empty() {
+			xml.push_str(&EventListWriter::write_xml("Event", &obj.events));
+		}
+		if obj.queue != QueueArn::default() {
+			xml.push_str(&QueueArnWriter::write_xml("Queue", &obj.queue));
+		}
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 #[derive(Debug, Default)]
 pub struct GetObjectOutput {
 	/// Last modified date of the object
-	pub last_modified: LastModified,
+	pub last_modified: Option<DateTime<UTC>>,
 	/// The portion of the object returned in the response.
 	pub content_range: ContentRange,
 	pub request_charged: RequestCharged,
@@ -6970,7 +8014,7 @@ pub struct GetObjectOutput {
 	/// the value of this header in the object metadata.
 	pub website_redirect_location: WebsiteRedirectLocation,
 	/// The date and time at which the object is no longer cacheable.
-	pub expires: Expires,
+	pub expires: Option<DateTime<UTC>>,
 	/// Specifies whether the object retrieved was (true) or was not (false) a Delete
 	/// Marker. If false, this response header does not appear in the response.
 	pub delete_marker: DeleteMarker,
@@ -7008,6 +8052,14 @@ pub struct GetObjectOutput {
 	/// requested, the response will include this header to provide round trip message
 	/// integrity verification of the customer-provided encryption key.
 	pub sse_customer_key_md5: SSECustomerKeyMD5,
+	/// The base64-encoded, 32-bit CRC32 checksum of the object.
+	pub checksum_crc32: ChecksumCRC32,
+	/// The base64-encoded, 32-bit CRC32C checksum of the object.
+	pub checksum_crc32_c: ChecksumCRC32C,
+	/// The base64-encoded, 160-bit SHA-1 digest of the object.
+	pub checksum_sha1: ChecksumSHA1,
+	/// The base64-encoded, 256-bit SHA-256 digest of the object.
+	pub checksum_sha256: ChecksumSHA256,
 }
 
 /// Parse GetObjectOutput from XML
@@ -7018,10 +8070,6 @@ impl GetObjectOutputParser {
 		let mut obj = GetObjectOutput::default();
 		loop {
 			let current_name = try!(peek_at_name(stack));
-			if current_name == "Last-Modified" {
-				obj.last_modified = try!(LastModifiedParser::parse_xml("Last-Modified", stack));
-				continue;
-			}
 			if current_name == "Content-Range" {
 				obj.content_range = try!(ContentRangeParser::parse_xml("Content-Range", stack));
 				continue;
@@ -7070,10 +8118,6 @@ impl GetObjectOutputParser {
 				obj.website_redirect_location = try!(WebsiteRedirectLocationParser::parse_xml("x-amz-website-redirect-location", stack));
 				continue;
 			}
-			if current_name == "Expires" {
-				obj.expires = try!(ExpiresParser::parse_xml("Expires", stack));
-				continue;
-			}
 			if current_name == "x-amz-delete-marker" {
 				obj.delete_marker = try!(DeleteMarkerParser::parse_xml("x-amz-delete-marker", stack));
 				continue;
@@ -7122,12 +8166,119 @@ impl GetObjectOutputParser {
 				obj.sse_customer_key_md5 = try!(SSECustomerKeyMD5Parser::parse_xml("x-amz-server-side-encryption-customer-key-MD5", stack));
 				continue;
 			}
+			if current_name == "x-amz-checksum-crc32" {
+				obj.checksum_crc32 = try!(ChecksumCRC32Parser::parse_xml("x-amz-checksum-crc32", stack));
+				continue;
+			}
+			if current_name == "x-amz-checksum-crc32c" {
+				obj.checksum_crc32_c = try!(ChecksumCRC32CParser::parse_xml("x-amz-checksum-crc32c", stack));
+				continue;
+			}
+			if current_name == "x-amz-checksum-sha1" {
+				obj.checksum_sha1 = try!(ChecksumSHA1Parser::parse_xml("x-amz-checksum-sha1", stack));
+				continue;
+			}
+			if current_name == "x-amz-checksum-sha256" {
+				obj.checksum_sha256 = try!(ChecksumSHA256Parser::parse_xml("x-amz-checksum-sha256", stack));
+				continue;
+			}
 			break;
 		}
 		try!(end_element(tag_name, stack));
 		Ok(obj)
 	}
 }
+/// Parse GetObjectOutput members bound to response headers
+impl GetObjectOutputParser {
+	fn parse_headers(headers: &Headers, obj: &mut GetObjectOutput) -> Result<(), XmlParseError> {
+		if let Some(value) = header_value(headers, "x-amz-delete-marker") {
+			obj.delete_marker = value == "true";
+		}
+		if let Some(value) = header_value(headers, "accept-ranges") {
+			obj.accept_ranges = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-expiration") {
+			obj.expiration = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-restore") {
+			obj.restore = value;
+		}
+		if let Some(value) = header_value(headers, "Last-Modified") {
+			obj.last_modified = conditional::parse_http_date(&value).ok();
+		}
+		if let Some(value) = header_value(headers, "Content-Length") {
+			obj.content_length = try!(i64::from_str(&value));
+		}
+		if let Some(value) = header_value(headers, "ETag") {
+			obj.e_tag = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-missing-meta") {
+			obj.missing_meta = try!(i32::from_str(&value));
+		}
+		if let Some(value) = header_value(headers, "x-amz-version-id") {
+			obj.version_id = value;
+		}
+		if let Some(value) = header_value(headers, "Cache-Control") {
+			obj.cache_control = value;
+		}
+		if let Some(value) = header_value(headers, "Content-Disposition") {
+			obj.content_disposition = value;
+		}
+		if let Some(value) = header_value(headers, "Content-Encoding") {
+			obj.content_encoding = value;
+		}
+		if let Some(value) = header_value(headers, "Content-Language") {
+			obj.content_language = value;
+		}
+		if let Some(value) = header_value(headers, "Content-Range") {
+			obj.content_range = value;
+		}
+		if let Some(value) = header_value(headers, "Content-Type") {
+			obj.content_type = value;
+		}
+		if let Some(value) = header_value(headers, "Expires") {
+			obj.expires = conditional::parse_http_date(&value).ok();
+		}
+		if let Some(value) = header_value(headers, "x-amz-website-redirect-location") {
+			obj.website_redirect_location = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-server-side-encryption") {
+			obj.server_side_encryption = value;
+		}
+		obj.metadata = headers_with_prefix(headers, "x-amz-meta-");
+		if let Some(value) = header_value(headers, "x-amz-server-side-encryption-customer-algorithm") {
+			obj.sse_customer_algorithm = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-server-side-encryption-customer-key-MD5") {
+			obj.sse_customer_key_md5 = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-server-side-encryption-aws-kms-key-id") {
+			obj.ssekms_key_id = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-storage-class") {
+			obj.storage_class = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-request-charged") {
+			obj.request_charged = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-replication-status") {
+			obj.replication_status = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-checksum-crc32") {
+			obj.checksum_crc32 = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-checksum-crc32c") {
+			obj.checksum_crc32_c = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-checksum-sha1") {
+			obj.checksum_sha1 = value;
+		}
+		if let Some(value) = header_value(headers, "x-amz-checksum-sha256") {
+			obj.checksum_sha256 = value;
+		}
+		Ok(())
+	}
+}
 /// Write GetObjectOutput contents to a SignedRequest
 struct GetObjectOutputWriter;
 impl GetObjectOutputWriter {
@@ -7160,6 +8311,10 @@ impl GetObjectOutputWriter {
 		ObjectVersionIdWriter::write_params(params, &(prefix.to_string() + "x-amz-version-id"), &obj.version_id);
 		ETagWriter::write_params(params, &(prefix.to_string() + "ETag"), &obj.e_tag);
 		SSECustomerKeyMD5Writer::write_params(params, &(prefix.to_string() + "x-amz-server-side-encryption-customer-key-MD5"), &obj.sse_customer_key_md5);
+		ChecksumCRC32Writer::write_params(params, &(prefix.to_string() + "x-amz-checksum-crc32"), &obj.checksum_crc32);
+		ChecksumCRC32CWriter::write_params(params, &(prefix.to_string() + "x-amz-checksum-crc32c"), &obj.checksum_crc32_c);
+		ChecksumSHA1Writer::write_params(params, &(prefix.to_string() + "x-amz-checksum-sha1"), &obj.checksum_sha1);
+		ChecksumSHA256Writer::write_params(params, &(prefix.to_string() + "x-amz-checksum-sha256"), &obj.checksum_sha256);
 	}
 }
 #[derive(Debug, Default)]
@@ -7294,6 +8449,16 @@ impl BucketLoggingStatusWriter {
 		LoggingEnabledWriter::write_params(params, &(prefix.to_string() + "LoggingEnabled"), &obj.logging_enabled);
 	}
 }
+/// Write BucketLoggingStatus contents as XML
+impl BucketLoggingStatusWriter {
+	fn write_xml(name: &str, obj: &BucketLoggingStatus) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		xml.push_str(&LoggingEnabledWriter::write_xml("LoggingEnabled", &obj.logging_enabled));
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 pub type IsLatest = bool;
 /// Parse IsLatest from XML
 struct IsLatestParser;
@@ -7377,6 +8542,19 @@ impl RoutingRuleWriter {
 		}
 	}
 }
+/// Write RoutingRule contents as XML
+impl RoutingRuleWriter {
+	fn write_xml(name: &str, obj: &RoutingRule) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		if let Some(ref obj) = obj.condition {
+			xml.push_str(&ConditionWriter::write_xml("Condition", obj));
+		}
+		xml.push_str(&RedirectWriter::write_xml("Redirect", &obj.redirect));
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 pub type MissingMeta = i32;
 /// Parse MissingMeta from XML
 struct MissingMetaParser;
@@ -7437,6 +8615,16 @@ impl AllowedOriginsWriter {
 		}
 	}
 }
+/// Write AllowedOrigins contents as XML
+impl AllowedOriginsWriter {
+	fn write_xml(name: &str, obj: &AllowedOrigins) -> String {
+		let mut xml = String::new();
+		for element in obj.iter() {
+			xml.push_str(&AllowedOriginWriter::write_xml(name, element));
+		}
+		xml
+	}
+}
 /// The specified multipart upload does not exist.
 #[derive(Debug, Default)]
 pub struct NoSuchUpload;
@@ -7524,6 +8712,27 @@ pub struct PutObjectRequest<'a> {
 	/// Amazon S3 uses this header for a message integrity check to ensure the
 	/// encryption key was transmitted without error.
 	pub sse_customer_key_md5: Option<SSECustomerKeyMD5>,
+	/// The base64-encoded, 32-bit CRC32 checksum of the object. Amazon S3 rejects the
+	/// object if it doesn't match.
+	pub checksum_crc32: Option<ChecksumCRC32>,
+	/// The base64-encoded, 32-bit CRC32C checksum of the object. Amazon S3 rejects the
+	/// object if it doesn't match.
+	pub checksum_crc32_c: Option<ChecksumCRC32C>,
+	/// The base64-encoded, 160-bit SHA-1 digest of the object. Amazon S3 rejects the
+	/// object if it doesn't match.
+	pub checksum_sha1: Option<ChecksumSHA1>,
+	/// The base64-encoded, 256-bit SHA-256 digest of the object. Amazon S3 rejects the
+	/// object if it doesn't match.
+	pub checksum_sha256: Option<ChecksumSHA256>,
+	/// The tag-set for the object, encoded as URL query parameters (for example,
+	/// "Key1=Value1").
+	pub tagging: Option<TaggingHeader>,
+	/// Uploads the object only if the one already there has this ETag, otherwise
+	/// returns 412 (precondition failed).
+	pub if_match: Option<IfMatch>,
+	/// Uploads the object only if there isn't one already when set to "*", otherwise
+	/// returns 412 (precondition failed).
+	pub if_none_match: Option<IfNoneMatch>,
 }
 
 pub type Code = String;
@@ -7586,6 +8795,16 @@ impl AllowedHeadersWriter {
 		}
 	}
 }
+/// Write AllowedHeaders contents as XML
+impl AllowedHeadersWriter {
+	fn write_xml(name: &str, obj: &AllowedHeaders) -> String {
+		let mut xml = String::new();
+		for element in obj.iter() {
+			xml.push_str(&AllowedHeaderWriter::write_xml(name, element));
+		}
+		xml
+	}
+}
 #[derive(Debug, Default)]
 pub struct Tagging {
 	pub tag_set: TagSet,
@@ -7618,6 +8837,16 @@ impl TaggingWriter {
 		TagSetWriter::write_params(params, &(prefix.to_string() + "Tag"), &obj.tag_set);
 	}
 }
+/// Write Tagging contents as XML
+impl TaggingWriter {
+	fn write_xml(name: &str, obj: &Tagging) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		xml.push_str(&TagSetWriter::write_xml("TagSet", &obj.tag_set));
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 pub type ContentMD5 = String;
 /// Parse ContentMD5 from XML
 struct ContentMD5Parser;
@@ -7756,12 +8985,12 @@ impl ListObjectVersionsOutputParser {
 				obj.name = try!(BucketNameParser::parse_xml("Name", stack));
 				continue;
 			}
-			if current_name == "ObjectVersion" {
-				obj.versions = try!(ObjectVersionListParser::parse_xml("ObjectVersion", stack));
+			if current_name == "Version" {
+				obj.versions.push(try!(ObjectVersionParser::parse_xml("Version", stack)));
 				continue;
 			}
-			if current_name == "DeleteMarkerEntry" {
-				obj.delete_markers = try!(DeleteMarkersParser::parse_xml("DeleteMarkerEntry", stack));
+			if current_name == "DeleteMarker" {
+				obj.delete_markers.push(try!(DeleteMarkerEntryParser::parse_xml("DeleteMarker", stack)));
 				continue;
 			}
 			if current_name == "NextKeyMarker" {
@@ -7800,8 +9029,8 @@ impl ListObjectVersionsOutputParser {
 				obj.version_id_marker = try!(VersionIdMarkerParser::parse_xml("VersionIdMarker", stack));
 				continue;
 			}
-			if current_name == "CommonPrefix" {
-				obj.common_prefixes = try!(CommonPrefixListParser::parse_xml("CommonPrefix", stack));
+			if current_name == "CommonPrefixes" {
+				obj.common_prefixes.push(try!(CommonPrefixParser::parse_xml("CommonPrefixes", stack)));
 				continue;
 			}
 			break;
@@ -7887,6 +9116,12 @@ impl PrefixWriter {
 		params.put(name, obj);
 	}
 }
+/// Write Prefix contents as XML
+impl PrefixWriter {
+	fn write_xml(name: &str, obj: &Prefix) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 pub type Parts = Vec<Part>;
 /// Parse Parts from XML
 struct PartsParser;
@@ -8328,7 +9563,7 @@ impl ListPartsOutputParser {
 				continue;
 			}
 			if current_name == "Part" {
-				obj.parts = try!(PartsParser::parse_xml("Part", stack));
+				obj.parts.push(try!(PartParser::parse_xml("Part", stack)));
 				continue;
 			}
 			if current_name == "UploadId" {
@@ -8369,9 +9604,18 @@ impl ListPartsOutputParser {
 		Ok(obj)
 	}
 }
-/// Write ListPartsOutput contents to a SignedRequest
-struct ListPartsOutputWriter;
-impl ListPartsOutputWriter {
+/// Parse ListPartsOutput members bound to response headers
+impl ListPartsOutputParser {
+	fn parse_headers(headers: &Headers, obj: &mut ListPartsOutput) -> Result<(), XmlParseError> {
+		if let Some(value) = header_value(headers, "x-amz-request-charged") {
+			obj.request_charged = value;
+		}
+		Ok(())
+	}
+}
+/// Write ListPartsOutput contents to a SignedRequest
+struct ListPartsOutputWriter;
+impl ListPartsOutputWriter {
 	fn write_params(params: &mut Params, name: &str, obj: &ListPartsOutput) {
 		let mut prefix = name.to_string();
 		if prefix != "" { prefix.push_str("."); }
@@ -8458,6 +9702,18 @@ impl RestoreRequestWriter {
 		DaysWriter::write_params(params, &(prefix.to_string() + "Days"), &obj.days);
 	}
 }
+/// Write RestoreRequest contents as XML
+impl RestoreRequestWriter {
+	fn write_xml(name: &str, obj: &RestoreRequest) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		if obj.days != Days::default() {
+			xml.push_str(&DaysWriter::write_xml("Days", &obj.days));
+		}
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 #[derive(Debug, Default)]
 pub struct CompletedPart {
 	/// Part number that identifies the part. This is a positive integer between 1 and
@@ -8517,6 +9773,12 @@ impl QueueArnWriter {
 		params.put(name, obj);
 	}
 }
+/// Write QueueArn contents as XML
+impl QueueArnWriter {
+	fn write_xml(name: &str, obj: &QueueArn) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 pub type Location = String;
 /// Parse Location from XML
 struct LocationParser;
@@ -8553,6 +9815,12 @@ impl HttpErrorCodeReturnedEqualsWriter {
 		params.put(name, obj);
 	}
 }
+/// Write HttpErrorCodeReturnedEquals contents as XML
+impl HttpErrorCodeReturnedEqualsWriter {
+	fn write_xml(name: &str, obj: &HttpErrorCodeReturnedEquals) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 #[derive(Debug, Default)]
 pub struct NotificationConfigurationDeprecated {
 	pub cloud_function_configuration: CloudFunctionConfiguration,
@@ -8597,6 +9865,18 @@ impl NotificationConfigurationDeprecatedWriter {
 		TopicConfigurationDeprecatedWriter::write_params(params, &(prefix.to_string() + "TopicConfiguration"), &obj.topic_configuration);
 	}
 }
+/// Write NotificationConfigurationDeprecated contents as XML
+impl NotificationConfigurationDeprecatedWriter {
+	fn write_xml(name: &str, obj: &NotificationConfigurationDeprecated) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		xml.push_str(&TopicConfigurationDeprecatedWriter::write_xml("TopicConfiguration", &obj.topic_configuration));
+		xml.push_str(&QueueConfigurationDeprecatedWriter::write_xml("QueueConfiguration", &obj.queue_configuration));
+		xml.push_str(&CloudFunctionConfigurationWriter::write_xml("CloudFunctionConfiguration", &obj.cloud_function_configuration));
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 #[derive(Debug, Default)]
 pub struct UploadPartCopyRequest {
 	/// Copies the object if its entity tag (ETag) matches the specified tag.
@@ -8814,6 +10094,16 @@ impl CORSConfigurationWriter {
 		CORSRulesWriter::write_params(params, &(prefix.to_string() + "CORSRule"), &obj.cors_rules);
 	}
 }
+/// Write CORSConfiguration contents as XML
+impl CORSConfigurationWriter {
+	fn write_xml(name: &str, obj: &CORSConfiguration) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		xml.push_str(&CORSRulesWriter::write_xml("CORSRule", &obj.cors_rules));
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 pub type LastModified = String;
 /// Parse LastModified from XML
 struct LastModifiedParser;
@@ -8919,6 +10209,29 @@ impl GranteeWriter {
 		}
 	}
 }
+/// Write Grantee contents as XML
+impl GranteeWriter {
+	fn write_xml(name: &str, obj: &Grantee) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(" xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\"");
+		xml.push_str(&format!(" xsi:type=\"{}\"", escape_xml(&obj.foo_type)));
+		xml.push_str(">");
+		if let Some(ref obj) = obj.display_name {
+			xml.push_str(&DisplayNameWriter::write_xml("DisplayName", obj));
+		}
+		if let Some(ref obj) = obj.email_address {
+			xml.push_str(&EmailAddressWriter::write_xml("EmailAddress", obj));
+		}
+		if let Some(ref obj) = obj.id {
+			xml.push_str(&IDWriter::write_xml("ID", obj));
+		}
+		if let Some(ref obj) = obj.uri {
+			xml.push_str(&URIWriter::write_xml("URI", obj));
+		}
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 pub type ExpirationStatus = String;
 /// Parse ExpirationStatus from XML
 struct ExpirationStatusParser;
@@ -8937,6 +10250,12 @@ impl ExpirationStatusWriter {
 		params.put(name, obj);
 	}
 }
+/// Write ExpirationStatus contents as XML
+impl ExpirationStatusWriter {
+	fn write_xml(name: &str, obj: &ExpirationStatus) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 pub type CopySourceIfUnmodifiedSince = String;
 /// Parse CopySourceIfUnmodifiedSince from XML
 struct CopySourceIfUnmodifiedSinceParser;
@@ -9096,6 +10415,17 @@ impl TagSetWriter {
 		}
 	}
 }
+/// Write TagSet contents as XML
+impl TagSetWriter {
+	fn write_xml(name: &str, obj: &TagSet) -> String {
+		let mut xml = format!("<{}>", name);
+		for element in obj.iter() {
+			xml.push_str(&TagWriter::write_xml("Tag", element));
+		}
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 pub type LambdaFunctionConfigurationList = Vec<LambdaFunctionConfiguration>;
 /// Parse LambdaFunctionConfigurationList from XML
 struct LambdaFunctionConfigurationListParser;
@@ -9120,6 +10450,16 @@ impl LambdaFunctionConfigurationListWriter {
 		}
 	}
 }
+/// Write LambdaFunctionConfigurationList contents as XML
+impl LambdaFunctionConfigurationListWriter {
+	fn write_xml(name: &str, obj: &LambdaFunctionConfigurationList) -> String {
+		let mut xml = String::new();
+		for element in obj.iter() {
+			xml.push_str(&LambdaFunctionConfigurationWriter::write_xml(name, element));
+		}
+		xml
+	}
+}
 pub type ServerSideEncryption = String;
 /// Parse ServerSideEncryption from XML
 struct ServerSideEncryptionParser;
@@ -9223,6 +10563,18 @@ impl NoncurrentVersionExpirationWriter {
 		DaysWriter::write_params(params, &(prefix.to_string() + "NoncurrentDays"), &obj.noncurrent_days);
 	}
 }
+/// Write NoncurrentVersionExpiration contents as XML
+impl NoncurrentVersionExpirationWriter {
+	fn write_xml(name: &str, obj: &NoncurrentVersionExpiration) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		if obj.noncurrent_days != Days::default() {
+			xml.push_str(&DaysWriter::write_xml("NoncurrentDays", &obj.noncurrent_days));
+		}
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 #[derive(Debug, Default)]
 pub struct GetBucketRequestPaymentOutput {
 	/// Specifies who pays for the download and request fees.
@@ -9303,6 +10655,8 @@ pub struct GetObjectRequest {
 	/// Amazon S3 uses this header for a message integrity check to ensure the
 	/// encryption key was transmitted without error.
 	pub sse_customer_key_md5: Option<SSECustomerKeyMD5>,
+	/// To retrieve the checksum, this mode must be enabled.
+	pub checksum_mode: Option<ChecksumMode>,
 }
 
 /// Parse GetObjectRequest from XML
@@ -9385,6 +10739,10 @@ impl GetObjectRequestParser {
 				obj.sse_customer_key_md5 = Some(try!(SSECustomerKeyMD5Parser::parse_xml("x-amz-server-side-encryption-customer-key-MD5", stack)));
 				continue;
 			}
+			if current_name == "x-amz-checksum-mode" {
+				obj.checksum_mode = Some(try!(ChecksumModeParser::parse_xml("x-amz-checksum-mode", stack)));
+				continue;
+			}
 			break;
 		}
 		try!(end_element(tag_name, stack));
@@ -9447,6 +10805,9 @@ impl GetObjectRequestWriter {
 		if let Some(ref obj) = obj.sse_customer_key_md5 {
 			SSECustomerKeyMD5Writer::write_params(params, &(prefix.to_string() + "x-amz-server-side-encryption-customer-key-MD5"), obj);
 		}
+		if let Some(ref obj) = obj.checksum_mode {
+			ChecksumModeWriter::write_params(params, &(prefix.to_string() + "x-amz-checksum-mode"), obj);
+		}
 	}
 }
 pub type ContentDisposition = String;
@@ -9577,6 +10938,12 @@ impl TypeWriter {
 		params.put(name, obj);
 	}
 }
+/// Write Type contents as XML
+impl TypeWriter {
+	fn write_xml(name: &str, obj: &Type) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 pub type Buckets = Vec<Bucket>;
 /// Parse Buckets from XML
 struct BucketsParser;
@@ -9642,6 +11009,15 @@ impl RestoreObjectOutputParser {
 		Ok(obj)
 	}
 }
+/// Parse RestoreObjectOutput members bound to response headers
+impl RestoreObjectOutputParser {
+	fn parse_headers(headers: &Headers, obj: &mut RestoreObjectOutput) -> Result<(), XmlParseError> {
+		if let Some(value) = header_value(headers, "x-amz-request-charged") {
+			obj.request_charged = value;
+		}
+		Ok(())
+	}
+}
 /// Write RestoreObjectOutput contents to a SignedRequest
 struct RestoreObjectOutputWriter;
 impl RestoreObjectOutputWriter {
@@ -9722,15 +11098,12 @@ pub struct GetBucketLocationOutput {
 struct GetBucketLocationOutputParser;
 impl GetBucketLocationOutputParser {
 	fn parse_xml<'a, T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<GetBucketLocationOutput, XmlParseError> {
+		// S3 sends the constraint as the root element: <LocationConstraint>EU</LocationConstraint>,
+		// empty for buckets in us-east-1
 		try!(start_element(tag_name, stack));
 		let mut obj = GetBucketLocationOutput::default();
-		loop {
-			let current_name = try!(peek_at_name(stack));
-			if current_name == "LocationConstraint" {
-				obj.location_constraint = try!(BucketLocationConstraintParser::parse_xml("LocationConstraint", stack));
-				continue;
-			}
-			break;
+		if let Some(&::xml::reader::events::XmlEvent::Characters(_)) = stack.peek() {
+			obj.location_constraint = try!(characters(stack));
 		}
 		try!(end_element(tag_name, stack));
 		Ok(obj)
@@ -9779,6 +11152,15 @@ impl GetObjectAclOutputParser {
 		Ok(obj)
 	}
 }
+/// Parse GetObjectAclOutput members bound to response headers
+impl GetObjectAclOutputParser {
+	fn parse_headers(headers: &Headers, obj: &mut GetObjectAclOutput) -> Result<(), XmlParseError> {
+		if let Some(value) = header_value(headers, "x-amz-request-charged") {
+			obj.request_charged = value;
+		}
+		Ok(())
+	}
+}
 /// Write GetObjectAclOutput contents to a SignedRequest
 struct GetObjectAclOutputWriter;
 impl GetObjectAclOutputWriter {
@@ -9808,6 +11190,12 @@ impl ReplaceKeyWithWriter {
 		params.put(name, obj);
 	}
 }
+/// Write ReplaceKeyWith contents as XML
+impl ReplaceKeyWithWriter {
+	fn write_xml(name: &str, obj: &ReplaceKeyWith) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 pub type ObjectKey = String;
 /// Parse ObjectKey from XML
 struct ObjectKeyParser;
@@ -9826,6 +11214,12 @@ impl ObjectKeyWriter {
 		params.put(name, obj);
 	}
 }
+/// Write ObjectKey contents as XML
+impl ObjectKeyWriter {
+	fn write_xml(name: &str, obj: &ObjectKey) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 #[derive(Debug, Default)]
 pub struct GetBucketTaggingRequest {
 	pub bucket: BucketName,
@@ -9909,6 +11303,12 @@ impl MaxAgeSecondsWriter {
 		params.put(name, &obj.to_string());
 	}
 }
+/// Write MaxAgeSeconds contents as XML
+impl MaxAgeSecondsWriter {
+	fn write_xml(name: &str, obj: &MaxAgeSeconds) -> String {
+		format!("<{}>{}</{}>", name, obj.to_string(), name)
+	}
+}
 pub type CopySourceRange = String;
 /// Parse CopySourceRange from XML
 struct CopySourceRangeParser;
@@ -9945,6 +11345,12 @@ impl TopicArnWriter {
 		params.put(name, obj);
 	}
 }
+/// Write TopicArn contents as XML
+impl TopicArnWriter {
+	fn write_xml(name: &str, obj: &TopicArn) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 #[derive(Debug, Default)]
 pub struct PutBucketTaggingRequest {
 	pub content_md5: Option<ContentMD5>,
@@ -10192,6 +11598,12 @@ impl AllowedHeaderWriter {
 		params.put(name, obj);
 	}
 }
+/// Write AllowedHeader contents as XML
+impl AllowedHeaderWriter {
+	fn write_xml(name: &str, obj: &AllowedHeader) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 #[derive(Debug, Default)]
 pub struct Bucket {
 	/// Date the bucket was created.
@@ -10250,6 +11662,12 @@ impl URIWriter {
 		params.put(name, obj);
 	}
 }
+/// Write URI contents as XML
+impl URIWriter {
+	fn write_xml(name: &str, obj: &URI) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 /// If present, indicates that the requester was successfully charged for the
 /// request.
 pub type RequestCharged = String;
@@ -10384,6 +11802,16 @@ impl LifecycleConfigurationWriter {
 		RulesWriter::write_params(params, &(prefix.to_string() + "Rule"), &obj.rules);
 	}
 }
+/// Write LifecycleConfiguration contents as XML
+impl LifecycleConfigurationWriter {
+	fn write_xml(name: &str, obj: &LifecycleConfiguration) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		xml.push_str(&RulesWriter::write_xml("Rule", &obj.rules));
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 pub type Expiration = String;
 /// Parse Expiration from XML
 struct ExpirationParser;
@@ -10519,6 +11947,20 @@ impl LambdaFunctionConfigurationWriter {
 		EventListWriter::write_params(params, &(prefix.to_string() + "Event"), &obj.events);
 	}
 }
+/// Write LambdaFunctionConfiguration contents as XML
+impl LambdaFunctionConfigurationWriter {
+	fn write_xml(name: &str, obj: &LambdaFunctionConfiguration) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		if let Some(ref obj) = obj.id {
+			xml.push_str(&NotificationIdWriter::write_xml("Id", obj));
+		}
+		xml.push_str(&LambdaFunctionArnWriter::write_xml("CloudFunction", &obj.lambda_function_arn));
+		xml.push_str(&EventListWriter::write_xml("Event", &obj.events));
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 /// Requests Amazon S3 to encode the object keys in the response and specifies the
 /// encoding method to use. An object key may contain any Unicode character;
 /// however, XML 1.0 parser cannot parse some characters, such as characters with
@@ -10561,6 +12003,12 @@ impl IDWriter {
 		params.put(name, obj);
 	}
 }
+/// Write ID contents as XML
+impl IDWriter {
+	fn write_xml(name: &str, obj: &ID) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 pub type PartNumberMarker = i32;
 /// Parse PartNumberMarker from XML
 struct PartNumberMarkerParser;
@@ -10683,6 +12131,12 @@ impl ReplicationRuleStatusWriter {
 		params.put(name, obj);
 	}
 }
+/// Write ReplicationRuleStatus contents as XML
+impl ReplicationRuleStatusWriter {
+	fn write_xml(name: &str, obj: &ReplicationRuleStatus) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 pub type ContentType = String;
 /// Parse ContentType from XML
 struct ContentTypeParser;
@@ -10901,6 +12355,19 @@ impl ObjectIdentifierWriter {
 		ObjectKeyWriter::write_params(params, &(prefix.to_string() + "Key"), &obj.key);
 	}
 }
+/// Write ObjectIdentifier contents as XML
+impl ObjectIdentifierWriter {
+	fn write_xml(name: &str, obj: &ObjectIdentifier) -> String {
+		let mut xml = format!("<{}", name);
+		xml.push_str(">");
+		xml.push_str(&ObjectKeyWriter::write_xml("Key", &obj.key));
+		if let Some(ref obj) = obj.version_id {
+			xml.push_str(&ObjectVersionIdWriter::write_xml("VersionId", obj));
+		}
+		xml.push_str(&format!("</{}>", name));
+		xml
+	}
+}
 /// Optional unique identifier for configurations in a notification configuration.
 /// If you don't provide one, Amazon S3 will assign an ID.
 pub type NotificationId = String;
@@ -10921,6 +12388,12 @@ impl NotificationIdWriter {
 		params.put(name, obj);
 	}
 }
+/// Write NotificationId contents as XML
+impl NotificationIdWriter {
+	fn write_xml(name: &str, obj: &NotificationId) -> String {
+		format!("<{}>{}</{}>", name, escape_xml(obj), name)
+	}
+}
 #[derive(Debug, Default)]
 pub struct GetBucketCorsOutput {
 	pub cors_rules: CORSRules,
@@ -10989,276 +12462,678 @@ impl MaxPartsWriter {
 		params.put(name, &obj.to_string());
 	}
 }
-pub struct S3Client<'a> {
-	creds: Box<AWSCredentialsProvider + 'a>,
-	region: &'a Region
+pub type TaggingHeader = String;
+/// Parse TaggingHeader from XML
+struct TaggingHeaderParser;
+impl TaggingHeaderParser {
+	fn parse_xml<'a, T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<TaggingHeader, XmlParseError> {
+		try!(start_element(tag_name, stack));
+		let obj = try!(characters(stack));
+		try!(end_element(tag_name, stack));
+		Ok(obj)
+	}
 }
-
-impl<'a> S3Client<'a> {
-	pub fn new<P: AWSCredentialsProvider + 'a>(creds: P, region: &'a Region) -> S3Client<'a> {
-		S3Client { creds: Box::new(creds), region: region }
+/// Write TaggingHeader contents to a SignedRequest
+struct TaggingHeaderWriter;
+impl TaggingHeaderWriter {
+	fn write_params(params: &mut Params, name: &str, obj: &TaggingHeader) {
+		params.put(name, obj);
 	}
-	/// Returns metadata about all of the versions of objects in a bucket.
-	pub fn list_object_versions(&mut self, input: &ListObjectVersionsRequest) -> Result<ListObjectVersionsOutput, AWSError> {
-		let mut request = SignedRequest::new("GET", "s3", &self.region, "/{Bucket}?versions");
-		let mut params = Params::new();
-		params.put("Action", "ListObjectVersions");
-		ListObjectVersionsRequestWriter::write_params(&mut params, "", &input);
-		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
-		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
-		match status {
-			200 => {
-				Ok(try!(ListObjectVersionsOutputParser::parse_xml("ListObjectVersionsOutput", &mut stack)))
-			}
-			_ => { Err(AWSError::new("error")) }
-		}
+}
+pub type ChecksumCRC32 = String;
+/// Parse ChecksumCRC32 from XML
+struct ChecksumCRC32Parser;
+impl ChecksumCRC32Parser {
+	fn parse_xml<'a, T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<ChecksumCRC32, XmlParseError> {
+		try!(start_element(tag_name, stack));
+		let obj = try!(characters(stack));
+		try!(end_element(tag_name, stack));
+		Ok(obj)
 	}
-	/// Replaces a policy on a bucket. If the bucket already has a policy, the one in
-	/// this request completely replaces it.
-	pub fn put_bucket_policy(&mut self, input: &PutBucketPolicyRequest) -> Result<(), AWSError> {
-		let mut request = SignedRequest::new("PUT", "s3", &self.region, "/{Bucket}?policy");
-		let mut params = Params::new();
-		params.put("Action", "PutBucketPolicy");
-		PutBucketPolicyRequestWriter::write_params(&mut params, "", &input);
-		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
-		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
-		match status {
-			200 => {
-				Ok(())
-			}
-			_ => { Err(AWSError::new("error")) }
-		}
+}
+/// Write ChecksumCRC32 contents to a SignedRequest
+struct ChecksumCRC32Writer;
+impl ChecksumCRC32Writer {
+	fn write_params(params: &mut Params, name: &str, obj: &ChecksumCRC32) {
+		params.put(name, obj);
 	}
-	/// Returns some or all (up to 1000) of the objects in a bucket. You can use the
-	/// request parameters as selection criteria to return a subset of the objects in
-	/// a bucket.
-	pub fn list_objects(&mut self, input: &ListObjectsRequest) -> Result<ListObjectsOutput, AWSError> {
-		let mut request = SignedRequest::new("GET", "s3", &self.region, "/{Bucket}");
-		let mut params = Params::new();
-		params.put("Action", "ListObjects");
-		ListObjectsRequestWriter::write_params(&mut params, "", &input);
-		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
-		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
-		match status {
-			200 => {
-				Ok(try!(ListObjectsOutputParser::parse_xml("ListObjectsOutput", &mut stack)))
-			}
-			_ => { Err(AWSError::new("error")) }
-		}
+}
+pub type ChecksumCRC32C = String;
+/// Parse ChecksumCRC32C from XML
+struct ChecksumCRC32CParser;
+impl ChecksumCRC32CParser {
+	fn parse_xml<'a, T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<ChecksumCRC32C, XmlParseError> {
+		try!(start_element(tag_name, stack));
+		let obj = try!(characters(stack));
+		try!(end_element(tag_name, stack));
+		Ok(obj)
 	}
-	/// Set the website configuration for a bucket.
-	pub fn put_bucket_website(&mut self, input: &PutBucketWebsiteRequest) -> Result<(), AWSError> {
-		let mut request = SignedRequest::new("PUT", "s3", &self.region, "/{Bucket}?website");
-		let mut params = Params::new();
-		params.put("Action", "PutBucketWebsite");
-		PutBucketWebsiteRequestWriter::write_params(&mut params, "", &input);
-		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
-		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
-		match status {
-			200 => {
-				Ok(())
-			}
-			_ => { Err(AWSError::new("error")) }
-		}
+}
+/// Write ChecksumCRC32C contents to a SignedRequest
+struct ChecksumCRC32CWriter;
+impl ChecksumCRC32CWriter {
+	fn write_params(params: &mut Params, name: &str, obj: &ChecksumCRC32C) {
+		params.put(name, obj);
 	}
-	/// Deprecated, see the PutBucketNotificationConfiguraiton operation.
-	pub fn put_bucket_notification(&mut self, input: &PutBucketNotificationRequest) -> Result<(), AWSError> {
-		let mut request = SignedRequest::new("PUT", "s3", &self.region, "/{Bucket}?notification");
-		let mut params = Params::new();
-		params.put("Action", "PutBucketNotification");
-		PutBucketNotificationRequestWriter::write_params(&mut params, "", &input);
-		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
-		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
-		match status {
-			200 => {
-				Ok(())
-			}
-			_ => { Err(AWSError::new("error")) }
-		}
+}
+pub type ChecksumSHA1 = String;
+/// Parse ChecksumSHA1 from XML
+struct ChecksumSHA1Parser;
+impl ChecksumSHA1Parser {
+	fn parse_xml<'a, T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<ChecksumSHA1, XmlParseError> {
+		try!(start_element(tag_name, stack));
+		let obj = try!(characters(stack));
+		try!(end_element(tag_name, stack));
+		Ok(obj)
 	}
-	/// Set the logging parameters for a bucket and to specify permissions for who can
-	/// view and modify the logging parameters. To set the logging status of a bucket,
-	/// you must be the bucket owner.
-	pub fn put_bucket_logging(&mut self, input: &PutBucketLoggingRequest) -> Result<(), AWSError> {
-		let mut request = SignedRequest::new("PUT", "s3", &self.region, "/{Bucket}?logging");
-		let mut params = Params::new();
-		params.put("Action", "PutBucketLogging");
-		PutBucketLoggingRequestWriter::write_params(&mut params, "", &input);
-		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
-		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
-		match status {
-			200 => {
-				Ok(())
-			}
-			_ => { Err(AWSError::new("error")) }
-		}
+}
+/// Write ChecksumSHA1 contents to a SignedRequest
+struct ChecksumSHA1Writer;
+impl ChecksumSHA1Writer {
+	fn write_params(params: &mut Params, name: &str, obj: &ChecksumSHA1) {
+		params.put(name, obj);
 	}
-	/// Creates a new replication configuration (or replaces an existing one, if
-	/// present).
-	pub fn put_bucket_replication(&mut self, input: &PutBucketReplicationRequest) -> Result<(), AWSError> {
-		let mut request = SignedRequest::new("PUT", "s3", &self.region, "/{Bucket}?replication");
-		let mut params = Params::new();
-		params.put("Action", "PutBucketReplication");
-		PutBucketReplicationRequestWriter::write_params(&mut params, "", &input);
-		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
-		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
-		match status {
-			200 => {
-				Ok(())
-			}
-			_ => { Err(AWSError::new("error")) }
-		}
+}
+pub type ChecksumSHA256 = String;
+/// Parse ChecksumSHA256 from XML
+struct ChecksumSHA256Parser;
+impl ChecksumSHA256Parser {
+	fn parse_xml<'a, T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<ChecksumSHA256, XmlParseError> {
+		try!(start_element(tag_name, stack));
+		let obj = try!(characters(stack));
+		try!(end_element(tag_name, stack));
+		Ok(obj)
 	}
-	/// Uploads a part in a multipart upload.
-	/// **Note:** After you initiate multipart upload and upload one or more parts, you must either complete or abort multipart upload in order to stop getting charged for storage of the uploaded parts. Only after you either complete or abort multipart upload, Amazon S3 frees up the parts storage and stops charging you for the parts storage.
-	pub fn upload_part(&mut self, input: &UploadPartRequest) -> Result<String, AWSError> {
-		let ref object_id = input.key;
-		let mut request = SignedRequest::new("PUT", "s3", &self.region, &format!("/{}", object_id));
-
-		request.set_payload(input.body);
-
-		let hostname = (&input.bucket).to_string() + ".s3.amazonaws.com";
-		request.set_hostname(Some(hostname));
-
-		match input.content_md5 {
-			Some(ref md5) => request.add_header("Content-MD5", &md5),
-			None => (),
-		}
-
-		let mut params = Params::new();
-		let ref upload_id = input.upload_id;
-		let ref part_number = input.part_number;
-		params.put("partNumber", &format!("{}", part_number));
-		params.put("uploadId", &format!("{}", upload_id));
-		request.set_params(params);
-
-		let mut result = request.sign_and_execute(try!(self.creds.get_credentials()));
-		let status = result.status.to_u16();
-
-		match status {
-			200 => {
-				for header in result.headers.iter() {
-					if header.name() == "ETag" {
-						return Ok(header.value_string());
-					}
-				}
-				return Err(AWSError::new("Couldn't find etag in response headers."));
-			}
-			_ => {
-				println!("Error: Status code was {}", status);
-				let mut body = String::new();
-			    result.read_to_string(&mut body).unwrap();
-			    println!("Error response body: {}", body);
-				return Err(AWSError::new("error: didn't get a 200."));
-			}
-		}
+}
+/// Write ChecksumSHA256 contents to a SignedRequest
+struct ChecksumSHA256Writer;
+impl ChecksumSHA256Writer {
+	fn write_params(params: &mut Params, name: &str, obj: &ChecksumSHA256) {
+		params.put(name, obj);
 	}
-	/// Adds an object to a bucket.
-	pub fn put_object(&mut self, input: &PutObjectRequest) -> Result<PutObjectOutput, AWSError> {
-		let mut uri = String::from("/");
-		uri = uri +  &input.key.to_string();
-		let mut request = SignedRequest::new("PUT", "s3", &self.region, &uri);
-
-		match input.storage_class {
-			Some(ref class) => {
-				request.add_header("x-amz-storage-class", class);
-			}
-			None => (),
-		}
-
-		match input.server_side_encryption {
-			Some(ref sse) => {
-				if sse.to_string().to_ascii_lowercase() == "aes256" {
-					request.add_header("x-amz-server-side-encryption", &sse);
-				} else {
-					match input.ssekms_key_id {
-						Some(ref key_id) => request.add_header("x-amz-server-side-encryption-aws-kms-key-id", &key_id),
-						None => return Err(AWSError::new("KMS key specified but no key id provided.")),
-					}
-					request.add_header("x-amz-server-side-encryption", "aws:kms");
-				}
-			}
-			None => (),
-		}
-
-		match input.content_md5 {
-			Some(ref md5) => request.add_header("Content-MD5", &md5),
-			None => (),
-		}
-
-		let hostname = (&input.bucket).to_string() + ".s3.amazonaws.com";
-		request.set_hostname(Some(hostname));
-		request.set_payload(input.body);
-
-		let mut result = request.sign_and_execute(try!(self.creds.get_credentials()));
-		let status = result.status.to_u16();
-
-		match status {
-			200 => {
-				let mut put_result = PutObjectOutput::default();
-
-				Ok(put_result)
+}
+pub type ChecksumMode = String;
+/// Parse ChecksumMode from XML
+struct ChecksumModeParser;
+impl ChecksumModeParser {
+	fn parse_xml<'a, T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<ChecksumMode, XmlParseError> {
+		try!(start_element(tag_name, stack));
+		let obj = try!(characters(stack));
+		try!(end_element(tag_name, stack));
+		Ok(obj)
+	}
+}
+/// Write ChecksumMode contents to a SignedRequest
+struct ChecksumModeWriter;
+impl ChecksumModeWriter {
+	fn write_params(params: &mut Params, name: &str, obj: &ChecksumMode) {
+		params.put(name, obj);
+	}
+}
+pub type Token = String;
+/// Parse Token from XML
+struct TokenParser;
+impl TokenParser {
+	fn parse_xml<'a, T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<Token, XmlParseError> {
+		try!(start_element(tag_name, stack));
+		let obj = try!(characters(stack));
+		try!(end_element(tag_name, stack));
+		Ok(obj)
+	}
+}
+/// Write Token contents to a SignedRequest
+struct TokenWriter;
+impl TokenWriter {
+	fn write_params(params: &mut Params, name: &str, obj: &Token) {
+		params.put(name, obj);
+	}
+}
+pub type NextToken = String;
+/// Parse NextToken from XML
+struct NextTokenParser;
+impl NextTokenParser {
+	fn parse_xml<'a, T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<NextToken, XmlParseError> {
+		try!(start_element(tag_name, stack));
+		let obj = try!(characters(stack));
+		try!(end_element(tag_name, stack));
+		Ok(obj)
+	}
+}
+/// Write NextToken contents to a SignedRequest
+struct NextTokenWriter;
+impl NextTokenWriter {
+	fn write_params(params: &mut Params, name: &str, obj: &NextToken) {
+		params.put(name, obj);
+	}
+}
+pub type StartAfter = String;
+/// Parse StartAfter from XML
+struct StartAfterParser;
+impl StartAfterParser {
+	fn parse_xml<'a, T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<StartAfter, XmlParseError> {
+		try!(start_element(tag_name, stack));
+		let obj = try!(characters(stack));
+		try!(end_element(tag_name, stack));
+		Ok(obj)
+	}
+}
+/// Write StartAfter contents to a SignedRequest
+struct StartAfterWriter;
+impl StartAfterWriter {
+	fn write_params(params: &mut Params, name: &str, obj: &StartAfter) {
+		params.put(name, obj);
+	}
+}
+pub type FetchOwner = bool;
+/// Parse FetchOwner from XML
+struct FetchOwnerParser;
+impl FetchOwnerParser {
+	fn parse_xml<'a, T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<FetchOwner, XmlParseError> {
+		try!(start_element(tag_name, stack));
+		let obj = bool::from_str(try!(characters(stack)).as_ref()).unwrap();
+		try!(end_element(tag_name, stack));
+		Ok(obj)
+	}
+}
+/// Write FetchOwner contents to a SignedRequest
+struct FetchOwnerWriter;
+impl FetchOwnerWriter {
+	fn write_params(params: &mut Params, name: &str, obj: &FetchOwner) {
+		params.put(name, &obj.to_string());
+	}
+}
+pub type KeyCount = i32;
+/// Parse KeyCount from XML
+struct KeyCountParser;
+impl KeyCountParser {
+	fn parse_xml<'a, T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<KeyCount, XmlParseError> {
+		try!(start_element(tag_name, stack));
+		let obj = i32::from_str(try!(characters(stack)).as_ref()).unwrap();
+		try!(end_element(tag_name, stack));
+		Ok(obj)
+	}
+}
+/// Write KeyCount contents to a SignedRequest
+struct KeyCountWriter;
+impl KeyCountWriter {
+	fn write_params(params: &mut Params, name: &str, obj: &KeyCount) {
+		params.put(name, &obj.to_string());
+	}
+}
+#[derive(Debug, Default)]
+pub struct ListObjectsV2Request {
+	pub bucket: BucketName,
+	pub delimiter: Option<Delimiter>,
+	/// Set to "url" to have S3 URL-encode keys in the response, EG: for keys with
+	/// characters XML can't hold. The S3Helper listings decode them again.
+	pub encoding_type: Option<EncodingType>,
+	pub max_keys: Option<MaxKeys>,
+	pub prefix: Option<Prefix>,
+	/// Continues a listing from where the previous, truncated response stopped.
+	pub continuation_token: Option<Token>,
+	/// Returns the owner of each key, which ListObjectsV2 leaves out by default.
+	pub fetch_owner: Option<FetchOwner>,
+	/// Lists keys after this one. Ignored once a continuation token is sent.
+	pub start_after: Option<StartAfter>,
+	pub request_payer: Option<RequestPayer>,
+}
+
+/// Parse ListObjectsV2Request from XML
+struct ListObjectsV2RequestParser;
+impl ListObjectsV2RequestParser {
+	fn parse_xml<'a, T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<ListObjectsV2Request, XmlParseError> {
+		try!(start_element(tag_name, stack));
+		let mut obj = ListObjectsV2Request::default();
+		loop {
+			let current_name = try!(peek_at_name(stack));
+			if current_name == "Bucket" {
+				obj.bucket = try!(BucketNameParser::parse_xml("Bucket", stack));
+				continue;
 			}
-			_ => {
-				println!("Error: Status code was {}", status);
-				let mut body = String::new();
-			    result.read_to_string(&mut body).unwrap();
-			    println!("Error response body: {}", body);
+			if current_name == "delimiter" {
+				obj.delimiter = Some(try!(DelimiterParser::parse_xml("delimiter", stack)));
+				continue;
+			}
+			if current_name == "encoding-type" {
+				obj.encoding_type = Some(try!(EncodingTypeParser::parse_xml("encoding-type", stack)));
+				continue;
+			}
+			if current_name == "max-keys" {
+				obj.max_keys = Some(try!(MaxKeysParser::parse_xml("max-keys", stack)));
+				continue;
+			}
+			if current_name == "prefix" {
+				obj.prefix = Some(try!(PrefixParser::parse_xml("prefix", stack)));
+				continue;
+			}
+			if current_name == "continuation-token" {
+				obj.continuation_token = Some(try!(TokenParser::parse_xml("continuation-token", stack)));
+				continue;
+			}
+			if current_name == "fetch-owner" {
+				obj.fetch_owner = Some(try!(FetchOwnerParser::parse_xml("fetch-owner", stack)));
+				continue;
+			}
+			if current_name == "start-after" {
+				obj.start_after = Some(try!(StartAfterParser::parse_xml("start-after", stack)));
+				continue;
+			}
+			if current_name == "x-amz-request-payer" {
+				obj.request_payer = Some(try!(RequestPayerParser::parse_xml("x-amz-request-payer", stack)));
+				continue;
+			}
+			break;
+		}
+		try!(end_element(tag_name, stack));
+		Ok(obj)
+	}
+}
+/// Write ListObjectsV2Request contents to a SignedRequest
+struct ListObjectsV2RequestWriter;
+impl ListObjectsV2RequestWriter {
+	fn write_params(params: &mut Params, name: &str, obj: &ListObjectsV2Request) {
+		let mut prefix = name.to_string();
+		if prefix != "" { prefix.push_str("."); }
+		BucketNameWriter::write_params(params, &(prefix.to_string() + "Bucket"), &obj.bucket);
+		if let Some(ref obj) = obj.delimiter {
+			DelimiterWriter::write_params(params, &(prefix.to_string() + "delimiter"), obj);
+		}
+		if let Some(ref obj) = obj.encoding_type {
+			EncodingTypeWriter::write_params(params, &(prefix.to_string() + "encoding-type"), obj);
+		}
+		if let Some(ref obj) = obj.max_keys {
+			MaxKeysWriter::write_params(params, &(prefix.to_string() + "max-keys"), obj);
+		}
+		if let Some(ref obj) = obj.prefix {
+			PrefixWriter::write_params(params, &(prefix.to_string() + "prefix"), obj);
+		}
+		if let Some(ref obj) = obj.continuation_token {
+			TokenWriter::write_params(params, &(prefix.to_string() + "continuation-token"), obj);
+		}
+		if let Some(ref obj) = obj.fetch_owner {
+			FetchOwnerWriter::write_params(params, &(prefix.to_string() + "fetch-owner"), obj);
+		}
+		if let Some(ref obj) = obj.start_after {
+			StartAfterWriter::write_params(params, &(prefix.to_string() + "start-after"), obj);
+		}
+		if let Some(ref obj) = obj.request_payer {
+			RequestPayerWriter::write_params(params, &(prefix.to_string() + "x-amz-request-payer"), obj);
+		}
+	}
+}
+#[derive(Debug, Default)]
+pub struct ListObjectsV2Output {
+	/// A flag that indicates whether or not Amazon S3 returned all of the results
+	/// that satisfied the search criteria.
+	pub is_truncated: IsTruncated,
+	pub contents: ObjectList,
+	pub name: BucketName,
+	pub prefix: Prefix,
+	pub delimiter: Delimiter,
+	pub max_keys: MaxKeys,
+	pub common_prefixes: CommonPrefixList,
+	pub encoding_type: EncodingType,
+	/// Number of keys and common prefixes in this response.
+	pub key_count: KeyCount,
+	pub continuation_token: Token,
+	/// Sent when the response is truncated: the continuation token for the next
+	/// request.
+	pub next_continuation_token: NextToken,
+	pub start_after: StartAfter,
+}
 
-				Err(AWSError::new("error uploading object to S3"))
+/// Parse ListObjectsV2Output from XML
+struct ListObjectsV2OutputParser;
+impl ListObjectsV2OutputParser {
+	fn parse_xml<'a, T: Peek + Next>(tag_name: &str, stack: &mut T) -> Result<ListObjectsV2Output, XmlParseError> {
+		try!(start_element(tag_name, stack));
+		let mut obj = ListObjectsV2Output::default();
+		loop {
+			let current_name = try!(peek_at_name(stack));
+			if current_name == "IsTruncated" {
+				obj.is_truncated = try!(IsTruncatedParser::parse_xml("IsTruncated", stack));
+				continue;
+			}
+			if current_name == "Contents" {
+				obj.contents.push(try!(ObjectParser::parse_xml("Contents", stack)));
+				continue;
+			}
+			if current_name == "Name" {
+				obj.name = try!(BucketNameParser::parse_xml("Name", stack));
+				continue;
+			}
+			if current_name == "Prefix" {
+				obj.prefix = try!(PrefixParser::parse_xml("Prefix", stack));
+				continue;
+			}
+			if current_name == "Delimiter" {
+				obj.delimiter = try!(DelimiterParser::parse_xml("Delimiter", stack));
+				continue;
+			}
+			if current_name == "MaxKeys" {
+				obj.max_keys = try!(MaxKeysParser::parse_xml("MaxKeys", stack));
+				continue;
+			}
+			if current_name == "CommonPrefixes" {
+				obj.common_prefixes.push(try!(CommonPrefixParser::parse_xml("CommonPrefixes", stack)));
+				continue;
+			}
+			if current_name == "EncodingType" {
+				obj.encoding_type = try!(EncodingTypeParser::parse_xml("EncodingType", stack));
+				continue;
+			}
+			if current_name == "KeyCount" {
+				obj.key_count = try!(KeyCountParser::parse_xml("KeyCount", stack));
+				continue;
+			}
+			if current_name == "ContinuationToken" {
+				obj.continuation_token = try!(TokenParser::parse_xml("ContinuationToken", stack));
+				continue;
+			}
+			if current_name == "NextContinuationToken" {
+				obj.next_continuation_token = try!(NextTokenParser::parse_xml("NextContinuationToken", stack));
+				continue;
+			}
+			if current_name == "StartAfter" {
+				obj.start_after = try!(StartAfterParser::parse_xml("StartAfter", stack));
+				continue;
+			}
+			break;
+		}
+		try!(end_element(tag_name, stack));
+		Ok(obj)
+	}
+}
+/// Write ListObjectsV2Output contents to a SignedRequest
+struct ListObjectsV2OutputWriter;
+impl ListObjectsV2OutputWriter {
+	fn write_params(params: &mut Params, name: &str, obj: &ListObjectsV2Output) {
+		let mut prefix = name.to_string();
+		if prefix != "" { prefix.push_str("."); }
+		IsTruncatedWriter::write_params(params, &(prefix.to_string() + "IsTruncated"), &obj.is_truncated);
+		ObjectListWriter::write_params(params, &(prefix.to_string() + "Object"), &obj.contents);
+		BucketNameWriter::write_params(params, &(prefix.to_string() + "Name"), &obj.name);
+		PrefixWriter::write_params(params, &(prefix.to_string() + "Prefix"), &obj.prefix);
+		DelimiterWriter::write_params(params, &(prefix.to_string() + "Delimiter"), &obj.delimiter);
+		MaxKeysWriter::write_params(params, &(prefix.to_string() + "MaxKeys"), &obj.max_keys);
+		CommonPrefixListWriter::write_params(params, &(prefix.to_string() + "CommonPrefix"), &obj.common_prefixes);
+		EncodingTypeWriter::write_params(params, &(prefix.to_string() + "EncodingType"), &obj.encoding_type);
+		KeyCountWriter::write_params(params, &(prefix.to_string() + "KeyCount"), &obj.key_count);
+		TokenWriter::write_params(params, &(prefix.to_string() + "ContinuationToken"), &obj.continuation_token);
+		NextTokenWriter::write_params(params, &(prefix.to_string() + "NextContinuationToken"), &obj.next_continuation_token);
+		StartAfterWriter::write_params(params, &(prefix.to_string() + "StartAfter"), &obj.start_after);
+	}
+}
+pub struct S3Client<'a> {
+	creds: Box<AWSCredentialsProvider + 'a>,
+	region: &'a Region,
+	hooks: RequestHooks,
+	endpoint: S3Endpoint,
+	bucket_regions: HashMap<String, Region>,
+}
+
+impl<'a> S3Client<'a> {
+	pub fn new<P: AWSCredentialsProvider + 'a>(creds: P, region: &'a Region) -> S3Client<'a> {
+		S3Client { creds: Box::new(creds), region: region, hooks: default_hooks(), endpoint: S3Endpoint::default(), bucket_regions: HashMap::new() }
+	}
+	/// Chooses between virtual-hosted and path-style requests.
+	pub fn set_addressing_style(&mut self, addressing_style: AddressingStyle) {
+		self.endpoint.addressing_style = addressing_style;
+	}
+	/// Sends requests to the dual-stack IPv4/IPv6 endpoints.
+	pub fn set_dualstack(&mut self, dualstack: bool) {
+		self.endpoint.dualstack = dualstack;
+	}
+	/// Sends requests through S3 Transfer Acceleration.
+	pub fn set_accelerate(&mut self, accelerate: bool) {
+		self.endpoint.accelerate = accelerate;
+	}
+	pub fn set_endpoint(&mut self, endpoint: S3Endpoint) {
+		self.endpoint = endpoint;
+	}
+	pub fn get_endpoint(&self) -> &S3Endpoint {
+		&self.endpoint
+	}
+	/// Adds a hook called around every request this client sends.
+	pub fn add_hook(&mut self, hook: Arc<RequestHook>) {
+		self.hooks.push(hook);
+	}
+	/// Removes all hooks, including the default logging hook.
+	pub fn clear_hooks(&mut self) {
+		self.hooks.clear();
+	}
+	/// Returns metadata about all of the versions of objects in a bucket.
+	pub fn list_object_versions(&mut self, input: &ListObjectVersionsRequest) -> Result<ListObjectVersionsOutput, AWSError> {
+		let mut uri = "/{Bucket}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		let mut request = SignedRequest::new("GET", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
+		let mut params = Params::new();
+		params.put("versions", "");
+		if let Some(ref obj) = input.delimiter {
+			params.put("delimiter", obj);
+		}
+		if let Some(ref obj) = input.encoding_type {
+			params.put("encoding-type", obj);
+		}
+		if let Some(ref obj) = input.key_marker {
+			params.put("key-marker", obj);
+		}
+		if let Some(ref obj) = input.max_keys {
+			params.put("max-keys", &obj.to_string());
+		}
+		if let Some(ref obj) = input.prefix {
+			params.put("prefix", obj);
+		}
+		if let Some(ref obj) = input.version_id_marker {
+			params.put("version-id-marker", obj);
+		}
+		request.set_params(params);
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
+		let status = result.status.to_u16();
+		match status {
+			200...299 => {
+				let mut reader = EventReader::new(result);
+				let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+				stack.next(); // xml start tag
+				let root = try!(peek_at_name(&mut stack));
+				Ok(try!(ListObjectVersionsOutputParser::parse_xml(&root, &mut stack)))
+			}
+			_ => { Err(AWSError::new("error")) }
+		}
+	}
+	/// Replaces a policy on a bucket. If the bucket already has a policy, the one in
+	/// this request completely replaces it.
+	pub fn put_bucket_policy(&mut self, input: &PutBucketPolicyRequest) -> Result<(), AWSError> {
+		let mut uri = "/{Bucket}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		let mut request = SignedRequest::new("PUT", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
+		let mut params = Params::new();
+		params.put("policy", "");
+		if let Some(ref obj) = input.content_md5 {
+			request.add_header("Content-MD5", obj);
+		}
+		request.set_payload(Some(input.policy.as_bytes()));
+		if input.content_md5.is_none() {
+			request.add_header("Content-MD5", &hash(MD5, input.policy.as_bytes()).to_base64(STANDARD));
+		}
+		request.set_params(params);
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
+		let status = result.status.to_u16();
+		match status {
+			200...299 => {
+				Ok(())
+			}
+			_ => { Err(AWSError::new("error")) }
+		}
+	}
+	/// Returns some or all (up to 1000) of the objects in a bucket. You can use the
+	/// request parameters as selection criteria to return a subset of the objects in
+	/// a bucket.
+	pub fn list_objects(&mut self, input: &ListObjectsRequest) -> Result<ListObjectsOutput, AWSError> {
+		let mut uri = "/{Bucket}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		let mut request = SignedRequest::new("GET", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
+		let mut params = Params::new();
+		if let Some(ref obj) = input.delimiter {
+			params.put("delimiter", obj);
+		}
+		if let Some(ref obj) = input.encoding_type {
+			params.put("encoding-type", obj);
+		}
+		if let Some(ref obj) = input.marker {
+			params.put("marker", obj);
+		}
+		if let Some(ref obj) = input.max_keys {
+			params.put("max-keys", &obj.to_string());
+		}
+		if let Some(ref obj) = input.prefix {
+			params.put("prefix", obj);
+		}
+		request.set_params(params);
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
+		let status = result.status.to_u16();
+		match status {
+			200...299 => {
+				let mut reader = EventReader::new(result);
+				let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+				stack.next(); // xml start tag
+				let root = try!(peek_at_name(&mut stack));
+				Ok(try!(ListObjectsOutputParser::parse_xml(&root, &mut stack)))
+			}
+			_ => { Err(AWSError::new("error")) }
+		}
+	}
+	/// Set the website configuration for a bucket.
+	pub fn put_bucket_website(&mut self, input: &PutBucketWebsiteRequest) -> Result<(), AWSError> {
+		let mut uri = "/{Bucket}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		let payload = with_xml_namespace(&WebsiteConfigurationWriter::write_xml("WebsiteConfiguration", &input.website_configuration), "http://s3.amazonaws.com/doc/2006-03-01/");
+		let mut request = SignedRequest::new("PUT", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
+		let mut params = Params::new();
+		params.put("website", "");
+		if let Some(ref obj) = input.content_md5 {
+			request.add_header("Content-MD5", obj);
+		}
+		request.set_payload(Some(payload.as_bytes()));
+		if input.content_md5.is_none() {
+			request.add_header("Content-MD5", &hash(MD5, payload.as_bytes()).to_base64(STANDARD));
+		}
+		request.set_params(params);
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
+		let status = result.status.to_u16();
+		match status {
+			200...299 => {
+				Ok(())
+			}
+			_ => { Err(AWSError::new("error")) }
+		}
+	}
+	/// Deprecated, see the PutBucketNotificationConfiguraiton operation.
+	pub fn put_bucket_notification(&mut self, input: &PutBucketNotificationRequest) -> Result<(), AWSError> {
+		let mut uri = "/{Bucket}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		let payload = with_xml_namespace(&NotificationConfigurationDeprecatedWriter::write_xml("NotificationConfiguration", &input.notification_configuration), "http://s3.amazonaws.com/doc/2006-03-01/");
+		let mut request = SignedRequest::new("PUT", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
+		let mut params = Params::new();
+		params.put("notification", "");
+		if let Some(ref obj) = input.content_md5 {
+			request.add_header("Content-MD5", obj);
+		}
+		request.set_payload(Some(payload.as_bytes()));
+		if input.content_md5.is_none() {
+			request.add_header("Content-MD5", &hash(MD5, payload.as_bytes()).to_base64(STANDARD));
+		}
+		request.set_params(params);
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
+		let status = result.status.to_u16();
+		match status {
+			200...299 => {
+				Ok(())
+			}
+			_ => { Err(AWSError::new("error")) }
+		}
+	}
+	/// Set the logging parameters for a bucket and to specify permissions for who can
+	/// view and modify the logging parameters. To set the logging status of a bucket,
+	/// you must be the bucket owner.
+	pub fn put_bucket_logging(&mut self, input: &PutBucketLoggingRequest) -> Result<(), AWSError> {
+		let mut uri = "/{Bucket}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		let payload = with_xml_namespace(&BucketLoggingStatusWriter::write_xml("BucketLoggingStatus", &input.bucket_logging_status), "http://s3.amazonaws.com/doc/2006-03-01/");
+		let mut request = SignedRequest::new("PUT", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
+		let mut params = Params::new();
+		params.put("logging", "");
+		if let Some(ref obj) = input.content_md5 {
+			request.add_header("Content-MD5", obj);
+		}
+		request.set_payload(Some(payload.as_bytes()));
+		if input.content_md5.is_none() {
+			request.add_header("Content-MD5", &hash(MD5, payload.as_bytes()).to_base64(STANDARD));
+		}
+		request.set_params(params);
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
+		let status = result.status.to_u16();
+		match status {
+			200...299 => {
+				Ok(())
+			}
+			_ => { Err(AWSError::new("error")) }
+		}
+	}
+	/// Creates a new replication configuration (or replaces an existing one, if
+	/// present).
+	pub fn put_bucket_replication(&mut self, input: &PutBucketReplicationRequest) -> Result<(), AWSError> {
+		let mut uri = "/{Bucket}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		let payload = with_xml_namespace(&ReplicationConfigurationWriter::write_xml("ReplicationConfiguration", &input.replication_configuration), "http://s3.amazonaws.com/doc/2006-03-01/");
+		let mut request = SignedRequest::new("PUT", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
+		let mut params = Params::new();
+		params.put("replication", "");
+		if let Some(ref obj) = input.content_md5 {
+			request.add_header("Content-MD5", obj);
+		}
+		request.set_payload(Some(payload.as_bytes()));
+		if input.content_md5.is_none() {
+			request.add_header("Content-MD5", &hash(MD5, payload.as_bytes()).to_base64(STANDARD));
+		}
+		request.set_params(params);
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
+		let status = result.status.to_u16();
+		match status {
+			200...299 => {
+				Ok(())
 			}
+			_ => { Err(AWSError::new("error")) }
 		}
 	}
 	/// Deletes the cors configuration information set for the bucket.
 	pub fn delete_bucket_cors(&mut self, input: &DeleteBucketCorsRequest) -> Result<(), AWSError> {
-		let mut request = SignedRequest::new("DELETE", "s3", &self.region, "/{Bucket}?cors");
+		let mut uri = "/{Bucket}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		let mut request = SignedRequest::new("DELETE", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
 		let mut params = Params::new();
-		params.put("Action", "DeleteBucketCors");
-		DeleteBucketCorsRequestWriter::write_params(&mut params, "", &input);
+		params.put("cors", "");
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
 		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
 		match status {
-			200 => {
+			200...299 => {
 				Ok(())
 			}
 			_ => { Err(AWSError::new("error")) }
@@ -11267,19 +13142,29 @@ impl<'a> S3Client<'a> {
 	/// Sets the versioning state of an existing bucket. To set the versioning state,
 	/// you must be the bucket owner.
 	pub fn put_bucket_versioning(&mut self, input: &PutBucketVersioningRequest) -> Result<(), AWSError> {
-		let mut request = SignedRequest::new("PUT", "s3", &self.region, "/{Bucket}?versioning");
+		let mut uri = "/{Bucket}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		let payload = with_xml_namespace(&VersioningConfigurationWriter::write_xml("VersioningConfiguration", &input.versioning_configuration), "http://s3.amazonaws.com/doc/2006-03-01/");
+		let mut request = SignedRequest::new("PUT", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
 		let mut params = Params::new();
-		params.put("Action", "PutBucketVersioning");
-		PutBucketVersioningRequestWriter::write_params(&mut params, "", &input);
+		params.put("versioning", "");
+		if let Some(ref obj) = input.content_md5 {
+			request.add_header("Content-MD5", obj);
+		}
+		if let Some(ref obj) = input.mfa {
+			request.add_header("x-amz-mfa", obj);
+		}
+		request.set_payload(Some(payload.as_bytes()));
+		if input.content_md5.is_none() {
+			request.add_header("Content-MD5", &hash(MD5, payload.as_bytes()).to_base64(STANDARD));
+		}
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
 		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
 		match status {
-			200 => {
+			200...299 => {
 				Ok(())
 			}
 			_ => { Err(AWSError::new("error")) }
@@ -11287,20 +13172,23 @@ impl<'a> S3Client<'a> {
 	}
 	/// Returns the cors configuration for the bucket.
 	pub fn get_bucket_cors(&mut self, input: &GetBucketCorsRequest) -> Result<GetBucketCorsOutput, AWSError> {
-		let mut request = SignedRequest::new("GET", "s3", &self.region, "/{Bucket}?cors");
+		let mut uri = "/{Bucket}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		let mut request = SignedRequest::new("GET", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
 		let mut params = Params::new();
-		params.put("Action", "GetBucketCors");
-		GetBucketCorsRequestWriter::write_params(&mut params, "", &input);
+		params.put("cors", "");
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
 		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
 		match status {
-			200 => {
-				Ok(try!(GetBucketCorsOutputParser::parse_xml("GetBucketCorsOutput", &mut stack)))
+			200...299 => {
+				let mut reader = EventReader::new(result);
+				let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+				stack.next(); // xml start tag
+				let root = try!(peek_at_name(&mut stack));
+				Ok(try!(GetBucketCorsOutputParser::parse_xml(&root, &mut stack)))
 			}
 			_ => { Err(AWSError::new("error")) }
 		}
@@ -11308,19 +13196,31 @@ impl<'a> S3Client<'a> {
 	/// Sets lifecycle configuration for your bucket. If a lifecycle configuration
 	/// exists, it replaces it.
 	pub fn put_bucket_lifecycle(&mut self, input: &PutBucketLifecycleRequest) -> Result<(), AWSError> {
-		let mut request = SignedRequest::new("PUT", "s3", &self.region, "/{Bucket}?lifecycle");
+		let mut uri = "/{Bucket}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		let payload = match input.lifecycle_configuration {
+			Some(ref obj) => Some(with_xml_namespace(&LifecycleConfigurationWriter::write_xml("LifecycleConfiguration", obj), "http://s3.amazonaws.com/doc/2006-03-01/")),
+			None => None,
+		};
+		let mut request = SignedRequest::new("PUT", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
 		let mut params = Params::new();
-		params.put("Action", "PutBucketLifecycle");
-		PutBucketLifecycleRequestWriter::write_params(&mut params, "", &input);
+		params.put("lifecycle", "");
+		if let Some(ref obj) = input.content_md5 {
+			request.add_header("Content-MD5", obj);
+		}
+		if let Some(ref payload) = payload {
+			request.set_payload(Some(payload.as_bytes()));
+			if input.content_md5.is_none() {
+				request.add_header("Content-MD5", &hash(MD5, payload.as_bytes()).to_base64(STANDARD));
+			}
+		}
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
 		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
 		match status {
-			200 => {
+			200...299 => {
 				Ok(())
 			}
 			_ => { Err(AWSError::new("error")) }
@@ -11328,20 +13228,23 @@ impl<'a> S3Client<'a> {
 	}
 	/// Gets the access control policy for the bucket.
 	pub fn get_bucket_acl(&mut self, input: &GetBucketAclRequest) -> Result<GetBucketAclOutput, AWSError> {
-		let mut request = SignedRequest::new("GET", "s3", &self.region, "/{Bucket}?acl");
+		let mut uri = "/{Bucket}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		let mut request = SignedRequest::new("GET", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
 		let mut params = Params::new();
-		params.put("Action", "GetBucketAcl");
-		GetBucketAclRequestWriter::write_params(&mut params, "", &input);
+		params.put("acl", "");
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
 		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
 		match status {
-			200 => {
-				Ok(try!(GetBucketAclOutputParser::parse_xml("GetBucketAclOutput", &mut stack)))
+			200...299 => {
+				let mut reader = EventReader::new(result);
+				let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+				stack.next(); // xml start tag
+				let root = try!(peek_at_name(&mut stack));
+				Ok(try!(GetBucketAclOutputParser::parse_xml(&root, &mut stack)))
 			}
 			_ => { Err(AWSError::new("error")) }
 		}
@@ -11349,20 +13252,23 @@ impl<'a> S3Client<'a> {
 	/// Returns the logging status of a bucket and the permissions users have to view
 	/// and modify that status. To use GET, you must be the bucket owner.
 	pub fn get_bucket_logging(&mut self, input: &GetBucketLoggingRequest) -> Result<GetBucketLoggingOutput, AWSError> {
-		let mut request = SignedRequest::new("GET", "s3", &self.region, "/{Bucket}?logging");
+		let mut uri = "/{Bucket}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		let mut request = SignedRequest::new("GET", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
 		let mut params = Params::new();
-		params.put("Action", "GetBucketLogging");
-		GetBucketLoggingRequestWriter::write_params(&mut params, "", &input);
+		params.put("logging", "");
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
 		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
 		match status {
-			200 => {
-				Ok(try!(GetBucketLoggingOutputParser::parse_xml("GetBucketLoggingOutput", &mut stack)))
+			200...299 => {
+				let mut reader = EventReader::new(result);
+				let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+				stack.next(); // xml start tag
+				let root = try!(peek_at_name(&mut stack));
+				Ok(try!(GetBucketLoggingOutputParser::parse_xml(&root, &mut stack)))
 			}
 			_ => { Err(AWSError::new("error")) }
 		}
@@ -11370,19 +13276,17 @@ impl<'a> S3Client<'a> {
 	/// This operation is useful to determine if a bucket exists and you have
 	/// permission to access it.
 	pub fn head_bucket(&mut self, input: &HeadBucketRequest) -> Result<(), AWSError> {
-		let mut request = SignedRequest::new("HEAD", "s3", &self.region, "/{Bucket}");
-		let mut params = Params::new();
-		params.put("Action", "HeadBucket");
-		HeadBucketRequestWriter::write_params(&mut params, "", &input);
+		let mut uri = "/{Bucket}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		let mut request = SignedRequest::new("HEAD", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
+		let params = Params::new();
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
 		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
 		match status {
-			200 => {
+			200...299 => {
 				Ok(())
 			}
 			_ => { Err(AWSError::new("error")) }
@@ -11390,18 +13294,49 @@ impl<'a> S3Client<'a> {
 	}
 	/// Sets the permissions on a bucket using access control lists (ACL).
 	pub fn put_bucket_acl(&mut self, input: &PutBucketAclRequest) -> Result<(), AWSError> {
-		let mut request = SignedRequest::new("PUT", "s3", &self.region, "/{Bucket}?acl");
+		let mut uri = "/{Bucket}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		let payload = match input.access_control_policy {
+			Some(ref obj) => Some(with_xml_namespace(&AccessControlPolicyWriter::write_xml("AccessControlPolicy", obj), "http://s3.amazonaws.com/doc/2006-03-01/")),
+			None => None,
+		};
+		let mut request = SignedRequest::new("PUT", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
 		let mut params = Params::new();
-		params.put("Action", "PutBucketAcl");
+		params.put("acl", "");
+		if let Some(ref obj) = input.acl {
+			request.add_header("x-amz-acl", &obj.to_string());
+		}
+		if let Some(ref obj) = input.content_md5 {
+			request.add_header("Content-MD5", obj);
+		}
+		if let Some(ref obj) = input.grant_full_control {
+			request.add_header("x-amz-grant-full-control", obj);
+		}
+		if let Some(ref obj) = input.grant_read {
+			request.add_header("x-amz-grant-read", obj);
+		}
+		if let Some(ref obj) = input.grant_read_acp {
+			request.add_header("x-amz-grant-read-acp", obj);
+		}
+		if let Some(ref obj) = input.grant_write {
+			request.add_header("x-amz-grant-write", obj);
+		}
+		if let Some(ref obj) = input.grant_write_acp {
+			request.add_header("x-amz-grant-write-acp", obj);
+		}
+		if let Some(ref payload) = payload {
+			request.set_payload(Some(payload.as_bytes()));
+			if input.content_md5.is_none() {
+				request.add_header("Content-MD5", &hash(MD5, payload.as_bytes()).to_base64(STANDARD));
+			}
+		}
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
 		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
 		match status {
-			200 => {
+			200...299 => {
 				Ok(())
 			}
 			_ => { Err(AWSError::new("error")) }
@@ -11409,19 +13344,18 @@ impl<'a> S3Client<'a> {
 	}
 	/// This operation removes the website configuration from the bucket.
 	pub fn delete_bucket_website(&mut self, input: &DeleteBucketWebsiteRequest) -> Result<(), AWSError> {
-		let mut request = SignedRequest::new("DELETE", "s3", &self.region, "/{Bucket}?website");
+		let mut uri = "/{Bucket}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		let mut request = SignedRequest::new("DELETE", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
 		let mut params = Params::new();
-		params.put("Action", "DeleteBucketWebsite");
-		DeleteBucketWebsiteRequestWriter::write_params(&mut params, "", &input);
+		params.put("website", "");
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
 		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
 		match status {
-			200 => {
+			200...299 => {
 				Ok(())
 			}
 			_ => { Err(AWSError::new("error")) }
@@ -11429,19 +13363,18 @@ impl<'a> S3Client<'a> {
 	}
 	/// Deletes the policy from the bucket.
 	pub fn delete_bucket_policy(&mut self, input: &DeleteBucketPolicyRequest) -> Result<(), AWSError> {
-		let mut request = SignedRequest::new("DELETE", "s3", &self.region, "/{Bucket}?policy");
+		let mut uri = "/{Bucket}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		let mut request = SignedRequest::new("DELETE", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
 		let mut params = Params::new();
-		params.put("Action", "DeleteBucketPolicy");
-		DeleteBucketPolicyRequestWriter::write_params(&mut params, "", &input);
+		params.put("policy", "");
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
 		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
 		match status {
-			200 => {
+			200...299 => {
 				Ok(())
 			}
 			_ => { Err(AWSError::new("error")) }
@@ -11449,20 +13382,23 @@ impl<'a> S3Client<'a> {
 	}
 	/// Returns the notification configuration of a bucket.
 	pub fn get_bucket_notification_configuration(&mut self, input: &GetBucketNotificationConfigurationRequest) -> Result<NotificationConfiguration, AWSError> {
-		let mut request = SignedRequest::new("GET", "s3", &self.region, "/{Bucket}?notification");
+		let mut uri = "/{Bucket}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		let mut request = SignedRequest::new("GET", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
 		let mut params = Params::new();
-		params.put("Action", "GetBucketNotificationConfiguration");
-		GetBucketNotificationConfigurationRequestWriter::write_params(&mut params, "", &input);
+		params.put("notification", "");
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
 		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
 		match status {
-			200 => {
-				Ok(try!(NotificationConfigurationParser::parse_xml("NotificationConfiguration", &mut stack)))
+			200...299 => {
+				let mut reader = EventReader::new(result);
+				let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+				stack.next(); // xml start tag
+				let root = try!(peek_at_name(&mut stack));
+				Ok(try!(NotificationConfigurationParser::parse_xml(&root, &mut stack)))
 			}
 			_ => { Err(AWSError::new("error")) }
 		}
@@ -11470,41 +13406,52 @@ impl<'a> S3Client<'a> {
 	/// This operation enables you to delete multiple objects from a bucket using a
 	/// single HTTP request. You may specify up to 1000 keys.
 	pub fn delete_objects(&mut self, input: &DeleteObjectsRequest) -> Result<DeleteObjectsOutput, AWSError> {
-		// let mut uri = String::from("/");
-		// uri = uri +  &input.key.to_string();
-		// let mut request = SignedRequest::new("DELETE", "s3", &self.region, &uri);
-		// let mut params = Params::new();
-		//
-		// let hostname = (&input.bucket).to_string() + ".s3.amazonaws.com";
-		// request.set_hostname(Some(hostname));
-		//
-		// params.put("Action", "DeleteObjects");
-		// DeleteObjectsRequestWriter::write_params(&mut params, "", &input);
-		// request.set_params(params);
-		// let result = request.sign_and_execute(try!(self.creds.get_credentials()));
-		// let status = result.status.to_u16();
-		// match status {
-		// 	200 => {
-		// 		Ok(try!(DeleteObjectsOutputParser::parse_xml("DeleteObjectsOutput", &mut stack)))
-		// 	}
-		// 	_ => { Err(AWSError::new("error")) }
-		// }
-		Err(AWSError::new("not implemented"))
+		let mut uri = "/{Bucket}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		let payload = with_xml_namespace(&DeleteWriter::write_xml("Delete", &input.delete), "http://s3.amazonaws.com/doc/2006-03-01/");
+		let mut request = SignedRequest::new("POST", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
+		let mut params = Params::new();
+		params.put("delete", "");
+		if let Some(ref obj) = input.mfa {
+			request.add_header("x-amz-mfa", obj);
+		}
+		if let Some(ref obj) = input.request_payer {
+			request.add_header("x-amz-request-payer", obj);
+		}
+		request.set_payload(Some(payload.as_bytes()));
+		request.add_header("Content-MD5", &hash(MD5, payload.as_bytes()).to_base64(STANDARD));
+		request.set_params(params);
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
+		let status = result.status.to_u16();
+		match status {
+			200...299 => {
+				let headers = result.headers.clone();
+				let mut reader = EventReader::new(result);
+				let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+				stack.next(); // xml start tag
+				let root = try!(peek_at_name(&mut stack));
+				let mut obj = try!(DeleteObjectsOutputParser::parse_xml(&root, &mut stack));
+				try!(DeleteObjectsOutputParser::parse_headers(&headers, &mut obj));
+				Ok(obj)
+			}
+			_ => { Err(AWSError::new("error")) }
+		}
 	}
 	pub fn delete_bucket_replication(&mut self, input: &DeleteBucketReplicationRequest) -> Result<(), AWSError> {
-		let mut request = SignedRequest::new("DELETE", "s3", &self.region, "/{Bucket}?replication");
+		let mut uri = "/{Bucket}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		let mut request = SignedRequest::new("DELETE", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
 		let mut params = Params::new();
-		params.put("Action", "DeleteBucketReplication");
-		DeleteBucketReplicationRequestWriter::write_params(&mut params, "", &input);
+		params.put("replication", "");
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
 		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
 		match status {
-			200 => {
+			200...299 => {
 				Ok(())
 			}
 			_ => { Err(AWSError::new("error")) }
@@ -11512,20 +13459,116 @@ impl<'a> S3Client<'a> {
 	}
 	/// Creates a copy of an object that is already stored in Amazon S3.
 	pub fn copy_object(&mut self, input: &CopyObjectRequest) -> Result<CopyObjectOutput, AWSError> {
-		let mut request = SignedRequest::new("PUT", "s3", &self.region, "/{Bucket}/{Key+}");
-		let mut params = Params::new();
-		params.put("Action", "CopyObject");
-		CopyObjectRequestWriter::write_params(&mut params, "", &input);
+		let mut uri = "/{Bucket}/{Key+}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		uri = uri.replace("{Key+}", &encode_uri_path(&input.key));
+		let mut request = SignedRequest::new("PUT", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
+		let params = Params::new();
+		if let Some(ref obj) = input.acl {
+			request.add_header("x-amz-acl", &obj.to_string());
+		}
+		if let Some(ref obj) = input.cache_control {
+			request.add_header("Cache-Control", obj);
+		}
+		if let Some(ref obj) = input.content_disposition {
+			request.add_header("Content-Disposition", obj);
+		}
+		if let Some(ref obj) = input.content_encoding {
+			request.add_header("Content-Encoding", obj);
+		}
+		if let Some(ref obj) = input.content_language {
+			request.add_header("Content-Language", obj);
+		}
+		if let Some(ref obj) = input.content_type {
+			request.add_header("Content-Type", obj);
+		}
+		{
+			let obj = &input.copy_source;
+			request.add_header("x-amz-copy-source", obj);
+		}
+		if let Some(ref obj) = input.copy_source_if_match {
+			request.add_header("x-amz-copy-source-if-match", obj);
+		}
+		if let Some(ref obj) = input.copy_source_if_modified_since {
+			request.add_header("x-amz-copy-source-if-modified-since", obj);
+		}
+		if let Some(ref obj) = input.copy_source_if_none_match {
+			request.add_header("x-amz-copy-source-if-none-match", obj);
+		}
+		if let Some(ref obj) = input.copy_source_if_unmodified_since {
+			request.add_header("x-amz-copy-source-if-unmodified-since", obj);
+		}
+		if let Some(ref obj) = input.expires {
+			request.add_header("Expires", obj);
+		}
+		if let Some(ref obj) = input.grant_full_control {
+			request.add_header("x-amz-grant-full-control", obj);
+		}
+		if let Some(ref obj) = input.grant_read {
+			request.add_header("x-amz-grant-read", obj);
+		}
+		if let Some(ref obj) = input.grant_read_acp {
+			request.add_header("x-amz-grant-read-acp", obj);
+		}
+		if let Some(ref obj) = input.grant_write_acp {
+			request.add_header("x-amz-grant-write-acp", obj);
+		}
+		if let Some(ref obj) = input.metadata {
+			for (key, value) in obj.iter() {
+				request.add_header(&format!("x-amz-meta-{}", key), value);
+			}
+		}
+		if let Some(ref obj) = input.metadata_directive {
+			request.add_header("x-amz-metadata-directive", obj);
+		}
+		if let Some(ref obj) = input.server_side_encryption {
+			request.add_header("x-amz-server-side-encryption", obj);
+		}
+		if let Some(ref obj) = input.storage_class {
+			request.add_header("x-amz-storage-class", obj);
+		}
+		if let Some(ref obj) = input.website_redirect_location {
+			request.add_header("x-amz-website-redirect-location", obj);
+		}
+		if let Some(ref obj) = input.sse_customer_algorithm {
+			request.add_header("x-amz-server-side-encryption-customer-algorithm", obj);
+		}
+		if let Some(ref obj) = input.sse_customer_key {
+			request.add_header("x-amz-server-side-encryption-customer-key", obj);
+		}
+		if let Some(ref obj) = input.sse_customer_key_md5 {
+			request.add_header("x-amz-server-side-encryption-customer-key-MD5", obj);
+		}
+		if let Some(ref obj) = input.ssekms_key_id {
+			request.add_header("x-amz-server-side-encryption-aws-kms-key-id", obj);
+		}
+		if let Some(ref obj) = input.copy_source_sse_customer_algorithm {
+			request.add_header("x-amz-copy-source-server-side-encryption-customer-algorithm", obj);
+		}
+		if let Some(ref obj) = input.copy_source_sse_customer_key {
+			request.add_header("x-amz-copy-source-server-side-encryption-customer-key", obj);
+		}
+		if let Some(ref obj) = input.copy_source_sse_customer_key_md5 {
+			request.add_header("x-amz-copy-source-server-side-encryption-customer-key-MD5", obj);
+		}
+		if let Some(ref obj) = input.request_payer {
+			request.add_header("x-amz-request-payer", obj);
+		}
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
 		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
 		match status {
-			200 => {
-				Ok(try!(CopyObjectOutputParser::parse_xml("CopyObjectOutput", &mut stack)))
+			200...299 => {
+				let mut obj = CopyObjectOutput::default();
+				try!(CopyObjectOutputParser::parse_headers(&result.headers, &mut obj));
+				let mut reader = EventReader::new(result);
+				let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+				stack.next(); // xml start tag
+				let body = try!(CopyObjectResultParser::parse_xml("CopyObjectResult", &mut stack));
+				obj.copy_object_result = body;
+				Ok(obj)
 			}
 			_ => { Err(AWSError::new("error")) }
 		}
@@ -11533,23 +13576,23 @@ impl<'a> S3Client<'a> {
 	/// Returns a list of all buckets owned by the authenticated sender of the
 	/// request.
 	pub fn list_buckets(&mut self) -> Result<ListBucketsOutput, AWSError> {
-		let mut request = SignedRequest::new("GET", "s3", &self.region, "/");
-		let mut params = Params::new();
-		params.put("Action", "ListBuckets");
+		let uri = "/".to_string();
+		let mut request = SignedRequest::new("GET", "s3", &self.region, &uri);
+		request.set_hostname(Some(self.endpoint.service_hostname(self.region)));
+		let params = Params::new();
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(request.sign_and_execute(try!(self.creds.get_credentials())));
 		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-
-		stack.next(); // xml start tag
-
 		match status {
-			200 => {
-				// was "ListBucketsOutput"
-				Ok(try!(ListBucketsOutputParser::parse_xml("ListAllMyBucketsResult", &mut stack)))
+			200...299 => {
+				let mut reader = EventReader::new(result);
+				let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+				stack.next(); // xml start tag
+				let root = try!(peek_at_name(&mut stack));
+				Ok(try!(ListBucketsOutputParser::parse_xml(&root, &mut stack)))
 			}
-			_ => { Err(AWSError::new("error in list_buckets")) }
+			_ => { Err(AWSError::new("error")) }
 		}
 	}
 	/// Sets the request payment configuration for a bucket. By default, the bucket
@@ -11559,19 +13602,26 @@ impl<'a> S3Client<'a> {
 	/// be found at
 	/// http://docs.aws.amazon.com/AmazonS3/latest/dev/RequesterPaysBuckets.html
 	pub fn put_bucket_request_payment(&mut self, input: &PutBucketRequestPaymentRequest) -> Result<(), AWSError> {
-		let mut request = SignedRequest::new("PUT", "s3", &self.region, "/{Bucket}?requestPayment");
+		let mut uri = "/{Bucket}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		let payload = with_xml_namespace(&RequestPaymentConfigurationWriter::write_xml("RequestPaymentConfiguration", &input.request_payment_configuration), "http://s3.amazonaws.com/doc/2006-03-01/");
+		let mut request = SignedRequest::new("PUT", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
 		let mut params = Params::new();
-		params.put("Action", "PutBucketRequestPayment");
-		PutBucketRequestPaymentRequestWriter::write_params(&mut params, "", &input);
+		params.put("requestPayment", "");
+		if let Some(ref obj) = input.content_md5 {
+			request.add_header("Content-MD5", obj);
+		}
+		request.set_payload(Some(payload.as_bytes()));
+		if input.content_md5.is_none() {
+			request.add_header("Content-MD5", &hash(MD5, payload.as_bytes()).to_base64(STANDARD));
+		}
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
 		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
 		match status {
-			200 => {
+			200...299 => {
 				Ok(())
 			}
 			_ => { Err(AWSError::new("error")) }
@@ -11579,19 +13629,20 @@ impl<'a> S3Client<'a> {
 	}
 	/// Enables notifications of specified events for a bucket.
 	pub fn put_bucket_notification_configuration(&mut self, input: &PutBucketNotificationConfigurationRequest) -> Result<(), AWSError> {
-		let mut request = SignedRequest::new("PUT", "s3", &self.region, "/{Bucket}?notification");
+		let mut uri = "/{Bucket}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		let payload = with_xml_namespace(&NotificationConfigurationWriter::write_xml("NotificationConfiguration", &input.notification_configuration), "http://s3.amazonaws.com/doc/2006-03-01/");
+		let mut request = SignedRequest::new("PUT", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
 		let mut params = Params::new();
-		params.put("Action", "PutBucketNotificationConfiguration");
-		PutBucketNotificationConfigurationRequestWriter::write_params(&mut params, "", &input);
+		params.put("notification", "");
+		request.set_payload(Some(payload.as_bytes()));
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
 		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
 		match status {
-			200 => {
+			200...299 => {
 				Ok(())
 			}
 			_ => { Err(AWSError::new("error")) }
@@ -11601,39 +13652,69 @@ impl<'a> S3Client<'a> {
 	/// object itself. This operation is useful if you're only interested in an
 	/// object's metadata. To use HEAD, you must have READ access to the object.
 	pub fn head_object(&mut self, input: &HeadObjectRequest) -> Result<HeadObjectOutput, AWSError> {
-		let mut request = SignedRequest::new("HEAD", "s3", &self.region, "/{Bucket}/{Key+}");
+		let mut uri = "/{Bucket}/{Key+}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		uri = uri.replace("{Key+}", &encode_uri_path(&input.key));
+		let mut request = SignedRequest::new("HEAD", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
 		let mut params = Params::new();
-		params.put("Action", "HeadObject");
-		HeadObjectRequestWriter::write_params(&mut params, "", &input);
+		if let Some(ref obj) = input.if_match {
+			request.add_header("If-Match", obj);
+		}
+		if let Some(ref obj) = input.if_modified_since {
+			request.add_header("If-Modified-Since", obj);
+		}
+		if let Some(ref obj) = input.if_none_match {
+			request.add_header("If-None-Match", obj);
+		}
+		if let Some(ref obj) = input.if_unmodified_since {
+			request.add_header("If-Unmodified-Since", obj);
+		}
+		if let Some(ref obj) = input.range {
+			request.add_header("Range", obj);
+		}
+		if let Some(ref obj) = input.version_id {
+			params.put("versionId", obj);
+		}
+		if let Some(ref obj) = input.sse_customer_algorithm {
+			request.add_header("x-amz-server-side-encryption-customer-algorithm", obj);
+		}
+		if let Some(ref obj) = input.sse_customer_key {
+			request.add_header("x-amz-server-side-encryption-customer-key", obj);
+		}
+		if let Some(ref obj) = input.sse_customer_key_md5 {
+			request.add_header("x-amz-server-side-encryption-customer-key-MD5", obj);
+		}
+		if let Some(ref obj) = input.request_payer {
+			request.add_header("x-amz-request-payer", obj);
+		}
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
 		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
 		match status {
-			200 => {
-				Ok(try!(HeadObjectOutputParser::parse_xml("HeadObjectOutput", &mut stack)))
+			200...299 => {
+				let mut obj = HeadObjectOutput::default();
+				try!(HeadObjectOutputParser::parse_headers(&result.headers, &mut obj));
+				Ok(obj)
 			}
 			_ => { Err(AWSError::new("error")) }
 		}
 	}
 	/// Deletes the tags from the bucket.
 	pub fn delete_bucket_tagging(&mut self, input: &DeleteBucketTaggingRequest) -> Result<(), AWSError> {
-		let mut request = SignedRequest::new("DELETE", "s3", &self.region, "/{Bucket}?tagging");
+		let mut uri = "/{Bucket}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		let mut request = SignedRequest::new("DELETE", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
 		let mut params = Params::new();
-		params.put("Action", "DeleteBucketTagging");
-		DeleteBucketTaggingRequestWriter::write_params(&mut params, "", &input);
+		params.put("tagging", "");
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
 		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
 		match status {
-			200 => {
+			200...299 => {
 				Ok(())
 			}
 			_ => { Err(AWSError::new("error")) }
@@ -11641,128 +13722,77 @@ impl<'a> S3Client<'a> {
 	}
 	/// Return torrent files from a bucket.
 	pub fn get_object_torrent(&mut self, input: &GetObjectTorrentRequest) -> Result<GetObjectTorrentOutput, AWSError> {
-		let mut request = SignedRequest::new("GET", "s3", &self.region, "/{Bucket}/{Key+}?torrent");
+		let mut uri = "/{Bucket}/{Key+}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		uri = uri.replace("{Key+}", &encode_uri_path(&input.key));
+		let mut request = SignedRequest::new("GET", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
 		let mut params = Params::new();
-		params.put("Action", "GetObjectTorrent");
-		GetObjectTorrentRequestWriter::write_params(&mut params, "", &input);
+		params.put("torrent", "");
+		if let Some(ref obj) = input.request_payer {
+			request.add_header("x-amz-request-payer", obj);
+		}
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
 		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
 		match status {
-			200 => {
-				Ok(try!(GetObjectTorrentOutputParser::parse_xml("GetObjectTorrentOutput", &mut stack)))
+			200...299 => {
+				let mut obj = GetObjectTorrentOutput::default();
+				try!(GetObjectTorrentOutputParser::parse_headers(&result.headers, &mut obj));
+				let mut result = result;
+				let mut body = Vec::new();
+				if let Err(why) = result.read_to_end(&mut body) {
+					return Err(AWSError::new(format!("Couldn't read response body: {}", why)));
+				}
+				obj.body = body;
+				Ok(obj)
 			}
 			_ => { Err(AWSError::new("error")) }
 		}
 	}
 	/// Returns the lifecycle configuration information set on the bucket.
 	pub fn get_bucket_lifecycle(&mut self, input: &GetBucketLifecycleRequest) -> Result<GetBucketLifecycleOutput, AWSError> {
-		let mut request = SignedRequest::new("GET", "s3", &self.region, "/{Bucket}?lifecycle");
-		let mut params = Params::new();
-		params.put("Action", "GetBucketLifecycle");
-		GetBucketLifecycleRequestWriter::write_params(&mut params, "", &input);
-		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
-		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
-		match status {
-			200 => {
-				Ok(try!(GetBucketLifecycleOutputParser::parse_xml("GetBucketLifecycleOutput", &mut stack)))
-			}
-			_ => { Err(AWSError::new("error")) }
-		}
-	}
-	/// Creates a new bucket.
-	/// All requests go to the us-east-1/us-standard endpoint, but can create buckets anywhere.
-	pub fn create_bucket(&mut self, input: &CreateBucketRequest) -> Result<CreateBucketOutput, AWSError> {
-		let region = Region::UsEast1;
-		let mut create_config : Vec<u8>;
-		let mut request = SignedRequest::new("PUT", "s3", &region, "");
-		let hostname = format!("{}.s3.amazonaws.com", input.bucket);
-		request.set_hostname(Some(hostname));
-
-		if needs_create_bucket_config(&self.region) {
-			create_config = create_bucket_config_xml(&self.region);
-			request.set_payload(Some(&create_config));
-		}
-
-		match input.acl {
-			None => (),
-			Some(ref canned_acl) => request.add_header("x-amz-acl", &canned_acl_in_aws_format(&canned_acl)),
-		}
-
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
-		let status = result.status.to_u16();
-
-		match status {
-			200 => {
-				for header in result.headers.iter() {
-					if header.name() == "Location" {
-						return Ok(CreateBucketOutput{location: header.value_string()});
-					}
-				}
-				panic!("Something went wrong when creating a bucket.");
-			}
-			_ => {
-				Err(AWSError::new("error in create_bucket"))
-			}
-		}
-	}
-	/// Completes a multipart upload by assembling previously uploaded parts.
-	pub fn complete_multipart_upload(&mut self, input: &CompleteMultipartUploadRequest) -> Result<CompleteMultipartUploadOutput, AWSError> {
-		let mut request = SignedRequest::new("POST", "s3", &self.region,
-			&format!("/{}", input.key));
-
+		let mut uri = "/{Bucket}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		let mut request = SignedRequest::new("GET", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
 		let mut params = Params::new();
-		params.put("uploadId", &input.upload_id.to_string());
+		params.put("lifecycle", "");
 		request.set_params(params);
-
-		let hostname = (&input.bucket).to_string() + ".s3.amazonaws.com";
-		request.set_hostname(Some(hostname));
-
-		request.set_payload(input.multipart_upload);
-
-		let mut result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
 		let status = result.status.to_u16();
-
 		match status {
-			200 => {
+			200...299 => {
 				let mut reader = EventReader::new(result);
 				let mut stack = XmlResponseFromAws::new(reader.events().peekable());
 				stack.next(); // xml start tag
-
-				Ok(try!(CompleteMultipartUploadOutputParser::parse_xml("CompleteMultipartUploadResult", &mut stack)))
-			}
-			_ => {
-				let mut body = String::new();
-			    result.read_to_string(&mut body).unwrap();
-				Err(AWSError::new("error in complete_multipart_upload"))
+				let root = try!(peek_at_name(&mut stack));
+				Ok(try!(GetBucketLifecycleOutputParser::parse_xml(&root, &mut stack)))
 			}
+			_ => { Err(AWSError::new("error")) }
 		}
 	}
 	/// Returns the website configuration for a bucket.
 	pub fn get_bucket_website(&mut self, input: &GetBucketWebsiteRequest) -> Result<GetBucketWebsiteOutput, AWSError> {
-		let mut request = SignedRequest::new("GET", "s3", &self.region, "/{Bucket}?website");
+		let mut uri = "/{Bucket}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		let mut request = SignedRequest::new("GET", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
 		let mut params = Params::new();
-		params.put("Action", "GetBucketWebsite");
-		GetBucketWebsiteRequestWriter::write_params(&mut params, "", &input);
+		params.put("website", "");
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
 		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
 		match status {
-			200 => {
-				Ok(try!(GetBucketWebsiteOutputParser::parse_xml("GetBucketWebsiteOutput", &mut stack)))
+			200...299 => {
+				let mut reader = EventReader::new(result);
+				let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+				stack.next(); // xml start tag
+				let root = try!(peek_at_name(&mut stack));
+				Ok(try!(GetBucketWebsiteOutputParser::parse_xml(&root, &mut stack)))
 			}
 			_ => { Err(AWSError::new("error")) }
 		}
@@ -11770,274 +13800,191 @@ impl<'a> S3Client<'a> {
 	/// Initiates a multipart upload and returns an upload ID.
 	/// **Note:** After you initiate multipart upload and upload one or more parts, you must either complete or abort multipart upload in order to stop getting charged for storage of the uploaded parts. Only after you either complete or abort multipart upload, Amazon S3 frees up the parts storage and stops charging you for the parts storage.
 	pub fn create_multipart_upload(&mut self, input: &CreateMultipartUploadRequest) -> Result<CreateMultipartUploadOutput, AWSError> {
-
-		let ref object_name = input.key;
-		let mut request = SignedRequest::new("POST", "s3", &self.region, &format!("/{}", object_name));
-
+		let mut uri = "/{Bucket}/{Key+}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		uri = uri.replace("{Key+}", &encode_uri_path(&input.key));
+		let mut request = SignedRequest::new("POST", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
 		let mut params = Params::new();
 		params.put("uploads", "");
-		request.set_params(params);
-
-		let hostname = (&input.bucket).to_string() + ".s3.amazonaws.com";
-		request.set_hostname(Some(hostname));
-
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
-		let status = result.status.to_u16();
-
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		match status {
-			200 => {
-				Ok(try!(CreateMultipartUploadOutputParser::parse_xml("InitiateMultipartUploadResult", &mut stack)))
-			}
-			_ => { Err(AWSError::new("error")) }
+		if let Some(ref obj) = input.acl {
+			request.add_header("x-amz-acl", obj);
 		}
-	}
-	/// Deletes the bucket. All objects (including all object versions and Delete
-	/// Markers) in the bucket must be deleted before the bucket itself can be
-	/// deleted.
-	pub fn delete_bucket(&mut self, input: &DeleteBucketRequest, region: &Region) -> Result<(), AWSError> {
-		let mut request = SignedRequest::new("DELETE", "s3", region, "");
-
-		let hostname = (&input.bucket).to_string() + ".s3.amazonaws.com";
-		request.set_hostname(Some(hostname));
-
-		let mut result = request.sign_and_execute(try!(self.creds.get_credentials()));
-		let status = result.status.to_u16();
-		match status {
-			204 => {
-				Ok(())
+		if let Some(ref obj) = input.cache_control {
+			request.add_header("Cache-Control", obj);
+		}
+		if let Some(ref obj) = input.content_disposition {
+			request.add_header("Content-Disposition", obj);
+		}
+		if let Some(ref obj) = input.content_encoding {
+			request.add_header("Content-Encoding", obj);
+		}
+		if let Some(ref obj) = input.content_language {
+			request.add_header("Content-Language", obj);
+		}
+		if let Some(ref obj) = input.content_type {
+			request.add_header("Content-Type", obj);
+		}
+		if let Some(ref obj) = input.expires {
+			request.add_header("Expires", obj);
+		}
+		if let Some(ref obj) = input.grant_full_control {
+			request.add_header("x-amz-grant-full-control", obj);
+		}
+		if let Some(ref obj) = input.grant_read {
+			request.add_header("x-amz-grant-read", obj);
+		}
+		if let Some(ref obj) = input.grant_read_acp {
+			request.add_header("x-amz-grant-read-acp", obj);
+		}
+		if let Some(ref obj) = input.grant_write_acp {
+			request.add_header("x-amz-grant-write-acp", obj);
+		}
+		if let Some(ref obj) = input.metadata {
+			for (key, value) in obj.iter() {
+				request.add_header(&format!("x-amz-meta-{}", key), value);
 			}
-			_ => {
-				let mut body = String::new();
-			    result.read_to_string(&mut body).unwrap();
-				println!("resposne body: {}", body);
-				Err(AWSError::new(format!("delete bucket error, status was {}", status)))
-			}
-		}
-	}
-
-	pub fn get_value_for_header(header_name: String, response: &Response) -> Result<String, AWSError> {
-		for header in response.headers.iter() {
-			if header.name() == header_name {
-				return Ok(header.value_string());
-			}
-		}
-		Ok(String::new())
-		// Err(AWSError::new(format!("Couldn't find field {} in headers", header_name)))
-	}
-
-	/// Use the Hyper resposne to populate the GetObjectOutput
-	// This would be a great candidate for some codegen magicks.
-	pub fn get_object_from_response(response: &mut Response) -> Result<GetObjectOutput, AWSError> {
-		// get all the goodies for GetObjectOutput
-		let delete_marker_string = try!(S3Client::get_value_for_header("x-amz-delete-marker".to_string(), &response));
-		let delete_marker : bool;
-		if delete_marker_string.len() > 0 {
-			delete_marker = bool::from_str(&delete_marker_string).unwrap();
-		} else {
-			delete_marker = false;
-		}
-		let accept_ranges = try!(S3Client::get_value_for_header("accept-ranges".to_string(), response));
-		let last_modified = try!(S3Client::get_value_for_header("Last-Modified".to_string(), response));
-		let content_range = try!(S3Client::get_value_for_header("Content-Range".to_string(), response));
-		let request_charged = try!(S3Client::get_value_for_header("x-amz-request-charged".to_string(), response));
-		let content_encoding = try!(S3Client::get_value_for_header("Content-Encoding".to_string(), response));
-		let replication_status = try!(S3Client::get_value_for_header("x-amz-replication-status".to_string(), response));
-		let storage_class = try!(S3Client::get_value_for_header("x-amz-storage-class".to_string(), response));
-		let server_side_encryption = try!(S3Client::get_value_for_header("x-amz-server-side-encryption".to_string(), response));
-		let ssekms_key_id = try!(S3Client::get_value_for_header("x-amz-server-side-encryption-aws-kms-key-id".to_string(), response));
-		let content_disposition = try!(S3Client::get_value_for_header("Content-Disposition".to_string(), response));
-		let metadata = try!(S3Client::get_value_for_header("x-amz-meta-".to_string(), response));
-		let website_redirect_location = try!(S3Client::get_value_for_header("x-amz-website-redirect-location".to_string(), response));
-		let expires = try!(S3Client::get_value_for_header("Expires".to_string(), response));
-		let cache_control = try!(S3Client::get_value_for_header("Cache-Control".to_string(), response));
-		let content_length_string = try!(S3Client::get_value_for_header("Content-Length".to_string(), response));
-		let content_length = content_length_string.parse::<i32>().unwrap();
-		let expiration = try!(S3Client::get_value_for_header("x-amz-expiration".to_string(), response));
-		let missing_meta_string = try!(S3Client::get_value_for_header("x-amz-missing-meta".to_string(), response));
-		let missing_meta : i32;
-		if missing_meta_string.len() > 0 {
-			missing_meta = missing_meta_string.parse::<i32>().unwrap();
-		} else {
-			missing_meta = 0;
-		}
-		let restore = try!(S3Client::get_value_for_header("x-amz-restore".to_string(), response));
-		let sse_customer_algorithm = try!(S3Client::get_value_for_header("x-amz-server-side-encryption-customer-algorithm".to_string(), response));
-		let content_type = try!(S3Client::get_value_for_header("Content-Type".to_string(), response));
-		let content_language = try!(S3Client::get_value_for_header("Content-Language".to_string(), response));
-		let version_id = try!(S3Client::get_value_for_header("x-amz-version-id".to_string(), response));
-		let e_tag = try!(S3Client::get_value_for_header("ETag".to_string(), response));
-		let sse_customer_key_md5 = try!(S3Client::get_value_for_header("x-amz-server-side-encryption-customer-key-MD5".to_string(), response));
-		let mut body : Vec<u8> = Vec::new();
-	    response.read_to_end(&mut body).unwrap();
-		// make the object to return
-		let s3_object = GetObjectOutput {
-			delete_marker: delete_marker,
-			accept_ranges: accept_ranges,
-			last_modified: last_modified,
-			content_range: content_range,
-			request_charged: request_charged,
-			content_encoding: content_encoding,
-			replication_status: replication_status,
-			storage_class: storage_class,
-			server_side_encryption: server_side_encryption,
-			ssekms_key_id: ssekms_key_id,
-			content_disposition: content_disposition,
-			metadata: HashMap::new(),
-			body: body,
-			website_redirect_location: website_redirect_location,
-			expires: expires,
-			cache_control: cache_control,
-			content_length: content_length,
-			expiration: expiration,
-			missing_meta: missing_meta,
-			restore: restore,
-			sse_customer_algorithm: sse_customer_algorithm,
-			content_type: content_type,
-			content_language: content_language,
-			version_id: version_id,
-			e_tag: e_tag,
-			sse_customer_key_md5: sse_customer_key_md5,
-		};
-		Ok(s3_object)
-	}
-
-	/// Retrieves objects from Amazon S3.
-	pub fn get_object(&mut self, input: &GetObjectRequest) -> Result<GetObjectOutput, AWSError> {
-		let mut uri = String::from("/");
-		uri = uri +  &input.key.to_string();
-		let mut request = SignedRequest::new("GET", "s3", &self.region, &uri);
-		let mut params = Params::new();
-
-		let hostname = (&input.bucket).to_string() + ".s3.amazonaws.com";
-		request.set_hostname(Some(hostname));
-
-		params.put("Action", "GetObject");
-		GetObjectRequestWriter::write_params(&mut params, "", &input);
-
+		}
+		if let Some(ref obj) = input.server_side_encryption {
+			request.add_header("x-amz-server-side-encryption", obj);
+		}
+		if let Some(ref obj) = input.storage_class {
+			request.add_header("x-amz-storage-class", obj);
+		}
+		if let Some(ref obj) = input.website_redirect_location {
+			request.add_header("x-amz-website-redirect-location", obj);
+		}
+		if let Some(ref obj) = input.sse_customer_algorithm {
+			request.add_header("x-amz-server-side-encryption-customer-algorithm", obj);
+		}
+		if let Some(ref obj) = input.sse_customer_key {
+			request.add_header("x-amz-server-side-encryption-customer-key", obj);
+		}
+		if let Some(ref obj) = input.sse_customer_key_md5 {
+			request.add_header("x-amz-server-side-encryption-customer-key-MD5", obj);
+		}
+		if let Some(ref obj) = input.ssekms_key_id {
+			request.add_header("x-amz-server-side-encryption-aws-kms-key-id", obj);
+		}
+		if let Some(ref obj) = input.request_payer {
+			request.add_header("x-amz-request-payer", obj);
+		}
+		if let Some(ref obj) = input.tagging {
+			request.add_header("x-amz-tagging", obj);
+		}
 		request.set_params(params);
-		let mut result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
 		let status = result.status.to_u16();
-
 		match status {
-			200 => {
-				let s3_object = try!(S3Client::get_object_from_response(&mut result));
-
-				return Ok(s3_object);
-			}
-			_ => {
-				println!("Error: Status code was {}", status);
-				let mut body = String::new();
-			    result.read_to_string(&mut body).unwrap();
-			    println!("Error response body: {}", body);
-				Err(AWSError::new("error in get_object"))
+			200...299 => {
+				let headers = result.headers.clone();
+				let mut reader = EventReader::new(result);
+				let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+				stack.next(); // xml start tag
+				let root = try!(peek_at_name(&mut stack));
+				let mut obj = try!(CreateMultipartUploadOutputParser::parse_xml(&root, &mut stack));
+				try!(CreateMultipartUploadOutputParser::parse_headers(&headers, &mut obj));
+				Ok(obj)
 			}
+			_ => { Err(AWSError::new("error")) }
 		}
 	}
 
 	/// Returns the policy of a specified bucket.
 	pub fn get_bucket_policy(&mut self, input: &GetBucketPolicyRequest) -> Result<GetBucketPolicyOutput, AWSError> {
-		let mut request = SignedRequest::new("GET", "s3", &self.region, "/{Bucket}?policy");
+		let mut uri = "/{Bucket}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		let mut request = SignedRequest::new("GET", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
 		let mut params = Params::new();
-		params.put("Action", "GetBucketPolicy");
-		GetBucketPolicyRequestWriter::write_params(&mut params, "", &input);
+		params.put("policy", "");
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
 		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
 		match status {
-			200 => {
-				Ok(try!(GetBucketPolicyOutputParser::parse_xml("GetBucketPolicyOutput", &mut stack)))
+			200...299 => {
+				let mut obj = GetBucketPolicyOutput::default();
+				let mut result = result;
+				let mut body = String::new();
+				if let Err(why) = result.read_to_string(&mut body) {
+					return Err(AWSError::new(format!("Couldn't read response body: {}", why)));
+				}
+				obj.policy = body;
+				Ok(obj)
 			}
 			_ => { Err(AWSError::new("error")) }
 		}
 	}
 	/// Returns the versioning state of a bucket.
 	pub fn get_bucket_versioning(&mut self, input: &GetBucketVersioningRequest) -> Result<GetBucketVersioningOutput, AWSError> {
-		let mut request = SignedRequest::new("GET", "s3", &self.region, "/{Bucket}?versioning");
+		let mut uri = "/{Bucket}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		let mut request = SignedRequest::new("GET", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
 		let mut params = Params::new();
-		params.put("Action", "GetBucketVersioning");
-		GetBucketVersioningRequestWriter::write_params(&mut params, "", &input);
+		params.put("versioning", "");
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
 		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
 		match status {
-			200 => {
-				Ok(try!(GetBucketVersioningOutputParser::parse_xml("GetBucketVersioningOutput", &mut stack)))
+			200...299 => {
+				let mut reader = EventReader::new(result);
+				let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+				stack.next(); // xml start tag
+				let root = try!(peek_at_name(&mut stack));
+				Ok(try!(GetBucketVersioningOutputParser::parse_xml(&root, &mut stack)))
 			}
 			_ => { Err(AWSError::new("error")) }
 		}
 	}
-	/// This operation lists in-progress multipart uploads.
-	pub fn list_multipart_uploads(&mut self, input: &ListMultipartUploadsRequest) -> Result<ListMultipartUploadsOutput, AWSError> {
-		let mut request = SignedRequest::new("GET", "s3", &self.region, "/");
-
-		let mut params = Params::new();
-		params.put("uploads", "");
-		request.set_params(params);
-
-		let hostname = (&input.bucket).to_string() + ".s3.amazonaws.com";
-		request.set_hostname(Some(hostname));
-
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
-		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-
-		match status {
-			200 => {
-				Ok(try!(ListMultipartUploadsOutputParser::parse_xml("ListMultipartUploadsResult", &mut stack)))
-			}
-			_ => {
-				Err(AWSError::new("error"))
-			}
-		}
-	}
 	/// Returns the request payment configuration of a bucket.
 	pub fn get_bucket_request_payment(&mut self, input: &GetBucketRequestPaymentRequest) -> Result<GetBucketRequestPaymentOutput, AWSError> {
-		let mut request = SignedRequest::new("GET", "s3", &self.region, "/{Bucket}?requestPayment");
+		let mut uri = "/{Bucket}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		let mut request = SignedRequest::new("GET", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
 		let mut params = Params::new();
-		params.put("Action", "GetBucketRequestPayment");
-		GetBucketRequestPaymentRequestWriter::write_params(&mut params, "", &input);
+		params.put("requestPayment", "");
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
 		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
 		match status {
-			200 => {
-				Ok(try!(GetBucketRequestPaymentOutputParser::parse_xml("GetBucketRequestPaymentOutput", &mut stack)))
+			200...299 => {
+				let mut reader = EventReader::new(result);
+				let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+				stack.next(); // xml start tag
+				let root = try!(peek_at_name(&mut stack));
+				Ok(try!(GetBucketRequestPaymentOutputParser::parse_xml(&root, &mut stack)))
 			}
 			_ => { Err(AWSError::new("error")) }
 		}
 	}
 	/// Sets the tags for a bucket.
 	pub fn put_bucket_tagging(&mut self, input: &PutBucketTaggingRequest) -> Result<(), AWSError> {
-		let mut request = SignedRequest::new("PUT", "s3", &self.region, "/{Bucket}?tagging");
+		let mut uri = "/{Bucket}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		let payload = with_xml_namespace(&TaggingWriter::write_xml("Tagging", &input.tagging), "http://s3.amazonaws.com/doc/2006-03-01/");
+		let mut request = SignedRequest::new("PUT", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
 		let mut params = Params::new();
-		params.put("Action", "PutBucketTagging");
-		PutBucketTaggingRequestWriter::write_params(&mut params, "", &input);
+		params.put("tagging", "");
+		if let Some(ref obj) = input.content_md5 {
+			request.add_header("Content-MD5", obj);
+		}
+		request.set_payload(Some(payload.as_bytes()));
+		if input.content_md5.is_none() {
+			request.add_header("Content-MD5", &hash(MD5, payload.as_bytes()).to_base64(STANDARD));
+		}
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
 		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
 		match status {
-			200 => {
+			200...299 => {
 				Ok(())
 			}
 			_ => { Err(AWSError::new("error")) }
@@ -12045,107 +13992,134 @@ impl<'a> S3Client<'a> {
 	}
 	/// Returns the tag set associated with the bucket.
 	pub fn get_bucket_tagging(&mut self, input: &GetBucketTaggingRequest) -> Result<GetBucketTaggingOutput, AWSError> {
-		let mut request = SignedRequest::new("GET", "s3", &self.region, "/{Bucket}?tagging");
+		let mut uri = "/{Bucket}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		let mut request = SignedRequest::new("GET", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
 		let mut params = Params::new();
-		params.put("Action", "GetBucketTagging");
-		GetBucketTaggingRequestWriter::write_params(&mut params, "", &input);
+		params.put("tagging", "");
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
 		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
 		match status {
-			200 => {
-				Ok(try!(GetBucketTaggingOutputParser::parse_xml("GetBucketTaggingOutput", &mut stack)))
+			200...299 => {
+				let mut reader = EventReader::new(result);
+				let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+				stack.next(); // xml start tag
+				let root = try!(peek_at_name(&mut stack));
+				Ok(try!(GetBucketTaggingOutputParser::parse_xml(&root, &mut stack)))
 			}
 			_ => { Err(AWSError::new("error")) }
 		}
 	}
-	/// Aborts a multipart upload.
-	/// To verify that all parts have been removed, so you don't get charged for the
-	/// part storage, you should call the List Parts operation and ensure the parts
-	/// list is empty.
-	pub fn abort_multipart_upload(&mut self, input: &AbortMultipartUploadRequest) -> Result<AbortMultipartUploadOutput, AWSError> {
-		let mut request = SignedRequest::new("DELETE", "s3", &self.region, &format!("/{}", input.key));
-
-		let mut params = Params::new();
-		params.put("uploadId", &input.upload_id.to_string());
-		request.set_params(params);
-
-		let hostname = (&input.bucket).to_string() + ".s3.amazonaws.com";
-		request.set_hostname(Some(hostname));
-
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
-		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-
-		match status {
-			204 => {
-				Ok(AbortMultipartUploadOutput::default())
-			}
-			_ => { Err(AWSError::new(format!("error, got return code {}", status))) }
-		}
-	}
 	/// uses the acl subresource to set the access control list (ACL) permissions for
 	/// an object that already exists in a bucket
 	pub fn put_object_acl(&mut self, input: &PutObjectAclRequest) -> Result<PutObjectAclOutput, AWSError> {
-		let mut request = SignedRequest::new("PUT", "s3", &self.region, "/{Bucket}/{Key+}?acl");
+		let mut uri = "/{Bucket}/{Key+}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		uri = uri.replace("{Key+}", &encode_uri_path(&input.key));
+		let payload = match input.access_control_policy {
+			Some(ref obj) => Some(with_xml_namespace(&AccessControlPolicyWriter::write_xml("AccessControlPolicy", obj), "http://s3.amazonaws.com/doc/2006-03-01/")),
+			None => None,
+		};
+		let mut request = SignedRequest::new("PUT", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
 		let mut params = Params::new();
-		params.put("Action", "PutObjectAcl");
-		PutObjectAclRequestWriter::write_params(&mut params, "", &input);
+		params.put("acl", "");
+		if let Some(ref obj) = input.acl {
+			request.add_header("x-amz-acl", &obj.to_string());
+		}
+		if let Some(ref obj) = input.content_md5 {
+			request.add_header("Content-MD5", obj);
+		}
+		if let Some(ref obj) = input.grant_full_control {
+			request.add_header("x-amz-grant-full-control", obj);
+		}
+		if let Some(ref obj) = input.grant_read {
+			request.add_header("x-amz-grant-read", obj);
+		}
+		if let Some(ref obj) = input.grant_read_acp {
+			request.add_header("x-amz-grant-read-acp", obj);
+		}
+		if let Some(ref obj) = input.grant_write {
+			request.add_header("x-amz-grant-write", obj);
+		}
+		if let Some(ref obj) = input.grant_write_acp {
+			request.add_header("x-amz-grant-write-acp", obj);
+		}
+		if let Some(ref obj) = input.request_payer {
+			request.add_header("x-amz-request-payer", obj);
+		}
+		if let Some(ref payload) = payload {
+			request.set_payload(Some(payload.as_bytes()));
+			if input.content_md5.is_none() {
+				request.add_header("Content-MD5", &hash(MD5, payload.as_bytes()).to_base64(STANDARD));
+			}
+		}
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
 		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
 		match status {
-			200 => {
-				Ok(try!(PutObjectAclOutputParser::parse_xml("PutObjectAclOutput", &mut stack)))
+			200...299 => {
+				let mut obj = PutObjectAclOutput::default();
+				try!(PutObjectAclOutputParser::parse_headers(&result.headers, &mut obj));
+				Ok(obj)
 			}
 			_ => { Err(AWSError::new("error")) }
 		}
 	}
 	/// Returns the region the bucket resides in.
 	pub fn get_bucket_location(&mut self, input: &GetBucketLocationRequest) -> Result<GetBucketLocationOutput, AWSError> {
-		let mut request = SignedRequest::new("GET", "s3", &self.region, "/{Bucket}?location");
+		let mut uri = "/{Bucket}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		let mut request = SignedRequest::new("GET", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
 		let mut params = Params::new();
-		params.put("Action", "GetBucketLocation");
-		GetBucketLocationRequestWriter::write_params(&mut params, "", &input);
+		params.put("location", "");
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
 		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
 		match status {
-			200 => {
-				Ok(try!(GetBucketLocationOutputParser::parse_xml("GetBucketLocationOutput", &mut stack)))
+			200...299 => {
+				let mut reader = EventReader::new(result);
+				let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+				stack.next(); // xml start tag
+				let root = try!(peek_at_name(&mut stack));
+				Ok(try!(GetBucketLocationOutputParser::parse_xml(&root, &mut stack)))
 			}
 			_ => { Err(AWSError::new("error")) }
 		}
 	}
 	/// Sets the cors configuration for a bucket.
 	pub fn put_bucket_cors(&mut self, input: &PutBucketCorsRequest) -> Result<(), AWSError> {
-		let mut request = SignedRequest::new("PUT", "s3", &self.region, "/{Bucket}?cors");
+		let mut uri = "/{Bucket}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		let payload = match input.cors_configuration {
+			Some(ref obj) => Some(with_xml_namespace(&CORSConfigurationWriter::write_xml("CORSConfiguration", obj), "http://s3.amazonaws.com/doc/2006-03-01/")),
+			None => None,
+		};
+		let mut request = SignedRequest::new("PUT", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
 		let mut params = Params::new();
-		params.put("Action", "PutBucketCors");
-		PutBucketCorsRequestWriter::write_params(&mut params, "", &input);
+		params.put("cors", "");
+		if let Some(ref obj) = input.content_md5 {
+			request.add_header("Content-MD5", obj);
+		}
+		if let Some(ref payload) = payload {
+			request.set_payload(Some(payload.as_bytes()));
+			if input.content_md5.is_none() {
+				request.add_header("Content-MD5", &hash(MD5, payload.as_bytes()).to_base64(STANDARD));
+			}
+		}
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
 		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
 		match status {
-			200 => {
+			200...299 => {
 				Ok(())
 			}
 			_ => { Err(AWSError::new("error")) }
@@ -12153,19 +14127,18 @@ impl<'a> S3Client<'a> {
 	}
 	/// Deletes the lifecycle configuration from the bucket.
 	pub fn delete_bucket_lifecycle(&mut self, input: &DeleteBucketLifecycleRequest) -> Result<(), AWSError> {
-		let mut request = SignedRequest::new("DELETE", "s3", &self.region, "/{Bucket}?lifecycle");
+		let mut uri = "/{Bucket}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		let mut request = SignedRequest::new("DELETE", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
 		let mut params = Params::new();
-		params.put("Action", "DeleteBucketLifecycle");
-		DeleteBucketLifecycleRequestWriter::write_params(&mut params, "", &input);
+		params.put("lifecycle", "");
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
 		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
 		match status {
-			200 => {
+			200...299 => {
 				Ok(())
 			}
 			_ => { Err(AWSError::new("error")) }
@@ -12173,154 +14146,237 @@ impl<'a> S3Client<'a> {
 	}
 	/// Deprecated, see the GetBucketNotificationConfiguration operation.
 	pub fn get_bucket_notification(&mut self, input: &GetBucketNotificationConfigurationRequest) -> Result<NotificationConfigurationDeprecated, AWSError> {
-		let mut request = SignedRequest::new("GET", "s3", &self.region, "/{Bucket}?notification");
+		let mut uri = "/{Bucket}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		let mut request = SignedRequest::new("GET", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
 		let mut params = Params::new();
-		params.put("Action", "GetBucketNotification");
-		GetBucketNotificationConfigurationRequestWriter::write_params(&mut params, "", &input);
+		params.put("notification", "");
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
 		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
 		match status {
-			200 => {
-				Ok(try!(NotificationConfigurationDeprecatedParser::parse_xml("NotificationConfigurationDeprecated", &mut stack)))
+			200...299 => {
+				let mut reader = EventReader::new(result);
+				let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+				stack.next(); // xml start tag
+				let root = try!(peek_at_name(&mut stack));
+				Ok(try!(NotificationConfigurationDeprecatedParser::parse_xml(&root, &mut stack)))
 			}
 			_ => { Err(AWSError::new("error")) }
 		}
 	}
-	/// Lists the parts that have been uploaded for a specific multipart upload.
-	pub fn list_parts(&mut self, input: &ListPartsRequest) -> Result<ListPartsOutput, AWSError> {
-		let mut request = SignedRequest::new("GET", "s3", &self.region, &format!("/{}", input.key));
-
+	/// Returns the access control list (ACL) of an object.
+	pub fn get_object_acl(&mut self, input: &GetObjectAclRequest) -> Result<GetObjectAclOutput, AWSError> {
+		let mut uri = "/{Bucket}/{Key+}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		uri = uri.replace("{Key+}", &encode_uri_path(&input.key));
+		let mut request = SignedRequest::new("GET", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
 		let mut params = Params::new();
-		params.put("uploadId", &input.upload_id.to_string());
+		params.put("acl", "");
+		if let Some(ref obj) = input.version_id {
+			params.put("versionId", obj);
+		}
+		if let Some(ref obj) = input.request_payer {
+			request.add_header("x-amz-request-payer", obj);
+		}
 		request.set_params(params);
-
-		let hostname = (&input.bucket).to_string() + ".s3.amazonaws.com";
-		request.set_hostname(Some(hostname));
-
-		let mut result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
 		let status = result.status.to_u16();
-
 		match status {
-			200 => {
+			200...299 => {
+				let headers = result.headers.clone();
 				let mut reader = EventReader::new(result);
 				let mut stack = XmlResponseFromAws::new(reader.events().peekable());
 				stack.next(); // xml start tag
-
-				Ok(try!(ListPartsOutputParser::parse_xml("ListPartsResult", &mut stack)))
-			}
-			_ => {
-				let mut body = String::new();
-			    result.read_to_string(&mut body).unwrap();
-			    println!("Error response body: {}", body);
-
-				Err(AWSError::new("error in list_parts"))
+				let root = try!(peek_at_name(&mut stack));
+				let mut obj = try!(GetObjectAclOutputParser::parse_xml(&root, &mut stack));
+				try!(GetObjectAclOutputParser::parse_headers(&headers, &mut obj));
+				Ok(obj)
 			}
+			_ => { Err(AWSError::new("error")) }
 		}
 	}
-	/// Returns the access control list (ACL) of an object.
-	pub fn get_object_acl(&mut self, input: &GetObjectAclRequest) -> Result<GetObjectAclOutput, AWSError> {
-		let mut request = SignedRequest::new("GET", "s3", &self.region, "/{Bucket}/{Key+}?acl");
+	/// Uploads a part by copying data from an existing object as data source.
+	pub fn upload_part_copy(&mut self, input: &UploadPartCopyRequest) -> Result<UploadPartCopyOutput, AWSError> {
+		let mut uri = "/{Bucket}/{Key+}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		uri = uri.replace("{Key+}", &encode_uri_path(&input.key));
+		let mut request = SignedRequest::new("PUT", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
 		let mut params = Params::new();
-		params.put("Action", "GetObjectAcl");
-		GetObjectAclRequestWriter::write_params(&mut params, "", &input);
+		{
+			let obj = &input.copy_source;
+			request.add_header("x-amz-copy-source", obj);
+		}
+		if let Some(ref obj) = input.copy_source_if_match {
+			request.add_header("x-amz-copy-source-if-match", obj);
+		}
+		if let Some(ref obj) = input.copy_source_if_modified_since {
+			request.add_header("x-amz-copy-source-if-modified-since", obj);
+		}
+		if let Some(ref obj) = input.copy_source_if_none_match {
+			request.add_header("x-amz-copy-source-if-none-match", obj);
+		}
+		if let Some(ref obj) = input.copy_source_if_unmodified_since {
+			request.add_header("x-amz-copy-source-if-unmodified-since", obj);
+		}
+		if let Some(ref obj) = input.copy_source_range {
+			request.add_header("x-amz-copy-source-range", obj);
+		}
+		{
+			let obj = &input.part_number;
+			params.put("partNumber", &obj.to_string());
+		}
+		{
+			let obj = &input.upload_id;
+			params.put("uploadId", obj);
+		}
+		if let Some(ref obj) = input.sse_customer_algorithm {
+			request.add_header("x-amz-server-side-encryption-customer-algorithm", obj);
+		}
+		if let Some(ref obj) = input.sse_customer_key {
+			request.add_header("x-amz-server-side-encryption-customer-key", obj);
+		}
+		if let Some(ref obj) = input.sse_customer_key_md5 {
+			request.add_header("x-amz-server-side-encryption-customer-key-MD5", obj);
+		}
+		if let Some(ref obj) = input.copy_source_sse_customer_algorithm {
+			request.add_header("x-amz-copy-source-server-side-encryption-customer-algorithm", obj);
+		}
+		if let Some(ref obj) = input.copy_source_sse_customer_key {
+			request.add_header("x-amz-copy-source-server-side-encryption-customer-key", obj);
+		}
+		if let Some(ref obj) = input.copy_source_sse_customer_key_md5 {
+			request.add_header("x-amz-copy-source-server-side-encryption-customer-key-MD5", obj);
+		}
+		if let Some(ref obj) = input.request_payer {
+			request.add_header("x-amz-request-payer", obj);
+		}
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
 		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
 		match status {
-			200 => {
-				Ok(try!(GetObjectAclOutputParser::parse_xml("GetObjectAclOutput", &mut stack)))
+			200...299 => {
+				let mut obj = UploadPartCopyOutput::default();
+				try!(UploadPartCopyOutputParser::parse_headers(&result.headers, &mut obj));
+				let mut reader = EventReader::new(result);
+				let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+				stack.next(); // xml start tag
+				let body = try!(CopyPartResultParser::parse_xml("CopyPartResult", &mut stack));
+				obj.copy_part_result = body;
+				Ok(obj)
 			}
 			_ => { Err(AWSError::new("error")) }
 		}
 	}
-	/// Uploads a part by copying data from an existing object as data source.
-	// pub fn upload_part_copy(&mut self, input: &UploadPartCopyRequest) -> Result<bool, AWSError> {
-	// 	let ref part_number = input.part_number;
-	// 	let ref upload_id = input.upload_id;
-	// 	let ref object_id = input.key;
-	// 	let mut request = SignedRequest::new("PUT", "s3", &self.region, &format!("/{}?partNumber={}&uploadId={}",
-	// 		object_id, part_number, upload_id));
-	//
-	// 	let result = request.sign_and_execute(&self.creds.get_credentials());
-	// 	let status = result.status.to_u16();
-	//
-	// 	match status {
-	// 		200 => {
-	// 			Ok(true)
-	// 		}
-	// 		_ => { Err(AWSError::new("error")) }
-	// 	}
-	// }
-	/// Removes the null version (if there is one) of an object and inserts a delete
-	/// marker, which becomes the latest version of the object. If there isn't a null
-	/// version, Amazon S3 does not remove any objects.
-	pub fn delete_object(&mut self, input: &DeleteObjectRequest) -> Result<DeleteObjectOutput, AWSError> {
-		let mut uri = String::from("/");
-		uri = uri +  &input.key.to_string();
-		let mut request = SignedRequest::new("DELETE", "s3", &self.region, &uri);
+	/// Restores an archived copy of an object back into Amazon S3
+	pub fn restore_object(&mut self, input: &RestoreObjectRequest) -> Result<RestoreObjectOutput, AWSError> {
+		let mut uri = "/{Bucket}/{Key+}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		uri = uri.replace("{Key+}", &encode_uri_path(&input.key));
+		let payload = match input.restore_request {
+			Some(ref obj) => Some(with_xml_namespace(&RestoreRequestWriter::write_xml("RestoreRequest", obj), "http://s3.amazonaws.com/doc/2006-03-01/")),
+			None => None,
+		};
+		let mut request = SignedRequest::new("POST", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
 		let mut params = Params::new();
-
-		let hostname = (&input.bucket).to_string() + ".s3.amazonaws.com";
-		request.set_hostname(Some(hostname));
-
-		params.put("Action", "DeleteObject");
-		DeleteObjectRequestWriter::write_params(&mut params, "", &input);
+		params.put("restore", "");
+		if let Some(ref obj) = input.version_id {
+			params.put("versionId", obj);
+		}
+		if let Some(ref obj) = input.request_payer {
+			request.add_header("x-amz-request-payer", obj);
+		}
+		if let Some(ref payload) = payload {
+			request.set_payload(Some(payload.as_bytes()));
+		}
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
 		let status = result.status.to_u16();
-
 		match status {
-			204 => {
-				Ok(DeleteObjectOutput::default())
-				// Ok(try!(DeleteObjectOutputParser::parse_xml("DeleteObjectOutput", &mut stack)))
+			200...299 => {
+				let mut obj = RestoreObjectOutput::default();
+				try!(RestoreObjectOutputParser::parse_headers(&result.headers, &mut obj));
+				Ok(obj)
 			}
-			_ => { Err(AWSError::new("delete object error")) }
+			_ => { Err(AWSError::new("error")) }
 		}
 	}
-	/// Restores an archived copy of an object back into Amazon S3
-	pub fn restore_object(&mut self, input: &RestoreObjectRequest) -> Result<RestoreObjectOutput, AWSError> {
-		let mut request = SignedRequest::new("POST", "s3", &self.region, "/{Bucket}/{Key+}?restore");
+	pub fn get_bucket_replication(&mut self, input: &GetBucketReplicationRequest) -> Result<GetBucketReplicationOutput, AWSError> {
+		let mut uri = "/{Bucket}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		let mut request = SignedRequest::new("GET", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
 		let mut params = Params::new();
-		params.put("Action", "RestoreObject");
-		RestoreObjectRequestWriter::write_params(&mut params, "", &input);
+		params.put("replication", "");
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
 		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
 		match status {
-			200 => {
-				Ok(try!(RestoreObjectOutputParser::parse_xml("RestoreObjectOutput", &mut stack)))
+			200...299 => {
+				let mut obj = GetBucketReplicationOutput::default();
+				let mut reader = EventReader::new(result);
+				let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+				stack.next(); // xml start tag
+				let body = try!(ReplicationConfigurationParser::parse_xml("ReplicationConfiguration", &mut stack));
+				obj.replication_configuration = body;
+				Ok(obj)
 			}
 			_ => { Err(AWSError::new("error")) }
 		}
 	}
-	pub fn get_bucket_replication(&mut self, input: &GetBucketReplicationRequest) -> Result<GetBucketReplicationOutput, AWSError> {
-		let mut request = SignedRequest::new("GET", "s3", &self.region, "/{Bucket}?replication");
+	/// Returns some or all (up to 1000) of the objects in a bucket, continuing from a
+	/// continuation token or a start-after key.
+	pub fn list_objects_v2(&mut self, input: &ListObjectsV2Request) -> Result<ListObjectsV2Output, AWSError> {
+		let mut uri = "/{Bucket}".to_string();
+		uri = uri.replace("{Bucket}", &encode_uri_strict(&input.bucket));
+		let mut request = SignedRequest::new("GET", "s3", &self.region, &uri);
+		try!(self.endpoint.address(&mut request, &input.bucket));
 		let mut params = Params::new();
-		params.put("Action", "GetBucketReplication");
-		GetBucketReplicationRequestWriter::write_params(&mut params, "", &input);
+		params.put("list-type", "2");
+		if let Some(ref obj) = input.delimiter {
+			params.put("delimiter", obj);
+		}
+		if let Some(ref obj) = input.encoding_type {
+			params.put("encoding-type", obj);
+		}
+		if let Some(ref obj) = input.max_keys {
+			params.put("max-keys", &obj.to_string());
+		}
+		if let Some(ref obj) = input.prefix {
+			params.put("prefix", obj);
+		}
+		if let Some(ref obj) = input.continuation_token {
+			params.put("continuation-token", obj);
+		}
+		if let Some(ref obj) = input.fetch_owner {
+			params.put("fetch-owner", &obj.to_string());
+		}
+		if let Some(ref obj) = input.start_after {
+			params.put("start-after", obj);
+		}
+		if let Some(ref obj) = input.request_payer {
+			request.add_header("x-amz-request-payer", obj);
+		}
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
 		let status = result.status.to_u16();
-		let mut reader = EventReader::new(result);
-		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
-		stack.next(); // xml start tag
-		stack.next();
 		match status {
-			200 => {
-				Ok(try!(GetBucketReplicationOutputParser::parse_xml("GetBucketReplicationOutput", &mut stack)))
+			200...299 => {
+				let mut reader = EventReader::new(result);
+				let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+				stack.next(); // xml start tag
+				let root = try!(peek_at_name(&mut stack));
+				Ok(try!(ListObjectsV2OutputParser::parse_xml(&root, &mut stack)))
 			}
 			_ => { Err(AWSError::new("error")) }
 		}
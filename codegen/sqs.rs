@@ -2619,12 +2619,21 @@ impl SendMessageBatchRequestEntryWriter {
 }
 pub struct SQSClient<'a> {
 	creds: Box<AWSCredentialsProvider + 'a>,
-	region: &'a Region
+	region: &'a Region,
+	hooks: RequestHooks,
 }
 
 impl<'a> SQSClient<'a> {
 	pub fn new<P: AWSCredentialsProvider + 'a>(creds: P, region: &'a Region) -> SQSClient<'a> {
-		SQSClient { creds: Box::new(creds), region: region }
+		SQSClient { creds: Box::new(creds), region: region, hooks: default_hooks() }
+	}
+	/// Adds a hook called around every request this client sends.
+	pub fn add_hook(&mut self, hook: Arc<RequestHook>) {
+		self.hooks.push(hook);
+	}
+	/// Removes all hooks, including the default logging hook.
+	pub fn clear_hooks(&mut self) {
+		self.hooks.clear();
 	}
 	/// Creates a new queue, or returns the URL of an existing one. When you request
 	/// `CreateQueue`, you provide a name for the queue. To successfully create a new
@@ -2653,7 +2662,8 @@ impl<'a> SQSClient<'a> {
 		params.put("Action", "CreateQueue");
 		CreateQueueRequestWriter::write_params(&mut params, "", &input);
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(request.sign_and_execute(try!(self.creds.get_credentials())));
 		let status = result.status.to_u16();
 		let mut reader = EventReader::new(result);
 		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -2696,7 +2706,8 @@ impl<'a> SQSClient<'a> {
 		params.put("Action", "GetQueueAttributes");
 		GetQueueAttributesRequestWriter::write_params(&mut params, "", &input);
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(request.sign_and_execute(try!(self.creds.get_credentials())));
 		let status = result.status.to_u16();
 		let mut reader = EventReader::new(result);
 		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -2722,7 +2733,8 @@ impl<'a> SQSClient<'a> {
 		params.put("Action", "SetQueueAttributes");
 		SetQueueAttributesRequestWriter::write_params(&mut params, "", &input);
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(request.sign_and_execute(try!(self.creds.get_credentials())));
 		let status = result.status.to_u16();
 		let mut reader = EventReader::new(result);
 		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -2749,7 +2761,8 @@ impl<'a> SQSClient<'a> {
 		params.put("Action", "GetQueueUrl");
 		GetQueueUrlRequestWriter::write_params(&mut params, "", &input);
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(request.sign_and_execute(try!(self.creds.get_credentials())));
 		let status = result.status.to_u16();
 		let mut reader = EventReader::new(result);
 		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -2779,7 +2792,8 @@ impl<'a> SQSClient<'a> {
 		params.put("Action", "DeleteMessageBatch");
 		DeleteMessageBatchRequestWriter::write_params(&mut params, "", &input);
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(request.sign_and_execute(try!(self.creds.get_credentials())));
 		let status = result.status.to_u16();
 		let mut reader = EventReader::new(result);
 		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -2820,7 +2834,8 @@ impl<'a> SQSClient<'a> {
 		params.put("Action", "SendMessageBatch");
 		SendMessageBatchRequestWriter::write_params(&mut params, "", &input);
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(request.sign_and_execute(try!(self.creds.get_credentials())));
 		let status = result.status.to_u16();
 		let mut reader = EventReader::new(result);
 		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -2844,7 +2859,8 @@ impl<'a> SQSClient<'a> {
 		params.put("Action", "ListDeadLetterSourceQueues");
 		ListDeadLetterSourceQueuesRequestWriter::write_params(&mut params, "", &input);
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(request.sign_and_execute(try!(self.creds.get_credentials())));
 		let status = result.status.to_u16();
 		let mut reader = EventReader::new(result);
 		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -2892,7 +2908,8 @@ impl<'a> SQSClient<'a> {
 		params.put("Action", "ChangeMessageVisibility");
 		ChangeMessageVisibilityRequestWriter::write_params(&mut params, "", &input);
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(request.sign_and_execute(try!(self.creds.get_credentials())));
 		let status = result.status.to_u16();
 		let mut reader = EventReader::new(result);
 		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -2929,7 +2946,8 @@ impl<'a> SQSClient<'a> {
 		params.put("Action", "AddPermission");
 		AddPermissionRequestWriter::write_params(&mut params, "", &input);
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(request.sign_and_execute(try!(self.creds.get_credentials())));
 		let status = result.status.to_u16();
 		let mut reader = EventReader::new(result);
 		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -2961,7 +2979,8 @@ impl<'a> SQSClient<'a> {
 		params.put("Action", "ChangeMessageVisibilityBatch");
 		ChangeMessageVisibilityBatchRequestWriter::write_params(&mut params, "", &input);
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(request.sign_and_execute(try!(self.creds.get_credentials())));
 		let status = result.status.to_u16();
 		let mut reader = EventReader::new(result);
 		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -2991,7 +3010,8 @@ impl<'a> SQSClient<'a> {
 		params.put("Action", "SendMessage");
 		SendMessageRequestWriter::write_params(&mut params, "", &input);
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(request.sign_and_execute(try!(self.creds.get_credentials())));
 		let status = result.status.to_u16();
 		let mut reader = EventReader::new(result);
 		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -3025,7 +3045,8 @@ impl<'a> SQSClient<'a> {
 		params.put("Action", "DeleteQueue");
 		DeleteQueueRequestWriter::write_params(&mut params, "", &input);
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(request.sign_and_execute(try!(self.creds.get_credentials())));
 		let status = result.status.to_u16();
 		let mut reader = EventReader::new(result);
 		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -3052,7 +3073,8 @@ impl<'a> SQSClient<'a> {
 		params.put("Action", "PurgeQueue");
 		PurgeQueueRequestWriter::write_params(&mut params, "", &input);
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(request.sign_and_execute(try!(self.creds.get_credentials())));
 		let status = result.status.to_u16();
 		let mut reader = EventReader::new(result);
 		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -3103,7 +3125,8 @@ impl<'a> SQSClient<'a> {
 		params.put("Action", "ReceiveMessage");
 		ReceiveMessageRequestWriter::write_params(&mut params, "", &input);
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(request.sign_and_execute(try!(self.creds.get_credentials())));
 		let status = result.status.to_u16();
 		let mut reader = EventReader::new(result);
 		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -3140,7 +3163,8 @@ impl<'a> SQSClient<'a> {
 		params.put("Action", "DeleteMessage");
 		DeleteMessageRequestWriter::write_params(&mut params, "", &input);
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(request.sign_and_execute(try!(self.creds.get_credentials())));
 		let status = result.status.to_u16();
 		let mut reader = EventReader::new(result);
 		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -3163,7 +3187,8 @@ impl<'a> SQSClient<'a> {
 		params.put("Action", "ListQueues");
 		ListQueuesRequestWriter::write_params(&mut params, "", &input);
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(request.sign_and_execute(try!(self.creds.get_credentials())));
 		let status = result.status.to_u16();
 		let mut reader = EventReader::new(result);
 		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -3184,7 +3209,8 @@ impl<'a> SQSClient<'a> {
 		params.put("Action", "RemovePermission");
 		RemovePermissionRequestWriter::write_params(&mut params, "", &input);
 		request.set_params(params);
-		let result = request.sign_and_execute(try!(self.creds.get_credentials()));
+		request.set_hooks(&self.hooks);
+		let result = try!(request.sign_and_execute(try!(self.creds.get_credentials())));
 		let status = result.status.to_u16();
 		let mut reader = EventReader::new(result);
 		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
@@ -0,0 +1,317 @@
+//! Checks that S3 objects arrive, and come back, intact
+//!
+//! S3 reports the MD5 of an object uploaded in one piece as its ETag, unless the object is
+//! encrypted with a KMS or customer-provided key.  The ETag of a multipart upload is the MD5 of the
+//! parts' MD5s, followed by `-` and the number of parts.  S3 also checks uploads against, and
+//! returns with downloads, CRC32, CRC32C, SHA-1 or SHA-256 checksums in `x-amz-checksum-*` headers,
+//! whatever the encryption.
+//!
+//! `S3Helper` and `transfer::TransferManager` check what they can; anything that doesn't match
+//! fails with an `AWSError` of kind `ChecksumMismatch`, carrying the `ChecksumMismatchError`, or
+//! `UploadRejected` when S3 found the mismatch.  `is_checksum_mismatch` recognises both.
+
+use std::ascii::AsciiExt;
+use std::fmt;
+use std::io::{self, Read, Write};
+use openssl::crypto::hash::Type::{MD5, SHA1, SHA256};
+use openssl::crypto::hash::Hasher;
+use serialize::base64::{ToBase64, STANDARD};
+use serialize::hex::{FromHex, ToHex};
+use error::{AWSError, AWSErrorKind};
+
+const CHECKSUM_MISMATCH: &'static str = "Checksum mismatch";
+const CRC32_POLYNOMIAL: u32 = 0xEDB88320;
+const CRC32C_POLYNOMIAL: u32 = 0x82F63B78;
+
+/// Checksums S3 checks uploads against and returns with downloads.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChecksumAlgorithm {
+	Crc32,
+	Crc32c,
+	Sha1,
+	Sha256,
+}
+
+impl ChecksumAlgorithm {
+	/// Name S3 gives the algorithm, EG: `CRC32C`
+	pub fn name(&self) -> &'static str {
+		match *self {
+			ChecksumAlgorithm::Crc32 => "CRC32",
+			ChecksumAlgorithm::Crc32c => "CRC32C",
+			ChecksumAlgorithm::Sha1 => "SHA1",
+			ChecksumAlgorithm::Sha256 => "SHA256",
+		}
+	}
+
+	/// Header carrying the checksum, EG: `x-amz-checksum-crc32c`
+	pub fn header_name(&self) -> &'static str {
+		match *self {
+			ChecksumAlgorithm::Crc32 => "x-amz-checksum-crc32",
+			ChecksumAlgorithm::Crc32c => "x-amz-checksum-crc32c",
+			ChecksumAlgorithm::Sha1 => "x-amz-checksum-sha1",
+			ChecksumAlgorithm::Sha256 => "x-amz-checksum-sha256",
+		}
+	}
+
+	/// Base64 encoded checksum of `data`, as sent in its header
+	pub fn checksum(&self, data: &[u8]) -> String {
+		let mut checksum = Checksum::new(*self);
+		checksum.update(data);
+		checksum.finish()
+	}
+}
+
+/// Computes a checksum of data handed over a piece at a time.
+pub struct Checksum {
+	algorithm: ChecksumAlgorithm,
+	state: ChecksumState,
+}
+
+enum ChecksumState {
+	Crc { table: Vec<u32>, crc: u32 },
+	Digest(Hasher),
+}
+
+impl Checksum {
+	pub fn new(algorithm: ChecksumAlgorithm) -> Checksum {
+		let state = match algorithm {
+			ChecksumAlgorithm::Crc32 => ChecksumState::Crc { table: crc_table(CRC32_POLYNOMIAL), crc: !0 },
+			ChecksumAlgorithm::Crc32c => ChecksumState::Crc { table: crc_table(CRC32C_POLYNOMIAL), crc: !0 },
+			ChecksumAlgorithm::Sha1 => ChecksumState::Digest(Hasher::new(SHA1)),
+			ChecksumAlgorithm::Sha256 => ChecksumState::Digest(Hasher::new(SHA256)),
+		};
+		Checksum { algorithm: algorithm, state: state }
+	}
+
+	pub fn algorithm(&self) -> ChecksumAlgorithm {
+		self.algorithm
+	}
+
+	pub fn update(&mut self, data: &[u8]) {
+		match self.state {
+			ChecksumState::Crc { ref table, ref mut crc } => {
+				for byte in data {
+					*crc = table[((*crc ^ *byte as u32) & 0xff) as usize] ^ (*crc >> 8);
+				}
+			}
+			ChecksumState::Digest(ref mut hasher) => {
+				// hashing into memory doesn't fail
+				hasher.write_all(data).unwrap();
+			}
+		}
+	}
+
+	/// Base64 encoded checksum of everything so far.  Call once, at the end.
+	pub fn finish(&mut self) -> String {
+		match self.state {
+			ChecksumState::Crc { crc, .. } => {
+				let crc = !crc;
+				[(crc >> 24) as u8, (crc >> 16) as u8, (crc >> 8) as u8, crc as u8].to_base64(STANDARD)
+			}
+			ChecksumState::Digest(ref mut hasher) => hasher.finish().to_base64(STANDARD),
+		}
+	}
+}
+
+fn crc_table(polynomial: u32) -> Vec<u32> {
+	(0..256).map(|byte| {
+		let mut crc = byte as u32;
+		for _ in 0..8 {
+			crc = if crc & 1 == 1 { polynomial ^ (crc >> 1) } else { crc >> 1 };
+		}
+		crc
+	}).collect()
+}
+
+/// Reads from another reader, keeping the MD5 and optionally a checksum of everything read.
+pub struct ChecksumReader<R> {
+	inner: R,
+	md5: Hasher,
+	checksum: Option<Checksum>,
+}
+
+impl <R: Read> ChecksumReader<R> {
+	pub fn new(inner: R, algorithm: Option<ChecksumAlgorithm>) -> ChecksumReader<R> {
+		ChecksumReader {
+			inner: inner,
+			md5: Hasher::new(MD5),
+			checksum: algorithm.map(Checksum::new),
+		}
+	}
+
+	/// Hex encoded MD5 of everything read.  Call once, at the end.
+	pub fn md5_hex(&mut self) -> String {
+		self.md5.finish().to_hex()
+	}
+
+	/// Base64 encoded checksum of everything read, if one was asked for.  Call once, at the end.
+	pub fn checksum(&mut self) -> Option<String> {
+		self.checksum.as_mut().map(|checksum| checksum.finish())
+	}
+}
+
+impl <R: Read> Read for ChecksumReader<R> {
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+		let read = try!(self.inner.read(buf));
+		try!(self.md5.write_all(&buf[..read]));
+		if let Some(ref mut checksum) = self.checksum {
+			checksum.update(&buf[..read]);
+		}
+		Ok(read)
+	}
+}
+
+/// An object's data doesn't match what S3 reports for it.
+#[derive(Debug, PartialEq)]
+pub struct ChecksumMismatchError {
+	/// Key of the object
+	pub object: String,
+	/// What was compared, EG: `MD5` or `CRC32C`
+	pub algorithm: String,
+	/// What S3 reports
+	pub expected: String,
+	/// What the data sent or received works out to
+	pub actual: String,
+}
+
+impl fmt::Display for ChecksumMismatchError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} for {}: S3 has {} {}, data has {}", CHECKSUM_MISMATCH, self.object,
+			self.algorithm, self.expected, self.actual)
+	}
+}
+
+/// The error for an upload S3 refused because its data didn't match the `Content-MD5` or checksum
+/// sent with it.
+pub fn upload_rejected(object: &str) -> AWSError {
+	AWSError::with_kind(format!("{} for {}: S3 rejected the upload's data", CHECKSUM_MISMATCH, object),
+		AWSErrorKind::UploadRejected)
+}
+
+/// Whether an error is data not matching its checksum or ETag.
+pub fn is_checksum_mismatch(error: &AWSError) -> bool {
+	match *error.kind() {
+		AWSErrorKind::ChecksumMismatch(_) | AWSErrorKind::UploadRejected => true,
+		_ => false,
+	}
+}
+
+/// Whether S3 derives the ETags of objects stored with this encryption from their MD5.
+pub fn etag_has_md5(server_side_encryption: &str, sse_customer_algorithm: &str) -> bool {
+	server_side_encryption != "aws:kms" && sse_customer_algorithm.is_empty()
+}
+
+/// Whether an ETag or checksum is of a multipart upload, EG: `"0732917abc3288784e318ac0aab1757a-2"`
+pub fn is_multipart(value: &str) -> bool {
+	value.contains('-')
+}
+
+/// Checks a single part ETag is the hex encoded MD5 of the data.
+pub fn check_etag(object: &str, e_tag: &str, md5_hex: &str) -> Result<(), ChecksumMismatchError> {
+	let e_tag = e_tag.trim_matches('"');
+	compare(object, "MD5", e_tag, md5_hex, e_tag.eq_ignore_ascii_case(md5_hex))
+}
+
+/// Checks a checksum S3 reports is the one of the data.
+pub fn check_checksum(object: &str, algorithm: ChecksumAlgorithm, expected: &str, actual: &str) -> Result<(), ChecksumMismatchError> {
+	compare(object, algorithm.name(), expected, actual, expected == actual)
+}
+
+/// The ETag S3 gives a multipart upload of parts with these ETags, or `None` if they aren't MD5s.
+pub fn multipart_etag(part_etags: &[String]) -> Option<String> {
+	let mut md5s = Hasher::new(MD5);
+	for e_tag in part_etags {
+		match e_tag.trim_matches('"').from_hex() {
+			Ok(ref md5) if md5.len() == 16 => md5s.write_all(md5).unwrap(),
+			_ => return None,
+		}
+	}
+	Some(format!("{}-{}", md5s.finish().to_hex(), part_etags.len()))
+}
+
+/// Checks the ETag of a completed multipart upload is the one of its parts.
+pub fn check_multipart_etag(object: &str, e_tag: &str, part_etags: &[String]) -> Result<(), ChecksumMismatchError> {
+	match multipart_etag(part_etags) {
+		Some(expected) => {
+			let e_tag = e_tag.trim_matches('"');
+			compare(object, "multipart ETag", e_tag, &expected, e_tag.eq_ignore_ascii_case(&expected))
+		}
+		None => {
+			debug!("Can't check the ETag of {}: its parts' ETags aren't MD5s", object);
+			Ok(())
+		}
+	}
+}
+
+fn compare(object: &str, algorithm: &str, expected: &str, actual: &str, matches: bool) -> Result<(), ChecksumMismatchError> {
+	if matches {
+		return Ok(());
+	}
+	Err(ChecksumMismatchError {
+		object: object.to_string(),
+		algorithm: algorithm.to_string(),
+		expected: expected.to_string(),
+		actual: actual.to_string(),
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::io::{self, Read};
+	use error::{AWSError, AWSErrorKind};
+
+	#[test]
+	fn checksums_match_known_values() {
+		assert_eq!("y/Q5Jg==", ChecksumAlgorithm::Crc32.checksum(b"123456789"));
+		assert_eq!("4waSgw==", ChecksumAlgorithm::Crc32c.checksum(b"123456789"));
+		assert_eq!("Kq5sNclPz7QV2+lfQIuc6R7oRu0=", ChecksumAlgorithm::Sha1.checksum(b"hello world"));
+		assert_eq!("uU0nuZNNPgilLlLX2n2r+sSE7+N6U4DukIj3rOLvzek=", ChecksumAlgorithm::Sha256.checksum(b"hello world"));
+	}
+
+	#[test]
+	fn checksum_reader_hashes_what_it_reads() {
+		let data = b"hello world";
+		let mut reader = ChecksumReader::new(&data[..], Some(ChecksumAlgorithm::Sha256));
+		let mut read = Vec::new();
+		reader.read_to_end(&mut read).unwrap();
+		assert_eq!(&data[..], &read[..]);
+		assert_eq!("5eb63bbbe01eeed093cb22bb8f5acdc3", reader.md5_hex());
+		assert_eq!(Some("uU0nuZNNPgilLlLX2n2r+sSE7+N6U4DukIj3rOLvzek=".to_string()), reader.checksum());
+
+		let mut reader = ChecksumReader::new(io::empty(), None);
+		assert_eq!(None, reader.checksum());
+	}
+
+	#[test]
+	fn etags_are_checked() {
+		assert!(check_etag("key", "\"5eb63bbbe01eeed093cb22bb8f5acdc3\"", "5eb63bbbe01eeed093cb22bb8f5acdc3").is_ok());
+		let mismatch = check_etag("key", "\"5eb63bbbe01eeed093cb22bb8f5acdc3\"", "d41d8cd98f00b204e9800998ecf8427e").unwrap_err();
+		assert_eq!("5eb63bbbe01eeed093cb22bb8f5acdc3", mismatch.expected);
+		let error = AWSError::from(mismatch);
+		assert!(is_checksum_mismatch(&error));
+		match *error.kind() {
+			AWSErrorKind::ChecksumMismatch(ref mismatch) => assert_eq!("d41d8cd98f00b204e9800998ecf8427e", mismatch.actual),
+			ref kind => panic!("Unexpected error kind {:?}", kind),
+		}
+		assert!(is_checksum_mismatch(&upload_rejected("key")));
+		assert!(!is_checksum_mismatch(&AWSError::new("Transfer cancelled")));
+	}
+
+	#[test]
+	fn multipart_etags_are_md5s_of_part_md5s() {
+		let parts = vec!["\"3303e12af474ca11d85ed2966a932992\"".to_string(), "\"3ea4e15b91a17dc76052c56cfcdf67a2\"".to_string()];
+		assert_eq!(Some("0732917abc3288784e318ac0aab1757a-2".to_string()), multipart_etag(&parts));
+		assert!(check_multipart_etag("key", "\"0732917abc3288784e318ac0aab1757a-2\"", &parts).is_ok());
+		assert!(check_multipart_etag("key", "\"0732917abc3288784e318ac0aab1757a-3\"", &parts).is_err());
+		assert_eq!(None, multipart_etag(&vec!["not-an-md5".to_string()]));
+	}
+
+	#[test]
+	fn encrypted_objects_have_no_md5_etags() {
+		assert!(etag_has_md5("", ""));
+		assert!(etag_has_md5("AES256", ""));
+		assert!(!etag_has_md5("aws:kms", ""));
+		assert!(!etag_has_md5("", "AES256"));
+	}
+}
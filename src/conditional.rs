@@ -0,0 +1,247 @@
+//! Conditional S3 requests and object metadata
+//!
+//! GETs and HEADs can be made conditional on the object's ETag or last modified date with
+//! `If-Match`, `If-None-Match`, `If-Modified-Since` and `If-Unmodified-Since`; PUTs on its ETag with
+//! `If-Match`, or on there being no object yet with `If-None-Match: *`.  S3 answers a GET whose
+//! object hasn't changed with `304 Not Modified`, and any other condition that fails with
+//! `412 Precondition Failed`.
+//!
+//! `S3Client` turns those responses, and `404 Not Found`, into errors of the `AWSErrorKind`s that
+//! `is_not_modified`, `is_precondition_failed` and `is_not_found` recognise.  `S3Helper`'s
+//! conditional methods return them as a `Conditional` instead, so they needn't be told apart from
+//! real errors.
+
+extern crate chrono;
+
+use std::collections::HashMap;
+use self::chrono::{DateTime, TimeZone, UTC};
+use error::{AWSError, AWSErrorKind};
+use s3::{HeadObjectOutput, GetObjectRequest, PutObjectRequest};
+
+const NOT_MODIFIED: &'static str = "Not modified";
+const PRECONDITION_FAILED: &'static str = "Precondition failed";
+const NOT_FOUND: &'static str = "Not found";
+const HTTP_DATE_FORMAT: &'static str = "%a, %d %b %Y %H:%M:%S GMT";
+
+/// Conditions on the object for a request to go ahead.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Conditions {
+	/// Only if the object's ETag is this one, or `*` for any object at all
+	pub if_match: Option<String>,
+	/// Only if the object's ETag isn't this one, or `*` for there being no object
+	pub if_none_match: Option<String>,
+	/// Only if the object has changed since
+	pub if_modified_since: Option<DateTime<UTC>>,
+	/// Only if the object hasn't changed since
+	pub if_unmodified_since: Option<DateTime<UTC>>,
+}
+
+impl Conditions {
+	/// Only if the object is still the one with this ETag, EG: to update it without losing changes
+	/// made since it was read.
+	pub fn if_match(e_tag: &str) -> Conditions {
+		Conditions { if_match: Some(e_tag.to_string()), ..Conditions::default() }
+	}
+
+	/// Only if the object is no longer the one with this ETag, EG: to download it only if changed.
+	pub fn if_none_match(e_tag: &str) -> Conditions {
+		Conditions { if_none_match: Some(e_tag.to_string()), ..Conditions::default() }
+	}
+
+	/// Only if the object has changed since `date`.
+	pub fn if_modified_since(date: DateTime<UTC>) -> Conditions {
+		Conditions { if_modified_since: Some(date), ..Conditions::default() }
+	}
+
+	/// Only if there is no object yet, so uploads don't overwrite each other.
+	pub fn if_absent() -> Conditions {
+		Conditions::if_none_match("*")
+	}
+
+	/// Adds the conditions to a GET.
+	pub fn apply_to_get_object(&self, request: &mut GetObjectRequest) {
+		request.if_match = self.if_match.clone();
+		request.if_none_match = self.if_none_match.clone();
+		request.if_modified_since = self.if_modified_since.as_ref().map(format_http_date);
+		request.if_unmodified_since = self.if_unmodified_since.as_ref().map(format_http_date);
+	}
+
+	/// Adds the conditions to a PUT.  S3 only checks ETags on uploads, so date conditions are an
+	/// error.
+	pub fn apply_to_put_object(&self, request: &mut PutObjectRequest) -> Result<(), AWSError> {
+		if self.if_modified_since.is_some() || self.if_unmodified_since.is_some() {
+			return Err(AWSError::new("S3 uploads can only be conditional on If-Match and If-None-Match"));
+		}
+		request.if_match = self.if_match.clone();
+		request.if_none_match = self.if_none_match.clone();
+		Ok(())
+	}
+}
+
+/// Outcome of a conditional request.
+#[derive(Debug, PartialEq)]
+pub enum Conditional<T> {
+	/// The conditions were met and the request went ahead
+	Done(T),
+	/// A GET or HEAD's object hasn't changed: `304 Not Modified`
+	NotModified,
+	/// The conditions weren't met, so nothing was done: `412 Precondition Failed`
+	PreconditionFailed,
+}
+
+impl<T> Conditional<T> {
+	/// What the request returned, if it went ahead.
+	pub fn done(self) -> Option<T> {
+		match self {
+			Conditional::Done(value) => Some(value),
+			_ => None,
+		}
+	}
+
+	/// Sorts a request's result into its outcome, leaving real errors as errors.
+	pub fn from_result(result: Result<T, AWSError>) -> Result<Conditional<T>, AWSError> {
+		match result {
+			Ok(value) => Ok(Conditional::Done(value)),
+			Err(ref why) if is_not_modified(why) => Ok(Conditional::NotModified),
+			Err(ref why) if is_precondition_failed(why) => Ok(Conditional::PreconditionFailed),
+			Err(why) => Err(why),
+		}
+	}
+}
+
+/// An object's metadata, as returned by a HEAD.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectMetadata {
+	/// Length of the object in bytes
+	pub size: u64,
+	/// When the object was last written, if S3 said
+	pub last_modified: Option<DateTime<UTC>>,
+	/// The object's ETag, with its quotes
+	pub e_tag: String,
+	/// The object's `Content-Type`
+	pub content_type: String,
+	/// The object's version, if the bucket is versioned
+	pub version_id: Option<String>,
+	/// The object's storage class, empty for `STANDARD`
+	pub storage_class: String,
+	/// User metadata sent as `x-amz-meta-` headers, by lowercased name without the prefix
+	pub metadata: HashMap<String, String>,
+}
+
+impl ObjectMetadata {
+	/// Parses the metadata from the headers of a HEAD.
+	pub fn from_head(output: HeadObjectOutput) -> Result<ObjectMetadata, AWSError> {
+		if output.content_length < 0 {
+			return Err(AWSError::new(format!("Invalid Content-Length {}", output.content_length)));
+		}
+		Ok(ObjectMetadata {
+			size: output.content_length as u64,
+			last_modified: output.last_modified,
+			e_tag: output.e_tag,
+			content_type: output.content_type,
+			version_id: if output.version_id.is_empty() { None } else { Some(output.version_id) },
+			storage_class: output.storage_class,
+			metadata: output.metadata,
+		})
+	}
+}
+
+/// The error for a response with a status of a condition that wasn't met, or of a missing object.
+pub fn status_error(status: u16, path: &str) -> Option<AWSError> {
+	let (reason, kind) = match status {
+		304 => (NOT_MODIFIED, AWSErrorKind::NotModified),
+		404 => (NOT_FOUND, AWSErrorKind::NotFound),
+		412 => (PRECONDITION_FAILED, AWSErrorKind::PreconditionFailed),
+		_ => return None,
+	};
+	Some(AWSError::with_kind(format!("{}: {}", reason, path), kind))
+}
+
+/// Whether an error is a GET or HEAD's object not having changed.
+pub fn is_not_modified(error: &AWSError) -> bool {
+	*error.kind() == AWSErrorKind::NotModified
+}
+
+/// Whether an error is a request's conditions not being met.
+pub fn is_precondition_failed(error: &AWSError) -> bool {
+	*error.kind() == AWSErrorKind::PreconditionFailed
+}
+
+/// Whether an error is a request finding no object or bucket.
+pub fn is_not_found(error: &AWSError) -> bool {
+	*error.kind() == AWSErrorKind::NotFound
+}
+
+/// Parses an HTTP date, EG: `Wed, 12 Oct 2009 17:50:00 GMT`
+pub fn parse_http_date(value: &str) -> Result<DateTime<UTC>, AWSError> {
+	match UTC.datetime_from_str(value, HTTP_DATE_FORMAT) {
+		Ok(date) => Ok(date),
+		Err(why) => Err(AWSError::new(format!("Invalid HTTP date {}: {}", value, why))),
+	}
+}
+
+/// Formats a date for an HTTP header, EG: `Wed, 12 Oct 2009 17:50:00 GMT`
+pub fn format_http_date(date: &DateTime<UTC>) -> String {
+	date.format(HTTP_DATE_FORMAT).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+	extern crate chrono;
+
+	use super::*;
+	use self::chrono::{TimeZone, UTC};
+	use error::AWSError;
+	use s3::{GetObjectRequest, HeadObjectOutput, PutObjectRequest};
+
+	#[test]
+	fn http_dates_round_trip() {
+		let date = parse_http_date("Wed, 12 Oct 2009 17:50:00 GMT").unwrap();
+		assert_eq!(UTC.ymd(2009, 10, 12).and_hms(17, 50, 0), date);
+		assert_eq!("Wed, 12 Oct 2009 17:50:00 GMT", format_http_date(&date));
+		assert!(parse_http_date("yesterday").is_err());
+	}
+
+	#[test]
+	fn condition_statuses_are_outcomes_not_errors() {
+		let not_modified: Result<(), AWSError> = Err(status_error(304, "key").unwrap());
+		assert_eq!(Conditional::NotModified, Conditional::from_result(not_modified).unwrap());
+		let failed: Result<(), AWSError> = Err(status_error(412, "key").unwrap());
+		assert_eq!(Conditional::PreconditionFailed, Conditional::from_result(failed).unwrap());
+		assert_eq!(Conditional::Done(1), Conditional::from_result(Ok(1)).unwrap());
+
+		let missing = status_error(404, "key").unwrap();
+		assert!(is_not_found(&missing));
+		assert!(!is_not_found(&AWSError::new(format!("{}: key", NOT_FOUND))));
+		assert!(Conditional::<()>::from_result(Err(missing)).is_err());
+		assert!(status_error(500, "key").is_none());
+	}
+
+	#[test]
+	fn conditions_fill_requests() {
+		let mut get = GetObjectRequest::default();
+		Conditions::if_modified_since(UTC.ymd(2009, 10, 12).and_hms(17, 50, 0)).apply_to_get_object(&mut get);
+		assert_eq!(Some("Wed, 12 Oct 2009 17:50:00 GMT".to_string()), get.if_modified_since);
+		assert_eq!(None, get.if_none_match);
+
+		let mut put = PutObjectRequest::default();
+		Conditions::if_absent().apply_to_put_object(&mut put).unwrap();
+		assert_eq!(Some("*".to_string()), put.if_none_match);
+		assert!(Conditions::if_modified_since(UTC::now()).apply_to_put_object(&mut put).is_err());
+	}
+
+	#[test]
+	fn object_metadata_from_head() {
+		let mut head = HeadObjectOutput::default();
+		head.content_length = 5368709120;
+		head.last_modified = Some(UTC.ymd(2009, 10, 12).and_hms(17, 50, 0));
+		head.e_tag = "\"fba9dede5f27731c9771645a39863328\"".to_string();
+		head.metadata.insert("owner".to_string(), "ops".to_string());
+
+		let metadata = ObjectMetadata::from_head(head).unwrap();
+		assert_eq!(5368709120, metadata.size);
+		assert_eq!(Some(UTC.ymd(2009, 10, 12).and_hms(17, 50, 0)), metadata.last_modified);
+		assert_eq!(None, metadata.version_id);
+		assert_eq!(Some(&"ops".to_string()), metadata.metadata.get("owner"));
+	}
+}
@@ -17,9 +17,13 @@ use std::io::prelude::*;
 use std::io::BufReader;
 use std::ascii::AsciiExt;
 use std::collections::HashMap;
-use hyper::Client;
-use hyper::header::Connection;
+use std::fmt;
+use std::sync::{Arc, Mutex, Condvar};
+use std::thread;
+use std::time::Duration as StdDuration;
 use error::*;
+use metadata::InstanceMetadataClient;
+use util::zero_string;
 use regex::Regex;
 
 extern crate rustc_serialize;
@@ -28,9 +32,10 @@ use self::rustc_serialize::json::*;
 extern crate chrono;
 use self::chrono::*;
 
-
 /// Represents AWS credentials.  Includes access key, secret key, token (for IAM profiles) and expiration timestamp.
-#[derive(Clone, Debug)]
+///
+/// The secret key and token are redacted from `Debug` output and overwritten with zeros when dropped.
+#[derive(Clone)]
 pub struct AWSCredentials {
     key: String,
     secret: String,
@@ -73,10 +78,42 @@ impl AWSCredentials {
         }
         return false;
     }
+
+    /// True until the moment the credentials expire, without the safety margin of
+    /// `credentials_are_expired`.  Used to keep serving cached credentials when a refresh fails.
+    fn credentials_are_usable(&self) -> bool {
+        self.expires_at > UTC::now()
+    }
+}
+
+impl fmt::Debug for AWSCredentials {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let token = match self.token {
+            Some(_) => "Some(**redacted**)",
+            None => "None",
+        };
+        write!(f, "AWSCredentials {{ key: {:?}, secret: **redacted**, token: {}, expires_at: {:?} }}",
+            self.key, token, self.expires_at)
+    }
+}
+
+impl Drop for AWSCredentials {
+    fn drop(&mut self) {
+        zero_string(&mut self.secret);
+        if let Some(ref mut token) = self.token {
+            zero_string(token);
+        }
+    }
 }
 
 pub trait AWSCredentialsProvider {
 	fn get_credentials(&mut self) -> Result<&AWSCredentials, AWSError>;
+
+	/// Fetches new credentials even if the cached ones haven't expired yet.
+	/// Providers without a cache of their own can rely on the default.
+	fn refresh_credentials(&mut self) -> Result<&AWSCredentials, AWSError> {
+		self.get_credentials()
+	}
 }
 
 fn err(message: &str) -> Result<&AWSCredentials, AWSError> {
@@ -95,6 +132,11 @@ impl AWSCredentialsProvider for EnvironmentCredentialsProvider {
         }
         Ok(self.credentials.as_ref().unwrap())
 	}
+
+	fn refresh_credentials(&mut self) -> Result<&AWSCredentials, AWSError> {
+        self.credentials = Some(try!(get_credentials_from_environment()));
+        Ok(self.credentials.as_ref().unwrap())
+	}
 }
 
 impl EnvironmentCredentialsProvider {
@@ -159,6 +201,11 @@ impl AWSCredentialsProvider for ProfileCredentialsProvider {
        }
        Ok(self.credentials.as_ref().unwrap())
    }
+
+    fn refresh_credentials(&mut self) -> Result<&AWSCredentials, AWSError> {
+        self.credentials = None;
+        self.get_credentials()
+    }
 }
 
 impl ProfileCredentialsProvider {
@@ -262,93 +309,88 @@ fn parse_credentials_file(file_with_path: &str) -> Result<HashMap<String, AWSCre
 }
 
 /// IAM profile source of AWS credentials
+///
+/// Fetches the credentials of the instance profile role from the EC2 instance metadata service.
+/// See the `metadata` module for IMDSv2 and the environment variables that control it.
 pub struct IAMRoleCredentialsProvider {
-    credentials: Option<AWSCredentials>
+    credentials: Option<AWSCredentials>,
+    metadata: InstanceMetadataClient,
 }
 
 impl IAMRoleCredentialsProvider {
-    fn new() -> IAMRoleCredentialsProvider {
-        IAMRoleCredentialsProvider { credentials: None }
+    pub fn new() -> IAMRoleCredentialsProvider {
+        IAMRoleCredentialsProvider { credentials: None, metadata: InstanceMetadataClient::new() }
+    }
+
+    /// Uses a metadata service at `endpoint`, EG: `http://localhost:8080`.
+    pub fn with_endpoint(endpoint: &str) -> IAMRoleCredentialsProvider {
+        IAMRoleCredentialsProvider { credentials: None, metadata: InstanceMetadataClient::with_endpoint(endpoint) }
+    }
+
+    /// Allow or forbid falling back to IMDSv1 when a session token can't be acquired.
+    pub fn set_allow_v1_fallback(&mut self, allow: bool) {
+        self.metadata.set_allow_v1_fallback(allow);
+    }
+
+    pub fn get_endpoint(&self) -> &str {
+        self.metadata.get_endpoint()
+    }
+
+    fn fetch_credentials(&mut self) -> Result<AWSCredentials, AWSError> {
+        let role_name = try!(self.metadata.iam_role_name());
+        let body = try!(self.metadata.iam_security_credentials(&role_name));
+        parse_instance_profile_credentials(&body)
     }
 }
 
 impl AWSCredentialsProvider for IAMRoleCredentialsProvider {
     fn get_credentials(&mut self) -> Result<&AWSCredentials, AWSError> {
         if self.credentials.is_none() || self.credentials.as_ref().unwrap().credentials_are_expired() {
-            // TODO: backoff and retry on failure.
-
-            //println!("Calling IAM metadata");
-            // for "real" use: http://169.254.169.254/latest/meta-data/iam/security-credentials/
-            let mut address : String = "http://169.254.169.254/latest/meta-data/iam/security-credentials".to_string();
-            let client = Client::new();
-            let mut response;
-            match client.get(&address)
-                .header(Connection::close()).send() {
-                    Err(_) => return err("Couldn't connect to metadata service"), // add Why?
-                    Ok(received_response) => response = received_response
-                };
-
-            let mut body = String::new();
-            match response.read_to_string(&mut body) {
-                Err(_) => return err("Didn't get a parsable response body from metadata service"),
-                Ok(_) => (),
-            };
-
-            address.push_str("/");
-            address.push_str(&body);
-            body = String::new();
-            match client.get(&address)
-                .header(Connection::close()).send() {
-                    Err(_) => return err("Didn't get a parseable response body from instance role details"),
-                    Ok(received_response) => response = received_response
-                };
-
-            match response.read_to_string(&mut body) {
-                Err(_) => return err("Had issues with reading iam role response: {}"),
-                Ok(_) => (),
-            };
-
-            let json_object : Json;
-            match Json::from_str(&body) {
-                Err(_) => return err("Couldn't parse metadata response body."),
-                Ok(val) => json_object = val
-            };
+            return self.refresh_credentials();
+        }
 
-            let access_key;
-            match json_object.find("AccessKeyId") {
-                None => return err("Couldn't find AccessKeyId in response."),
-                Some(val) => access_key = val.to_string().replace("\"", "")
-            };
+		Ok(&self.credentials.as_ref().unwrap())
+	}
 
-            let secret_key;
-            match json_object.find("SecretAccessKey") {
-                None => return err("Couldn't find SecretAccessKey in response."),
-                Some(val) => secret_key = val.to_string().replace("\"", "")
-            };
+    fn refresh_credentials(&mut self) -> Result<&AWSCredentials, AWSError> {
+        match self.fetch_credentials() {
+            Ok(creds) => self.credentials = Some(creds),
+            Err(why) => {
+                // metadata service hiccups shouldn't fail requests while we still hold working credentials
+                if !self.credentials.as_ref().map_or(false, |creds| creds.credentials_are_usable()) {
+                    return Err(why);
+                }
+            }
+        }
+        Ok(&self.credentials.as_ref().unwrap())
+    }
+}
 
-            let expiration;
-            match json_object.find("Expiration") {
-                None => return err("Couldn't find Expiration in response."),
-                Some(val) => expiration = val.to_string().replace("\"", "")
-            };
+/// Parses the JSON document served for an instance profile role.
+fn parse_instance_profile_credentials(body: &str) -> Result<AWSCredentials, AWSError> {
+    let json_object = match Json::from_str(body) {
+        Err(_) => return Err(AWSError::new("Couldn't parse metadata response body.")),
+        Ok(val) => val
+    };
 
-            let expiration_time;
-            match expiration.parse::<DateTime<UTC>>() {
-                Err(why) => panic!("Kabloey on parse: {}", why),
-                Ok(val) => expiration_time = val
-            };
+    let access_key = try!(json_string_field(&json_object, "AccessKeyId"));
+    let secret_key = try!(json_string_field(&json_object, "SecretAccessKey"));
+    let token = try!(json_string_field(&json_object, "Token"));
+    let expiration = try!(json_string_field(&json_object, "Expiration"));
 
-            let token_from_response;
-            match json_object.find("Token") {
-                None => return err("Couldn't find Token in response."),
-                Some(val) => token_from_response = val.to_string().replace("\"", "")
-            };
+    let expiration_time = match expiration.parse::<DateTime<UTC>>() {
+        Err(why) => return Err(AWSError::new(format!("Couldn't parse credentials expiration {}: {}", expiration, why))),
+        Ok(val) => val
+    };
 
-            self.credentials = Some(AWSCredentials::new(access_key, secret_key, Some(token_from_response.to_string()), expiration_time));
-        }
+    Ok(AWSCredentials::new(access_key, secret_key, Some(token), expiration_time))
+}
 
-		Ok(&self.credentials.as_ref().unwrap())
-	}
+fn json_string_field(json_object: &Json, name: &str) -> Result<String, AWSError> {
+    match json_object.find(name).and_then(|val| val.as_string()) {
+        None => Err(AWSError::new(format!("Couldn't find {} in response.", name))),
+        Some(val) => Ok(val.to_string())
+    }
 }
 
 /// Internally chains AWS credential providers in priority order.
@@ -362,20 +404,24 @@ pub struct DefaultAWSCredentialsProviderChain {
 impl AWSCredentialsProvider for DefaultAWSCredentialsProviderChain {
     fn get_credentials(&mut self) -> Result<&AWSCredentials, AWSError> {
         if self.credentials.is_none() || self.credentials.as_ref().unwrap().credentials_are_expired() {
-            // fetch creds in order: env, file, IAM
-
-            if let Ok(creds) = EnvironmentCredentialsProvider::new().get_credentials() {
-                //println!("Found creds in env");
-                self.credentials = Some(creds.clone());
-            } else if let Ok(creds) = ProfileCredentialsProvider::new().with_profile(&self.profile).get_credentials() {
-                //println!("Found creds in file");
-                self.credentials = Some(creds.clone());
-            } else if let Ok(creds) = IAMRoleCredentialsProvider::new().get_credentials() {
-                //println!("Found creds via iam");
-                self.credentials = Some(creds.clone());
-            } else {
-               return Err(AWSError::new("Couldn't find AWS credentials in environment, default credential file location or IAM role."))
-            }
+            return self.refresh_credentials();
+        }
+        Ok(self.credentials.as_ref().unwrap())
+    }
+
+    fn refresh_credentials(&mut self) -> Result<&AWSCredentials, AWSError> {
+        // fetch creds in order: env, file, IAM
+        if let Ok(creds) = EnvironmentCredentialsProvider::new().get_credentials() {
+            //println!("Found creds in env");
+            self.credentials = Some(creds.clone());
+        } else if let Ok(creds) = ProfileCredentialsProvider::new().with_profile(&self.profile).get_credentials() {
+            //println!("Found creds in file");
+            self.credentials = Some(creds.clone());
+        } else if let Ok(creds) = IAMRoleCredentialsProvider::new().get_credentials() {
+            //println!("Found creds via iam");
+            self.credentials = Some(creds.clone());
+        } else if !self.credentials.as_ref().map_or(false, |creds| creds.credentials_are_usable()) {
+           return Err(AWSError::new("Couldn't find AWS credentials in environment, default credential file location or IAM role."))
         }
         Ok(self.credentials.as_ref().unwrap())
     }
@@ -395,6 +441,174 @@ impl DefaultAWSCredentialsProviderChain {
     }
 }
 
+/// Wraps another provider and refreshes its credentials on a background thread before they expire.
+///
+/// Requests keep getting the current credentials while a refresh is in flight, so a slow STS or
+/// metadata service call never stalls them.  If a refresh fails the cached credentials are served
+/// for as long as they remain valid, and the refresh is retried.
+///
+/// ```no_run
+/// use rusoto::credentials::*;
+///
+/// let provider = BackgroundRefreshingCredentialsProvider::new(DefaultAWSCredentialsProviderChain::new());
+/// ```
+pub struct BackgroundRefreshingCredentialsProvider {
+    state: Arc<RefreshState>,
+    credentials: Option<AWSCredentials>,
+}
+
+struct RefreshState {
+    provider: Mutex<Box<AWSCredentialsProvider + Send>>,
+    cached: Mutex<Option<AWSCredentials>>,
+    shutdown: Mutex<bool>,
+    wakeup: Condvar,
+    refresh_before: Duration,
+    retry_interval: Duration,
+}
+
+impl BackgroundRefreshingCredentialsProvider {
+    /// Refreshes credentials five minutes before they expire, retrying failures every ten seconds.
+    pub fn new<P: AWSCredentialsProvider + Send + 'static>(provider: P) -> BackgroundRefreshingCredentialsProvider {
+        BackgroundRefreshingCredentialsProvider::with_configuration(provider, Duration::minutes(5), Duration::seconds(10))
+    }
+
+    /// Refreshes credentials `refresh_before` ahead of their expiration, retrying failed
+    /// refreshes after `retry_interval`.
+    pub fn with_configuration<P: AWSCredentialsProvider + Send + 'static>(provider: P, refresh_before: Duration,
+            retry_interval: Duration) -> BackgroundRefreshingCredentialsProvider {
+        let state = Arc::new(RefreshState {
+            provider: Mutex::new(Box::new(provider)),
+            cached: Mutex::new(None),
+            shutdown: Mutex::new(false),
+            wakeup: Condvar::new(),
+            refresh_before: refresh_before,
+            retry_interval: retry_interval,
+        });
+
+        let thread_state = state.clone();
+        thread::spawn(move || refresh_in_background(thread_state));
+
+        BackgroundRefreshingCredentialsProvider { state: state, credentials: None }
+    }
+}
+
+impl AWSCredentialsProvider for BackgroundRefreshingCredentialsProvider {
+    fn get_credentials(&mut self) -> Result<&AWSCredentials, AWSError> {
+        let cached = self.state.cached.lock().unwrap().clone();
+        match cached {
+            Some(ref creds) if !creds.credentials_are_expired() => self.credentials = Some(creds.clone()),
+            // Nothing fetched yet, or the background refreshes kept failing: try it ourselves.
+            _ => { try!(self.refresh_credentials()); },
+        }
+        Ok(self.credentials.as_ref().unwrap())
+    }
+
+    fn refresh_credentials(&mut self) -> Result<&AWSCredentials, AWSError> {
+        match self.state.refresh() {
+            Ok(creds) => self.credentials = Some(creds),
+            Err(why) => {
+                match self.state.cached.lock().unwrap().clone() {
+                    Some(creds) => {
+                        if !creds.credentials_are_usable() {
+                            return Err(why);
+                        }
+                        self.credentials = Some(creds);
+                    },
+                    None => return Err(why),
+                }
+            }
+        }
+        Ok(self.credentials.as_ref().unwrap())
+    }
+}
+
+impl Drop for BackgroundRefreshingCredentialsProvider {
+    fn drop(&mut self) {
+        *self.state.shutdown.lock().unwrap() = true;
+        self.state.wakeup.notify_all();
+    }
+}
+
+impl RefreshState {
+    fn refresh(&self) -> Result<AWSCredentials, AWSError> {
+        let creds = {
+            let mut provider = self.provider.lock().unwrap();
+            try!(provider.refresh_credentials()).clone()
+        };
+        *self.cached.lock().unwrap() = Some(creds.clone());
+        Ok(creds)
+    }
+
+    /// How long the background thread sleeps before its next refresh attempt.
+    fn time_until_refresh(&self) -> StdDuration {
+        let wait = match *self.cached.lock().unwrap() {
+            Some(ref creds) => *creds.get_expires_at() - self.refresh_before - UTC::now(),
+            None => self.retry_interval,
+        };
+        let wait = if wait < self.retry_interval { self.retry_interval } else { wait };
+        StdDuration::from_millis(wait.num_milliseconds() as u64)
+    }
+}
+
+fn refresh_in_background(state: Arc<RefreshState>) {
+    loop {
+        let wait = state.time_until_refresh();
+        {
+            let shutdown = state.shutdown.lock().unwrap();
+            if *shutdown {
+                return;
+            }
+            let (shutdown, _) = state.wakeup.wait_timeout(shutdown, wait).unwrap();
+            if *shutdown {
+                return;
+            }
+        }
+        // Failures are retried on the next pass; callers keep the cached credentials meanwhile.
+        let _ = state.refresh();
+    }
+}
+
+/// Shares one provider between threads: clones hand out whatever credentials the wrapped
+/// provider currently holds, so it only has to fetch and refresh them once for all of its users.
+///
+/// ```no_run
+/// use rusoto::credentials::*;
+///
+/// let provider = SharedCredentialsProvider::new(DefaultAWSCredentialsProviderChain::new());
+/// let for_worker = provider.clone();
+/// ```
+#[derive(Clone)]
+pub struct SharedCredentialsProvider {
+    provider: Arc<Mutex<Box<AWSCredentialsProvider + Send>>>,
+    credentials: Option<AWSCredentials>,
+}
+
+impl SharedCredentialsProvider {
+    pub fn new<P: AWSCredentialsProvider + Send + 'static>(provider: P) -> SharedCredentialsProvider {
+        SharedCredentialsProvider { provider: Arc::new(Mutex::new(Box::new(provider))), credentials: None }
+    }
+}
+
+impl AWSCredentialsProvider for SharedCredentialsProvider {
+    fn get_credentials(&mut self) -> Result<&AWSCredentials, AWSError> {
+        let creds = {
+            let mut provider = self.provider.lock().unwrap();
+            try!(provider.get_credentials()).clone()
+        };
+        self.credentials = Some(creds);
+        Ok(self.credentials.as_ref().unwrap())
+    }
+
+    fn refresh_credentials(&mut self) -> Result<&AWSCredentials, AWSError> {
+        let creds = {
+            let mut provider = self.provider.lock().unwrap();
+            try!(provider.refresh_credentials()).clone()
+        };
+        self.credentials = Some(creds);
+        Ok(self.credentials.as_ref().unwrap())
+    }
+}
+
 fn in_ten_minutes() -> DateTime<UTC> {
     UTC::now() + Duration::seconds(600)
 }
@@ -403,6 +617,11 @@ fn in_ten_minutes() -> DateTime<UTC> {
 mod tests {
     use super::*;
     use error::*;
+    use metadata::stub::*;
+    use std::sync::{Arc, Condvar, Mutex};
+    use std::thread;
+    use std::time::{Duration as StdDuration, Instant};
+    use super::chrono::{UTC, Duration};
 
     #[test]
     fn parse_credentials_file_default_profile() {
@@ -453,7 +672,7 @@ mod tests {
         let result = provider.get_credentials();
 
         assert!(result.is_err());
-        assert_eq!(result.err(), Some(AWSError("profile not found".to_string())));
+        assert_eq!(result.err(), Some(AWSError::new("profile not found")));
      }
 
      #[test]
@@ -489,4 +708,164 @@ mod tests {
         assert_eq!(result.err(), Some(AWSError::new("Couldn't open file.")));
     }
 
+    /// Number of fetches a `CountingProvider` has made, which tests can wait on.
+    #[derive(Clone)]
+    struct Fetches {
+        count: Arc<(Mutex<usize>, Condvar)>,
+    }
+
+    impl Fetches {
+        fn new() -> Fetches {
+            Fetches { count: Arc::new((Mutex::new(0), Condvar::new())) }
+        }
+
+        fn increment(&self) -> usize {
+            let &(ref count, ref changed) = &*self.count;
+            let mut count = count.lock().unwrap();
+            *count += 1;
+            changed.notify_all();
+            *count
+        }
+
+        fn get(&self) -> usize {
+            *self.count.0.lock().unwrap()
+        }
+
+        /// Waits for more than `fetches` fetches, failing the test if they take over ten seconds.
+        fn wait_for_more_than(&self, fetches: usize) {
+            let deadline = Instant::now() + StdDuration::from_secs(10);
+            let &(ref count, ref changed) = &*self.count;
+            let mut count = count.lock().unwrap();
+            while *count <= fetches {
+                let now = Instant::now();
+                assert!(now < deadline, "only {} fetches after ten seconds", *count);
+                count = changed.wait_timeout(count, deadline - now).unwrap().0;
+            }
+        }
+    }
+
+    /// Hands out credentials valid for ten minutes, failing every fetch after `fail_after`.
+    struct CountingProvider {
+        fetches: Fetches,
+        fail_after: usize,
+        credentials: Option<AWSCredentials>,
+    }
+
+    impl AWSCredentialsProvider for CountingProvider {
+        fn get_credentials(&mut self) -> Result<&AWSCredentials, AWSError> {
+            let fetches = self.fetches.increment();
+            if fetches > self.fail_after {
+                return Err(AWSError::new("transient failure"));
+            }
+            self.credentials = Some(AWSCredentials::new(format!("key{}", fetches), "secret", None,
+                UTC::now() + Duration::minutes(10)));
+            Ok(self.credentials.as_ref().unwrap())
+        }
+    }
+
+    fn counting_provider(fail_after: usize) -> (CountingProvider, Fetches) {
+        let fetches = Fetches::new();
+        (CountingProvider { fetches: fetches.clone(), fail_after: fail_after, credentials: None }, fetches)
+    }
+
+    #[test]
+    fn background_provider_refreshes_ahead_of_expiry() {
+        let (inner, fetches) = counting_provider(1000);
+        // credentials always fall inside the refresh window, so every retry interval triggers a refresh
+        let mut provider = BackgroundRefreshingCredentialsProvider::with_configuration(inner,
+            Duration::minutes(10), Duration::milliseconds(20));
+
+        assert!(provider.get_credentials().is_ok());
+        // the third fetch only starts once the second one's credentials are cached
+        fetches.wait_for_more_than(2);
+        assert!(provider.get_credentials().unwrap().get_aws_access_key_id() != "key1");
+    }
+
+    #[test]
+    fn background_provider_keeps_valid_credentials_when_refresh_fails() {
+        let (inner, fetches) = counting_provider(1);
+        let mut provider = BackgroundRefreshingCredentialsProvider::with_configuration(inner,
+            Duration::minutes(10), Duration::milliseconds(20));
+
+        assert_eq!(provider.get_credentials().unwrap().get_aws_access_key_id(), "key1");
+        fetches.wait_for_more_than(1);
+        assert_eq!(provider.get_credentials().unwrap().get_aws_access_key_id(), "key1");
+        assert_eq!(provider.refresh_credentials().unwrap().get_aws_access_key_id(), "key1");
+    }
+
+    #[test]
+    fn background_provider_reports_first_failure() {
+        let (inner, _) = counting_provider(0);
+        let mut provider = BackgroundRefreshingCredentialsProvider::new(inner);
+        assert_eq!(provider.get_credentials().err(), Some(AWSError::new("transient failure")));
+    }
+
+    #[test]
+    fn shared_provider_clones_use_one_provider() {
+        let (inner, fetches) = counting_provider(10);
+        let mut provider = SharedCredentialsProvider::new(inner);
+        let mut other = provider.clone();
+
+        assert_eq!(provider.get_credentials().unwrap().get_aws_access_key_id(), "key1");
+        let handle = thread::spawn(move || other.get_credentials().unwrap().get_aws_access_key_id().to_string());
+        assert_eq!(handle.join().unwrap(), "key2");
+        assert_eq!(fetches.get(), 2);
+    }
+
+    #[test]
+    fn credentials_debug_output_is_redacted() {
+        let creds = AWSCredentials::new("AKIDEXAMPLE", "supersecretkey", Some("supersecrettoken".to_string()),
+            UTC::now());
+        let output = format!("{:?}", creds);
+
+        assert!(output.contains("AKIDEXAMPLE"));
+        assert!(!output.contains("supersecretkey"));
+        assert!(!output.contains("supersecrettoken"));
+    }
+
+    #[test]
+    fn parse_instance_profile_credentials_happy_path() {
+        let result = super::parse_instance_profile_credentials(IAM_ROLE_CREDENTIALS);
+        assert!(result.is_ok());
+
+        let creds = result.ok().unwrap();
+        assert_eq!(creds.get_aws_access_key_id(), "ASIAEXAMPLEKEY");
+        assert_eq!(creds.get_aws_secret_key(), "examplesecret");
+        assert_eq!(creds.get_token(), &Some("exampletoken".to_string()));
+    }
+
+    #[test]
+    fn parse_instance_profile_credentials_bad_expiration() {
+        let body = IAM_ROLE_CREDENTIALS.replace("2015-10-09T18:47:42Z", "tomorrow-ish");
+        let result = super::parse_instance_profile_credentials(&body);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn iam_role_provider_uses_session_token() {
+        let endpoint = metadata_stub_server(true);
+        let mut provider = IAMRoleCredentialsProvider::with_endpoint(&endpoint);
+        provider.set_allow_v1_fallback(false);
+        let result = provider.get_credentials();
+
+        assert!(result.is_ok());
+        assert_eq!(result.ok().unwrap().get_aws_access_key_id(), "ASIAEXAMPLEKEY");
+    }
+
+    #[test]
+    fn iam_role_provider_v1_fallback() {
+        let endpoint = metadata_stub_server(false);
+        let mut provider = IAMRoleCredentialsProvider::with_endpoint(&endpoint);
+        provider.set_allow_v1_fallback(true);
+        assert!(provider.get_credentials().is_ok());
+    }
+
+    #[test]
+    fn iam_role_provider_v1_fallback_disabled() {
+        let endpoint = metadata_stub_server(false);
+        let mut provider = IAMRoleCredentialsProvider::with_endpoint(&endpoint);
+        provider.set_allow_v1_fallback(false);
+        assert!(provider.get_credentials().is_err());
+    }
+
 }
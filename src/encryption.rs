@@ -0,0 +1,327 @@
+//! Client-side encryption of S3 objects
+//!
+//! `S3EncryptionClient` encrypts objects before they leave the process and decrypts them after
+//! download, so S3 only ever stores ciphertext.  It uses envelope encryption: every object is
+//! encrypted with its own random AES-256-GCM data key, and the data key is stored next to the object,
+//! wrapped (encrypted) by a `KeyWrapper` holding the master key.
+//!
+//! Objects are laid out like the AWS S3 Encryption Client's v2 format, so either client can read
+//! what the other wrote: the body is the ciphertext followed by the 16 byte GCM tag, and the wrapped
+//! key, IV and algorithms go in `x-amz-meta-x-amz-key-v2`, `x-amz-meta-x-amz-iv` and friends.
+//!
+//! ```no_run
+//! use rusoto::credentials::DefaultAWSCredentialsProviderChain;
+//! use rusoto::encryption::{LocalMasterKey, S3EncryptionClient};
+//! use rusoto::regions::Region;
+//! use rusoto::s3::S3Helper;
+//!
+//! let region = Region::UsEast1;
+//! let s3 = S3Helper::new(DefaultAWSCredentialsProviderChain::new(), &region);
+//! let master_key = LocalMasterKey::new(&[7; 32]).unwrap();
+//! let mut client = S3EncryptionClient::new(s3, master_key);
+//! client.put_object("my-bucket", "secrets.txt", b"attack at dawn").unwrap();
+//! let object = client.get_object("my-bucket", "secrets.txt").unwrap();
+//! assert_eq!(&object.body[..], b"attack at dawn");
+//! ```
+
+use crypto::aead::{AeadDecryptor, AeadEncryptor};
+use crypto::aes::KeySize;
+use crypto::aes_gcm::AesGcm;
+use openssl::crypto::rand::rand_bytes;
+use serialize::base64::{FromBase64, ToBase64, STANDARD};
+
+use util::zero_memory;
+use error::AWSError;
+use s3::*;
+
+/// Algorithm the object bodies are encrypted with, stored as `x-amz-cek-alg`
+pub const CONTENT_ENCRYPTION_ALGORITHM: &'static str = "AES/GCM/NoPadding";
+/// Key wrapping algorithm of `LocalMasterKey`, stored as `x-amz-wrap-alg`
+pub const AES_GCM_WRAP_ALGORITHM: &'static str = "AES/GCM";
+
+const KEY_LENGTH: usize = 32;
+const IV_LENGTH: usize = 12;
+const TAG_LENGTH: usize = 16;
+
+// metadata names, without the x-amz-meta- prefix S3 adds
+const META_KEY_V2: &'static str = "x-amz-key-v2";
+const META_KEY_V1: &'static str = "x-amz-key";
+const META_IV: &'static str = "x-amz-iv";
+const META_MATERIAL_DESCRIPTION: &'static str = "x-amz-matdesc";
+const META_CEK_ALGORITHM: &'static str = "x-amz-cek-alg";
+const META_WRAP_ALGORITHM: &'static str = "x-amz-wrap-alg";
+const META_TAG_LENGTH: &'static str = "x-amz-tag-len";
+const META_UNENCRYPTED_LENGTH: &'static str = "x-amz-unencrypted-content-length";
+
+/// Wraps the data keys objects are encrypted with, and unwraps them again for decryption.
+/// `LocalMasterKey` holds an AES key in the process; a KMS backed wrapper plugs in the same way.
+pub trait KeyWrapper {
+	/// Name of the wrapping algorithm, stored as `x-amz-wrap-alg`
+	fn wrap_algorithm(&self) -> String;
+
+	/// JSON object describing the master key, stored as `x-amz-matdesc`
+	fn material_description(&self) -> String {
+		"{}".to_string()
+	}
+
+	/// Encrypts a data key.  `cek_algorithm` is the algorithm the data key encrypts the object with.
+	fn wrap_key(&mut self, data_key: &[u8], cek_algorithm: &str) -> Result<Vec<u8>, AWSError>;
+
+	/// Decrypts a data key wrapped with `wrap_algorithm` and the master key `material_description`
+	/// describes.
+	fn unwrap_key(&mut self, wrapped_key: &[u8], wrap_algorithm: &str, material_description: &str,
+		cek_algorithm: &str) -> Result<Vec<u8>, AWSError>;
+}
+
+/// A 256-bit AES master key held by the process.  Data keys are wrapped with AES-GCM under a fresh
+/// IV, authenticating the content encryption algorithm: the wrapped key is the IV, the encrypted
+/// data key and the tag.
+///
+/// The key is overwritten with zeros when dropped.
+pub struct LocalMasterKey {
+	key: Vec<u8>,
+}
+
+impl LocalMasterKey {
+	pub fn new(key: &[u8]) -> Result<LocalMasterKey, AWSError> {
+		if key.len() != KEY_LENGTH {
+			return Err(AWSError::new(format!("Master keys are {} bytes long, not {}", KEY_LENGTH, key.len())));
+		}
+		Ok(LocalMasterKey { key: key.to_vec() })
+	}
+}
+
+impl Drop for LocalMasterKey {
+	fn drop(&mut self) {
+		zero_memory(&mut self.key);
+	}
+}
+
+impl KeyWrapper for LocalMasterKey {
+	fn wrap_algorithm(&self) -> String {
+		AES_GCM_WRAP_ALGORITHM.to_string()
+	}
+
+	fn wrap_key(&mut self, data_key: &[u8], cek_algorithm: &str) -> Result<Vec<u8>, AWSError> {
+		let iv = rand_bytes(IV_LENGTH);
+		let (ciphertext, tag) = seal(&self.key, &iv, cek_algorithm.as_bytes(), data_key);
+		let mut wrapped = iv;
+		wrapped.extend(ciphertext);
+		wrapped.extend(tag);
+		Ok(wrapped)
+	}
+
+	fn unwrap_key(&mut self, wrapped_key: &[u8], wrap_algorithm: &str, _: &str,
+		cek_algorithm: &str) -> Result<Vec<u8>, AWSError> {
+
+		if wrap_algorithm != AES_GCM_WRAP_ALGORITHM {
+			return Err(AWSError::new(format!("A local master key can't unwrap data keys wrapped with {}", wrap_algorithm)));
+		}
+		if wrapped_key.len() != IV_LENGTH + KEY_LENGTH + TAG_LENGTH {
+			return Err(AWSError::new("Wrapped data key has the wrong length"));
+		}
+		let (iv, sealed) = wrapped_key.split_at(IV_LENGTH);
+		open(&self.key, iv, cek_algorithm.as_bytes(), sealed)
+			.ok_or(AWSError::new("Couldn't unwrap the data key: it was wrapped with another master key, or tampered with"))
+	}
+}
+
+/// Encrypts objects on upload and decrypts them on download, on top of an `S3Helper`.
+pub struct S3EncryptionClient<'a> {
+	s3: S3Helper<'a>,
+	key_wrapper: Box<KeyWrapper + 'a>,
+}
+
+impl<'a> S3EncryptionClient<'a> {
+	pub fn new<W: KeyWrapper + 'a>(s3: S3Helper<'a>, key_wrapper: W) -> S3EncryptionClient<'a> {
+		S3EncryptionClient { s3: s3, key_wrapper: Box::new(key_wrapper) }
+	}
+
+	/// The helper requests go through, EG: to list or delete encrypted objects.
+	pub fn helper(&mut self) -> &mut S3Helper<'a> {
+		&mut self.s3
+	}
+
+	/// Encrypts an object and uploads it.
+	pub fn put_object(&mut self, bucket_name: &str, object_name: &str, object_as_bytes: &[u8]) -> Result<PutObjectOutput, AWSError> {
+		let mut request = PutObjectRequest::default();
+		request.bucket = bucket_name.to_string();
+		request.key = object_name.to_string();
+		request.body = Some(object_as_bytes);
+		self.put_object_with_request(request)
+	}
+
+	/// Encrypts the body of a request and uploads it.  The rest of the request is sent as it is;
+	/// its metadata is kept alongside the encryption metadata.
+	pub fn put_object_with_request(&mut self, request: PutObjectRequest) -> Result<PutObjectOutput, AWSError> {
+		let plaintext = request.body.unwrap_or(b"");
+		let (ciphertext, encryption_metadata) = try!(encrypt_object(&mut *self.key_wrapper, plaintext));
+
+		let mut metadata = request.metadata.clone().unwrap_or(Metadata::new());
+		metadata.extend(encryption_metadata);
+		let mut encrypted = PutObjectRequest { body: Some(&ciphertext), metadata: Some(metadata), ..request };
+		self.s3.put_object_with_request(&mut encrypted)
+	}
+
+	/// Downloads an object and decrypts it.
+	pub fn get_object(&mut self, bucket_name: &str, object_name: &str) -> Result<GetObjectOutput, AWSError> {
+		let mut request = GetObjectRequest::default();
+		request.bucket = bucket_name.to_string();
+		request.key = object_name.to_string();
+		self.get_object_with_request(&request)
+	}
+
+	/// Downloads an object and decrypts it: the caller specifies the whole request.  Ranged
+	/// requests can't be decrypted, since the GCM tag covers the whole object.
+	pub fn get_object_with_request(&mut self, request: &GetObjectRequest) -> Result<GetObjectOutput, AWSError> {
+		if request.range.is_some() {
+			return Err(AWSError::new("Client-side encrypted objects can't be downloaded by range"));
+		}
+		let mut output = try!(self.s3.get_object_with_request(request));
+		let plaintext = try!(decrypt_object(&mut *self.key_wrapper, &output.body, &output.metadata));
+		output.content_length = plaintext.len() as i64;
+		output.body = plaintext;
+		Ok(output)
+	}
+}
+
+/// Encrypts an object body under a new data key.  Returns the body to upload and the metadata
+/// describing its encryption.
+pub fn encrypt_object<W: KeyWrapper + ?Sized>(key_wrapper: &mut W, plaintext: &[u8]) -> Result<(Vec<u8>, Metadata), AWSError> {
+	let mut data_key = rand_bytes(KEY_LENGTH);
+	let iv = rand_bytes(IV_LENGTH);
+	let wrapped_key = key_wrapper.wrap_key(&data_key, CONTENT_ENCRYPTION_ALGORITHM);
+	let (mut body, tag) = seal(&data_key, &iv, &[], plaintext);
+	zero_memory(&mut data_key);
+	let wrapped_key = try!(wrapped_key);
+	body.extend(tag);
+
+	let mut metadata = Metadata::new();
+	metadata.insert(META_KEY_V2.to_string(), wrapped_key.to_base64(STANDARD));
+	metadata.insert(META_IV.to_string(), iv.to_base64(STANDARD));
+	metadata.insert(META_MATERIAL_DESCRIPTION.to_string(), key_wrapper.material_description());
+	metadata.insert(META_CEK_ALGORITHM.to_string(), CONTENT_ENCRYPTION_ALGORITHM.to_string());
+	metadata.insert(META_WRAP_ALGORITHM.to_string(), key_wrapper.wrap_algorithm());
+	metadata.insert(META_TAG_LENGTH.to_string(), (TAG_LENGTH * 8).to_string());
+	metadata.insert(META_UNENCRYPTED_LENGTH.to_string(), plaintext.len().to_string());
+	Ok((body, metadata))
+}
+
+/// Decrypts an object body, given the object's metadata.
+pub fn decrypt_object<W: KeyWrapper + ?Sized>(key_wrapper: &mut W, body: &[u8], metadata: &Metadata) -> Result<Vec<u8>, AWSError> {
+	let wrapped_key = match metadata.get(META_KEY_V2) {
+		Some(wrapped_key) => try!(decode_base64(META_KEY_V2, wrapped_key)),
+		None if metadata.contains_key(META_KEY_V1) => {
+			return Err(AWSError::new("Object is encrypted in the S3 Encryption Client's v1 format, which isn't supported"));
+		}
+		None => return Err(AWSError::new("Object isn't client-side encrypted")),
+	};
+	let cek_algorithm = try!(required_metadata(metadata, META_CEK_ALGORITHM));
+	if cek_algorithm != CONTENT_ENCRYPTION_ALGORITHM {
+		return Err(AWSError::new(format!("Object is encrypted with {}, which isn't supported", cek_algorithm)));
+	}
+	if let Some(tag_length) = metadata.get(META_TAG_LENGTH) {
+		if *tag_length != (TAG_LENGTH * 8).to_string() {
+			return Err(AWSError::new(format!("GCM tags of {} bits aren't supported", tag_length)));
+		}
+	}
+	let iv = try!(decode_base64(META_IV, try!(required_metadata(metadata, META_IV))));
+	if iv.len() != IV_LENGTH || body.len() < TAG_LENGTH {
+		return Err(AWSError::new("Client-side encrypted object is malformed"));
+	}
+	let wrap_algorithm = try!(required_metadata(metadata, META_WRAP_ALGORITHM));
+	let material_description = metadata.get(META_MATERIAL_DESCRIPTION).map(|description| &description[..]).unwrap_or("{}");
+
+	let mut data_key = try!(key_wrapper.unwrap_key(&wrapped_key, wrap_algorithm, material_description, cek_algorithm));
+	let plaintext = open(&data_key, &iv, &[], body);
+	zero_memory(&mut data_key);
+	plaintext.ok_or(AWSError::new("Couldn't decrypt the object: it was tampered with"))
+}
+
+fn required_metadata<'m>(metadata: &'m Metadata, name: &str) -> Result<&'m str, AWSError> {
+	metadata.get(name)
+		.map(|value| &value[..])
+		.ok_or(AWSError::new(format!("Client-side encrypted object has no {} metadata", name)))
+}
+
+fn decode_base64(name: &str, value: &str) -> Result<Vec<u8>, AWSError> {
+	value.from_base64().map_err(|why| AWSError::new(format!("Couldn't decode {} metadata: {}", name, why)))
+}
+
+/// AES-256-GCM encryption: the ciphertext and the tag.
+fn seal(key: &[u8], iv: &[u8], aad: &[u8], plaintext: &[u8]) -> (Vec<u8>, Vec<u8>) {
+	let mut cipher = AesGcm::new(KeySize::KeySize256, key, iv, aad);
+	let mut ciphertext = vec![0; plaintext.len()];
+	let mut tag = vec![0; TAG_LENGTH];
+	cipher.encrypt(plaintext, &mut ciphertext, &mut tag);
+	(ciphertext, tag)
+}
+
+/// AES-256-GCM decryption of a ciphertext followed by its tag.  `None` when the tag doesn't match.
+fn open(key: &[u8], iv: &[u8], aad: &[u8], sealed: &[u8]) -> Option<Vec<u8>> {
+	let (ciphertext, tag) = sealed.split_at(sealed.len() - TAG_LENGTH);
+	let mut cipher = AesGcm::new(KeySize::KeySize256, key, iv, aad);
+	let mut plaintext = vec![0; ciphertext.len()];
+	if cipher.decrypt(ciphertext, &mut plaintext, tag) {
+		Some(plaintext)
+	} else {
+		None
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use s3::Metadata;
+	use serialize::base64::FromBase64;
+
+	fn master_key(byte: u8) -> LocalMasterKey {
+		LocalMasterKey::new(&[byte; 32]).unwrap()
+	}
+
+	#[test]
+	fn objects_round_trip() {
+		let mut key = master_key(7);
+		let (body, metadata) = encrypt_object(&mut key, b"attack at dawn").unwrap();
+		assert_eq!(body.len(), 14 + 16);
+		assert!(!body.windows(6).any(|window| window == b"attack"));
+		assert_eq!(decrypt_object(&mut key, &body, &metadata).unwrap(), b"attack at dawn".to_vec());
+	}
+
+	#[test]
+	fn metadata_follows_the_v2_format() {
+		let mut key = master_key(7);
+		let (_, metadata) = encrypt_object(&mut key, b"").unwrap();
+		assert_eq!(metadata["x-amz-cek-alg"], "AES/GCM/NoPadding");
+		assert_eq!(metadata["x-amz-wrap-alg"], "AES/GCM");
+		assert_eq!(metadata["x-amz-tag-len"], "128");
+		assert_eq!(metadata["x-amz-matdesc"], "{}");
+		assert_eq!(metadata["x-amz-unencrypted-content-length"], "0");
+		assert_eq!(metadata["x-amz-iv"].from_base64().unwrap().len(), 12);
+		// IV, encrypted data key and tag
+		assert_eq!(metadata["x-amz-key-v2"].from_base64().unwrap().len(), 12 + 32 + 16);
+	}
+
+	#[test]
+	fn tampering_is_detected() {
+		let mut key = master_key(7);
+		let (mut body, metadata) = encrypt_object(&mut key, b"attack at dawn").unwrap();
+		body[0] ^= 1;
+		assert!(decrypt_object(&mut key, &body, &metadata).is_err());
+	}
+
+	#[test]
+	fn another_master_key_cant_decrypt() {
+		let (body, metadata) = encrypt_object(&mut master_key(7), b"attack at dawn").unwrap();
+		let error = decrypt_object(&mut master_key(8), &body, &metadata).unwrap_err();
+		assert!(error.message().contains("another master key"));
+	}
+
+	#[test]
+	fn plain_objects_are_refused() {
+		let mut metadata = Metadata::new();
+		assert!(decrypt_object(&mut master_key(7), b"hello", &metadata).is_err());
+		metadata.insert("x-amz-key".to_string(), "AAAA".to_string());
+		assert!(decrypt_object(&mut master_key(7), b"hello", &metadata).unwrap_err().message().contains("v1"));
+	}
+}
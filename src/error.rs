@@ -1,29 +1,87 @@
 //! Errors during AWS communication or parsing
 //!
-//! Wrapper around String to store the error.
+//! Wrapper around String to store the error, with the kind of error for callers that need to
+//! tell some errors apart.
 //!
 
+use std::error::Error;
 use std::fmt;
+use std::io;
 use xmlutil::XmlParseError;
+use checksum::ChecksumMismatchError;
 
 /// Simple wrapper around a String to store the error
 #[derive(Debug, PartialEq)]
-pub struct AWSError(pub String);
+pub struct AWSError(String, AWSErrorKind);
+
+/// What went wrong, for the errors callers may want to handle.
+#[derive(Debug, PartialEq)]
+pub enum AWSErrorKind {
+	/// Any other error: see the message
+	Other,
+	/// Data sent or received didn't match its checksum or ETag
+	ChecksumMismatch(ChecksumMismatchError),
+	/// S3 refused an upload whose data didn't match the `Content-MD5` or checksum sent with it
+	UploadRejected,
+	/// The transfer's `ProgressListener` cancelled it
+	Cancelled,
+	/// A GET or HEAD's object hasn't changed since the `If-None-Match` or `If-Modified-Since` sent
+	NotModified,
+	/// A request's conditions weren't met
+	PreconditionFailed,
+	/// There's no such object or bucket
+	NotFound,
+}
 
 impl AWSError {
 	pub fn new<S>(msg:S) -> AWSError where S:Into<String>{
-		AWSError(msg.into())
+		AWSError(msg.into(), AWSErrorKind::Other)
+	}
+
+	/// An error of a kind callers can match on.
+	pub fn with_kind<S>(msg: S, kind: AWSErrorKind) -> AWSError where S: Into<String> {
+		AWSError(msg.into(), kind)
+	}
+
+	pub fn kind(&self) -> &AWSErrorKind {
+		&self.1
+	}
+
+	pub fn message(&self) -> &str {
+		&self.0
 	}
 }
 
 impl From<XmlParseError> for AWSError {
         fn from(err: XmlParseError) -> AWSError {
-                AWSError(format!("{:?}", err))
+                AWSError::new(format!("{:?}", err))
+        }
+}
+
+impl From<io::Error> for AWSError {
+        fn from(err: io::Error) -> AWSError {
+                // an AWSError passed through a Read or Write implementation keeps its kind
+                if err.get_ref().map_or(false, |inner| inner.is::<AWSError>()) {
+                        return *err.into_inner().unwrap().downcast::<AWSError>().unwrap();
+                }
+                AWSError::new(format!("{}", err))
+        }
+}
+
+impl From<ChecksumMismatchError> for AWSError {
+        fn from(err: ChecksumMismatchError) -> AWSError {
+                AWSError::with_kind(format!("{}", err), AWSErrorKind::ChecksumMismatch(err))
         }
 }
 
 impl fmt::Display for AWSError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self)
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Error for AWSError {
+    fn description(&self) -> &str {
+        &self.0
     }
 }
@@ -14,15 +14,31 @@
 //! Credentials are sourced from environment variables, AWS credentials file and IAM instance profiles,
 //! in that order.  IAM instance profile credentials are refreshed automatically as needed.
 //!
+//! ## Instance metadata
+//!
+//! The `metadata` module talks to the EC2 instance metadata service, EG: to look up the region
+//! an instance runs in at startup.
+//!
 //! ## Supported services
 //!
 //! * SQS
 //! * S3 (partial implementation)
 //!
+//! The `transfer` module uploads large S3 objects in parts, several at a time.  Transfers report
+//! their progress through the `progress` module.  The `listing` module iterates over S3 listings
+//! without the caller handling pages.  The `encryption` module encrypts S3 objects client-side
+//! before upload, the `checksum` module checks objects against their ETags and checksums, and the
+//! `upload` module sets the content type, metadata and other headers stored with uploaded objects.
+//! The `conditional` module makes S3 requests conditional on an object's ETag or last modified date.
+//! The `sync` module syncs local directories with S3 prefixes, like `aws s3 sync`.
+//!
 //! ## Requests and request signing
 //!
 //! Rusoto uses [AWS Signature 4](http://docs.aws.amazon.com/general/latest/gr/signature-version-4.html)
 //! to sign requests.
+//!
+//! Clients log each request and response through the [log](https://crates.io/crates/log) crate.  Add a
+//! `request::RequestHook` to a client for other instrumentation such as metrics or tracing.
 
 extern crate time;
 extern crate xml;
@@ -32,13 +48,25 @@ extern crate url;
 extern crate rustc_serialize as serialize;
 extern crate regex;
 extern crate crypto;
+#[macro_use] extern crate log;
 
 #[macro_use] pub mod params;
 #[macro_use] pub mod signature;
 pub mod credentials;
+pub mod metadata;
 pub mod error;
 pub mod sqs;
 pub mod s3;
 pub mod xmlutil;
 pub mod regions;
 pub mod request;
+pub mod transfer;
+pub mod progress;
+pub mod listing;
+pub mod encryption;
+pub mod checksum;
+pub mod upload;
+pub mod conditional;
+pub mod sync;
+mod workers;
+mod util;
@@ -0,0 +1,347 @@
+//! Iterators over S3 listings
+//!
+//! S3 returns listings a page at a time, up to 1000 entries per request.  A `ListIterator` hides
+//! the paging: it yields one object, version, upload or part at a time and fetches the next page
+//! when the current one runs out.  `S3Helper` has shortcuts such as `list_all_objects`, which
+//! lists with ListObjectsV2 and asks S3 to URL-encode keys so any key survives the XML response.
+//!
+//! ```no_run
+//! use rusoto::credentials::DefaultAWSCredentialsProviderChain;
+//! use rusoto::regions::Region;
+//! use rusoto::s3::S3Helper;
+//!
+//! let region = Region::UsEast1;
+//! let mut s3 = S3Helper::new(DefaultAWSCredentialsProviderChain::new(), &region);
+//! for object in s3.list_all_objects("my-bucket", "logs/").max_items(5000) {
+//!     let object = object.unwrap();
+//!     println!("{} {}", object.key, object.size);
+//! }
+//! ```
+
+use std::cmp;
+use std::vec;
+
+use error::AWSError;
+use s3::*;
+
+/// The most entries S3 returns in one page.
+const MAXIMUM_PAGE_SIZE: usize = 1000;
+
+/// Fetches one listing page after another.
+pub trait ListPager {
+	type Item;
+
+	/// Fetches the next page, asking for no more than `limit` entries when there's a limit.
+	/// Returns the page's entries and whether more pages follow.
+	fn next_page(&mut self, client: &mut S3Client, limit: Option<usize>) -> Result<(Vec<Self::Item>, bool), AWSError>;
+}
+
+/// Yields every entry of a listing, fetching pages as it goes.  An error ends the iteration.
+pub struct ListIterator<'c, 'a: 'c, P: ListPager> {
+	client: &'c mut S3Client<'a>,
+	pager: P,
+	page: vec::IntoIter<P::Item>,
+	more_pages: bool,
+	remaining: Option<usize>,
+}
+
+impl<'c, 'a: 'c, P: ListPager> ListIterator<'c, 'a, P> {
+	pub fn new(client: &'c mut S3Client<'a>, pager: P) -> ListIterator<'c, 'a, P> {
+		ListIterator {
+			client: client,
+			pager: pager,
+			page: Vec::new().into_iter(),
+			more_pages: true,
+			remaining: None,
+		}
+	}
+
+	/// Stops after `max_items` entries, without fetching pages beyond them.
+	pub fn max_items(mut self, max_items: usize) -> ListIterator<'c, 'a, P> {
+		self.remaining = Some(max_items);
+		self
+	}
+}
+
+impl<'c, 'a: 'c, P: ListPager> Iterator for ListIterator<'c, 'a, P> {
+	type Item = Result<P::Item, AWSError>;
+
+	fn next(&mut self) -> Option<Result<P::Item, AWSError>> {
+		if self.remaining == Some(0) {
+			return None;
+		}
+		loop {
+			if let Some(item) = self.page.next() {
+				self.remaining = self.remaining.map(|remaining| remaining - 1);
+				return Some(Ok(item));
+			}
+			if !self.more_pages {
+				return None;
+			}
+			match self.pager.next_page(self.client, self.remaining) {
+				Ok((items, more_pages)) => {
+					self.page = items.into_iter();
+					self.more_pages = more_pages;
+				}
+				Err(why) => {
+					self.more_pages = false;
+					return Some(Err(why));
+				}
+			}
+		}
+	}
+}
+
+/// Page size to ask S3 for, given how many entries are still wanted.
+fn page_size(limit: Option<usize>) -> Option<i32> {
+	limit.map(|limit| cmp::min(limit, MAXIMUM_PAGE_SIZE) as i32)
+}
+
+/// Pages through `list_objects_v2`, following continuation tokens.  This is how `S3Helper` lists
+/// objects.  Keys requested with encoding-type=url are decoded.
+pub struct ObjectV2Pager {
+	request: ListObjectsV2Request,
+}
+
+impl ObjectV2Pager {
+	pub fn new(request: ListObjectsV2Request) -> ObjectV2Pager {
+		ObjectV2Pager { request: request }
+	}
+}
+
+impl ListPager for ObjectV2Pager {
+	type Item = Object;
+
+	fn next_page(&mut self, client: &mut S3Client, limit: Option<usize>) -> Result<(Vec<Object>, bool), AWSError> {
+		if limit.is_some() {
+			self.request.max_keys = page_size(limit);
+		}
+		let mut output = try!(client.list_objects_v2(&self.request));
+		if self.request.encoding_type.as_ref().map(|encoding| encoding == "url").unwrap_or(false) {
+			decode_list_objects_v2_output(&mut output);
+		}
+		let more_pages = output.is_truncated && !output.next_continuation_token.is_empty();
+		if more_pages {
+			self.request.continuation_token = Some(output.next_continuation_token);
+		}
+		Ok((output.contents, more_pages))
+	}
+}
+
+/// Pages through the original, marker based `list_objects`.
+pub struct ObjectPager {
+	request: ListObjectsRequest,
+}
+
+impl ObjectPager {
+	pub fn new(request: ListObjectsRequest) -> ObjectPager {
+		ObjectPager { request: request }
+	}
+}
+
+impl ListPager for ObjectPager {
+	type Item = Object;
+
+	fn next_page(&mut self, client: &mut S3Client, limit: Option<usize>) -> Result<(Vec<Object>, bool), AWSError> {
+		if limit.is_some() {
+			self.request.max_keys = page_size(limit);
+		}
+		let output = try!(client.list_objects(&self.request));
+		let marker = next_object_marker(&output);
+		let more_pages = marker.is_some();
+		self.request.marker = marker;
+		Ok((output.contents, more_pages))
+	}
+}
+
+/// Where the listing after a truncated `list_objects` page starts.  S3 only sends `NextMarker` when
+/// the request had a delimiter; otherwise the next page starts after the last key.
+pub fn next_object_marker(output: &ListObjectsOutput) -> Option<String> {
+	if !output.is_truncated {
+		return None;
+	}
+	if !output.next_marker.is_empty() {
+		return Some(output.next_marker.clone());
+	}
+	output.contents.last().map(|object| object.key.clone())
+}
+
+/// Pages through `list_object_versions`, yielding the object versions.
+pub struct ObjectVersionPager {
+	request: ListObjectVersionsRequest,
+}
+
+impl ObjectVersionPager {
+	pub fn new(request: ListObjectVersionsRequest) -> ObjectVersionPager {
+		ObjectVersionPager { request: request }
+	}
+}
+
+impl ListPager for ObjectVersionPager {
+	type Item = ObjectVersion;
+
+	fn next_page(&mut self, client: &mut S3Client, limit: Option<usize>) -> Result<(Vec<ObjectVersion>, bool), AWSError> {
+		let (output, more_pages) = try!(next_versions_page(&mut self.request, client, limit));
+		Ok((output.versions, more_pages))
+	}
+}
+
+/// Pages through `list_object_versions`, yielding the delete markers.
+pub struct DeleteMarkerPager {
+	request: ListObjectVersionsRequest,
+}
+
+impl DeleteMarkerPager {
+	pub fn new(request: ListObjectVersionsRequest) -> DeleteMarkerPager {
+		DeleteMarkerPager { request: request }
+	}
+}
+
+impl ListPager for DeleteMarkerPager {
+	type Item = DeleteMarkerEntry;
+
+	fn next_page(&mut self, client: &mut S3Client, limit: Option<usize>) -> Result<(Vec<DeleteMarkerEntry>, bool), AWSError> {
+		let (output, more_pages) = try!(next_versions_page(&mut self.request, client, limit));
+		Ok((output.delete_markers, more_pages))
+	}
+}
+
+fn next_versions_page(request: &mut ListObjectVersionsRequest, client: &mut S3Client, limit: Option<usize>) -> Result<(ListObjectVersionsOutput, bool), AWSError> {
+	if limit.is_some() {
+		request.max_keys = page_size(limit);
+	}
+	let output = try!(client.list_object_versions(request));
+	if output.is_truncated {
+		request.key_marker = Some(output.next_key_marker.clone());
+		request.version_id_marker = Some(output.next_version_id_marker.clone());
+	}
+	let more_pages = output.is_truncated;
+	Ok((output, more_pages))
+}
+
+/// Pages through `list_multipart_uploads`.
+pub struct MultipartUploadPager {
+	request: ListMultipartUploadsRequest,
+}
+
+impl MultipartUploadPager {
+	pub fn new(request: ListMultipartUploadsRequest) -> MultipartUploadPager {
+		MultipartUploadPager { request: request }
+	}
+}
+
+impl ListPager for MultipartUploadPager {
+	type Item = MultipartUpload;
+
+	fn next_page(&mut self, client: &mut S3Client, limit: Option<usize>) -> Result<(Vec<MultipartUpload>, bool), AWSError> {
+		if limit.is_some() {
+			self.request.max_uploads = page_size(limit);
+		}
+		let output = try!(client.list_multipart_uploads(&self.request));
+		if output.is_truncated {
+			self.request.key_marker = Some(output.next_key_marker);
+			self.request.upload_id_marker = Some(output.next_upload_id_marker);
+		}
+		Ok((output.uploads, output.is_truncated))
+	}
+}
+
+/// Pages through `list_parts`.
+pub struct PartPager {
+	request: ListPartsRequest,
+}
+
+impl PartPager {
+	pub fn new(request: ListPartsRequest) -> PartPager {
+		PartPager { request: request }
+	}
+}
+
+impl ListPager for PartPager {
+	type Item = Part;
+
+	fn next_page(&mut self, client: &mut S3Client, limit: Option<usize>) -> Result<(Vec<Part>, bool), AWSError> {
+		if limit.is_some() {
+			self.request.max_parts = page_size(limit);
+		}
+		let output = try!(client.list_parts(&self.request));
+		if output.is_truncated {
+			self.request.part_number_marker = Some(output.next_part_number_marker);
+		}
+		Ok((output.parts, output.is_truncated))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use credentials::DefaultAWSCredentialsProviderChain;
+	use error::AWSError;
+	use regions::Region;
+	use s3::*;
+
+	/// Hands out canned pages, and records the limit asked for each time.
+	struct CannedPager {
+		pages: Vec<Result<Vec<i32>, AWSError>>,
+		limits: Vec<Option<usize>>,
+	}
+
+	impl ListPager for CannedPager {
+		type Item = i32;
+
+		fn next_page(&mut self, _: &mut S3Client, limit: Option<usize>) -> Result<(Vec<i32>, bool), AWSError> {
+			self.limits.push(limit);
+			let page = self.pages.remove(0);
+			page.map(|items| (items, !self.pages.is_empty()))
+		}
+	}
+
+	fn canned(pages: Vec<Result<Vec<i32>, AWSError>>) -> CannedPager {
+		CannedPager { pages: pages, limits: Vec::new() }
+	}
+
+	#[test]
+	fn iterator_walks_every_page() {
+		let region = Region::UsEast1;
+		let mut client = S3Client::new(DefaultAWSCredentialsProviderChain::new(), &region);
+		let pager = canned(vec![Ok(vec![1, 2]), Ok(vec![]), Ok(vec![3])]);
+		let items: Vec<i32> = ListIterator::new(&mut client, pager).map(|item| item.unwrap()).collect();
+		assert_eq!(items, vec![1, 2, 3]);
+	}
+
+	#[test]
+	fn iterator_stops_at_max_items() {
+		let region = Region::UsEast1;
+		let mut client = S3Client::new(DefaultAWSCredentialsProviderChain::new(), &region);
+		let mut iterator = ListIterator::new(&mut client, canned(vec![Ok(vec![1, 2]), Ok(vec![3, 4]), Ok(vec![5])])).max_items(3);
+		let items: Vec<i32> = iterator.by_ref().map(|item| item.unwrap()).collect();
+		assert_eq!(items, vec![1, 2, 3]);
+		// the last page was never fetched, and each request only asked for what was still wanted
+		assert_eq!(iterator.pager.limits, vec![Some(3), Some(1)]);
+	}
+
+	#[test]
+	fn iterator_ends_after_an_error() {
+		let region = Region::UsEast1;
+		let mut client = S3Client::new(DefaultAWSCredentialsProviderChain::new(), &region);
+		let pager = canned(vec![Ok(vec![1]), Err(AWSError::new("throttled")), Ok(vec![2])]);
+		let items: Vec<Result<i32, AWSError>> = ListIterator::new(&mut client, pager).collect();
+		assert_eq!(items.len(), 2);
+		assert_eq!(items[1].as_ref().unwrap_err().message(), "throttled");
+	}
+
+	#[test]
+	fn next_marker_falls_back_to_last_key() {
+		let mut output = ListObjectsOutput::default();
+		let mut object = Object::default();
+		object.key = "b".to_string();
+		output.contents.push(object);
+		assert_eq!(next_object_marker(&output), None);
+
+		output.is_truncated = true;
+		assert_eq!(next_object_marker(&output), Some("b".to_string()));
+
+		output.next_marker = "photos/".to_string();
+		assert_eq!(next_object_marker(&output), Some("photos/".to_string()));
+	}
+}
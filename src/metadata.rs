@@ -0,0 +1,468 @@
+//! EC2 instance metadata
+//!
+//! Client for the instance metadata service available on EC2 instances.  Used for IAM instance
+//! profile credentials and for looking up details about the running instance, such as its region.
+//!
+//! Requests use IMDSv2: a session token is acquired with `PUT /latest/api/token` and sent along with
+//! every metadata request.  Unauthenticated IMDSv1 requests are only used if the token request fails
+//! and fallback is allowed.
+//!
+//! ## Environment variables
+//!
+//! * `AWS_EC2_METADATA_DISABLED=true` turns off all metadata requests.
+//! * `AWS_EC2_METADATA_SERVICE_ENDPOINT` overrides the metadata endpoint, EG: `http://localhost:8080`.
+//! * `AWS_EC2_METADATA_V1_DISABLED=true` disables IMDSv1 fallback.
+
+use std::env::var;
+use std::io::{self, Read};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::thread;
+use std::time::Duration as StdDuration;
+use std::ascii::AsciiExt;
+use hyper::Client;
+use hyper::Error as HyperError;
+use hyper::header::{Connection, Headers};
+use hyper::method::Method;
+use hyper::net::{HttpStream, NetworkConnector};
+use hyper::status::StatusCode;
+use serialize::json::Json;
+use error::*;
+use regions::*;
+
+extern crate chrono;
+use self::chrono::{DateTime, UTC, Duration};
+
+const DEFAULT_METADATA_ENDPOINT: &'static str = "http://169.254.169.254";
+const METADATA_TOKEN_PATH: &'static str = "/latest/api/token";
+const METADATA_PATH: &'static str = "/latest/meta-data/";
+const USER_DATA_PATH: &'static str = "/latest/user-data";
+const IDENTITY_DOCUMENT_PATH: &'static str = "/latest/dynamic/instance-identity/document";
+const METADATA_TOKEN_TTL_SECONDS: i64 = 21600;
+const METADATA_TIMEOUT_MILLISECONDS: u64 = 1000;
+const METADATA_RETRY_DELAY_MILLISECONDS: u64 = 100;
+const METADATA_ATTEMPTS: u32 = 3;
+
+/// Client for the EC2 instance metadata service.
+pub struct InstanceMetadataClient {
+    endpoint: String,
+    allow_v1_fallback: bool,
+    token: Option<String>,
+    token_expires_at: DateTime<UTC>,
+}
+
+/// The instance identity document from `/latest/dynamic/instance-identity/document`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct InstanceIdentityDocument {
+    pub account_id: String,
+    pub architecture: String,
+    pub availability_zone: String,
+    pub image_id: String,
+    pub instance_id: String,
+    pub instance_type: String,
+    pub pending_time: String,
+    pub private_ip: String,
+    pub region: String,
+}
+
+impl InstanceMetadataClient {
+    /// Creates a client for the default metadata endpoint, or the one in `AWS_EC2_METADATA_SERVICE_ENDPOINT`.
+    pub fn new() -> InstanceMetadataClient {
+        let endpoint = match var("AWS_EC2_METADATA_SERVICE_ENDPOINT") {
+            Ok(ref val) if !val.is_empty() => val.to_string(),
+            _ => DEFAULT_METADATA_ENDPOINT.to_string(),
+        };
+        InstanceMetadataClient::with_endpoint(&endpoint)
+    }
+
+    /// Creates a client for a metadata service at `endpoint`, EG: `http://localhost:8080`.
+    pub fn with_endpoint(endpoint: &str) -> InstanceMetadataClient {
+        InstanceMetadataClient {
+            endpoint: endpoint.trim_right_matches('/').to_string(),
+            allow_v1_fallback: !env_flag_is_set("AWS_EC2_METADATA_V1_DISABLED"),
+            token: None,
+            token_expires_at: UTC::now(),
+        }
+    }
+
+    /// Allow or forbid falling back to IMDSv1 when a session token can't be acquired.
+    pub fn set_allow_v1_fallback(&mut self, allow: bool) {
+        self.allow_v1_fallback = allow;
+    }
+
+    pub fn get_endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Fetches a metadata item by its path under `/latest/meta-data/`, EG: `instance-id`.
+    pub fn get_metadata(&mut self, path: &str) -> Result<String, AWSError> {
+        let bytes = try!(self.get_path(&format!("{}{}", METADATA_PATH, path)));
+        bytes_to_string(bytes)
+    }
+
+    /// ID of the running instance, EG: `i-1234567890abcdef0`.
+    pub fn instance_id(&mut self) -> Result<String, AWSError> {
+        self.get_metadata("instance-id")
+    }
+
+    /// Instance type, EG: `m4.large`.
+    pub fn instance_type(&mut self) -> Result<String, AWSError> {
+        self.get_metadata("instance-type")
+    }
+
+    /// Availability zone the instance runs in, EG: `us-east-1a`.
+    pub fn availability_zone(&mut self) -> Result<String, AWSError> {
+        self.get_metadata("placement/availability-zone")
+    }
+
+    /// Region the instance runs in.
+    pub fn region(&mut self) -> Result<Region, AWSError> {
+        let document = try!(self.instance_identity_document());
+        match region_from_aws_format(&document.region) {
+            Some(region) => Ok(region),
+            None => Err(AWSError::new(format!("Unknown region {} in instance identity document", document.region))),
+        }
+    }
+
+    /// User data supplied at launch.  `None` if the instance has no user data.
+    pub fn user_data(&mut self) -> Result<Option<Vec<u8>>, AWSError> {
+        match try!(self.request_with_token(USER_DATA_PATH)) {
+            (StatusCode::Ok, body) => Ok(Some(body)),
+            (StatusCode::NotFound, _) => Ok(None),
+            (status, _) => Err(AWSError::new(format!("Metadata service returned {} for {}", status, USER_DATA_PATH))),
+        }
+    }
+
+    /// Fetches and parses the instance identity document.
+    pub fn instance_identity_document(&mut self) -> Result<InstanceIdentityDocument, AWSError> {
+        let body = try!(bytes_to_string(try!(self.get_path(IDENTITY_DOCUMENT_PATH))));
+        parse_instance_identity_document(&body)
+    }
+
+    /// Name of the IAM role attached through the instance profile.
+    pub fn iam_role_name(&mut self) -> Result<String, AWSError> {
+        let role_list = try!(self.get_metadata("iam/security-credentials/"));
+        match role_list.lines().next() {
+            Some(name) if !name.trim().is_empty() => Ok(name.trim().to_string()),
+            _ => Err(AWSError::new("No IAM role attached to this instance")),
+        }
+    }
+
+    /// JSON credentials document for the named IAM role.
+    pub fn iam_security_credentials(&mut self, role_name: &str) -> Result<String, AWSError> {
+        self.get_metadata(&format!("iam/security-credentials/{}", role_name))
+    }
+
+    fn get_path(&mut self, path: &str) -> Result<Vec<u8>, AWSError> {
+        match try!(self.request_with_token(path)) {
+            (StatusCode::Ok, body) => Ok(body),
+            (status, _) => Err(AWSError::new(format!("Metadata service returned {} for {}", status, path))),
+        }
+    }
+
+    fn request_with_token(&mut self, path: &str) -> Result<(StatusCode, Vec<u8>), AWSError> {
+        if env_flag_is_set("AWS_EC2_METADATA_DISABLED") {
+            return Err(AWSError::new("EC2 metadata service disabled by AWS_EC2_METADATA_DISABLED"));
+        }
+
+        let mut headers = Headers::new();
+        if let Some(token) = try!(self.get_session_token()) {
+            headers.set_raw("X-aws-ec2-metadata-token", vec![token.into_bytes()]);
+        }
+        self.metadata_request(Method::Get, path, headers)
+    }
+
+    /// Acquires an IMDSv2 session token, reusing the last one until it's close to expiring.
+    /// `None` means IMDSv1 should be used instead.
+    fn get_session_token(&mut self) -> Result<Option<String>, AWSError> {
+        if self.token.is_some() && self.token_expires_at > UTC::now() + Duration::seconds(60) {
+            return Ok(self.token.clone());
+        }
+
+        let mut headers = Headers::new();
+        headers.set_raw("X-aws-ec2-metadata-token-ttl-seconds", vec![METADATA_TOKEN_TTL_SECONDS.to_string().into_bytes()]);
+
+        let failure = match self.metadata_request(Method::Put, METADATA_TOKEN_PATH, headers) {
+            Ok((StatusCode::Ok, token)) => {
+                self.token = Some(try!(bytes_to_string(token)).trim().to_string());
+                self.token_expires_at = UTC::now() + Duration::seconds(METADATA_TOKEN_TTL_SECONDS);
+                return Ok(self.token.clone());
+            },
+            Ok((status, _)) => format!("metadata service refused session token request: {}", status),
+            Err(why) => why.message().to_string(),
+        };
+
+        if self.allow_v1_fallback {
+            Ok(None)
+        } else {
+            Err(AWSError::new(format!("Couldn't get IMDSv2 session token and IMDSv1 fallback is disabled: {}", failure)))
+        }
+    }
+
+    /// Sends a request to the metadata service with a short timeout, retrying connection
+    /// failures and server errors.
+    fn metadata_request(&self, method: Method, path: &str, headers: Headers) -> Result<(StatusCode, Vec<u8>), AWSError> {
+        let address = format!("{}{}", self.endpoint, path);
+        let mut client = Client::with_connector(TimeoutConnector { timeout: StdDuration::from_millis(METADATA_TIMEOUT_MILLISECONDS) });
+        client.set_read_timeout(Some(StdDuration::from_millis(METADATA_TIMEOUT_MILLISECONDS)));
+        client.set_write_timeout(Some(StdDuration::from_millis(METADATA_TIMEOUT_MILLISECONDS)));
+
+        let mut last_error = String::new();
+        for attempt in 0..METADATA_ATTEMPTS {
+            if attempt > 0 {
+                thread::sleep(StdDuration::from_millis(METADATA_RETRY_DELAY_MILLISECONDS * attempt as u64));
+            }
+
+            let mut response = match client.request(method.clone(), &address)
+                .headers(headers.clone())
+                .header(Connection::close()).send() {
+                    Err(why) => { last_error = format!("{}", why); continue; },
+                    Ok(received_response) => received_response
+                };
+
+            let mut body = Vec::new();
+            if let Err(why) = response.read_to_end(&mut body) {
+                last_error = format!("{}", why);
+                continue;
+            }
+
+            if response.status.is_server_error() {
+                last_error = format!("{}", response.status);
+                continue;
+            }
+
+            return Ok((response.status, body));
+        }
+
+        Err(AWSError::new(format!("Couldn't connect to metadata service at {}: {}", address, last_error)))
+    }
+}
+
+/// Opens plain HTTP connections, giving up on connecting after `timeout`.  Off EC2 nothing answers
+/// at 169.254.169.254, and waiting out the OS connect timeout on every attempt would stall callers
+/// for minutes.
+struct TimeoutConnector {
+    timeout: StdDuration,
+}
+
+impl NetworkConnector for TimeoutConnector {
+    type Stream = HttpStream;
+
+    fn connect(&self, host: &str, port: u16, scheme: &str) -> Result<HttpStream, HyperError> {
+        if scheme != "http" {
+            return Err(HyperError::from(io::Error::new(io::ErrorKind::InvalidInput, format!("Unsupported metadata service scheme {}", scheme))));
+        }
+        let mut last_error = io::Error::new(io::ErrorKind::NotFound, format!("No addresses found for {}", host));
+        for address in try!((host, port).to_socket_addrs()) {
+            match TcpStream::connect_timeout(&address, self.timeout) {
+                Ok(stream) => return Ok(HttpStream(stream)),
+                Err(why) => last_error = why,
+            }
+        }
+        Err(HyperError::from(last_error))
+    }
+}
+
+/// Looks up the region of the EC2 instance we're running on.
+///
+/// Handy for picking a `Region` at startup:
+///
+/// ```no_run
+/// use rusoto::metadata::detect_region;
+/// use rusoto::regions::Region;
+///
+/// let region = detect_region().unwrap_or(Region::UsEast1);
+/// ```
+pub fn detect_region() -> Result<Region, AWSError> {
+    InstanceMetadataClient::new().region()
+}
+
+fn parse_instance_identity_document(body: &str) -> Result<InstanceIdentityDocument, AWSError> {
+    let json_object = match Json::from_str(body) {
+        Err(_) => return Err(AWSError::new("Couldn't parse instance identity document.")),
+        Ok(val) => val
+    };
+
+    let field = |name: &str| -> String {
+        match json_object.find(name).and_then(|val| val.as_string()) {
+            Some(val) => val.to_string(),
+            None => String::new(),
+        }
+    };
+
+    let document = InstanceIdentityDocument {
+        account_id: field("accountId"),
+        architecture: field("architecture"),
+        availability_zone: field("availabilityZone"),
+        image_id: field("imageId"),
+        instance_id: field("instanceId"),
+        instance_type: field("instanceType"),
+        pending_time: field("pendingTime"),
+        private_ip: field("privateIp"),
+        region: field("region"),
+    };
+
+    if document.region.is_empty() || document.instance_id.is_empty() {
+        return Err(AWSError::new("Instance identity document is missing region or instanceId."));
+    }
+    Ok(document)
+}
+
+fn bytes_to_string(bytes: Vec<u8>) -> Result<String, AWSError> {
+    match String::from_utf8(bytes) {
+        Ok(val) => Ok(val),
+        Err(_) => Err(AWSError::new("Metadata response wasn't valid UTF-8")),
+    }
+}
+
+fn env_flag_is_set(name: &str) -> bool {
+    match var(name) {
+        Ok(val) => val.trim().to_ascii_lowercase() == "true",
+        Err(_) => false
+    }
+}
+
+/// Local stand-in for the metadata service, used by tests here and in the credentials module.
+#[cfg(test)]
+pub mod stub {
+    use std::ascii::AsciiExt;
+    use std::io::prelude::*;
+    use std::net::TcpListener;
+    use std::thread;
+
+    pub const IAM_ROLE_CREDENTIALS: &'static str = "{
+        \"Code\" : \"Success\",
+        \"LastUpdated\" : \"2015-10-09T12:29:35Z\",
+        \"Type\" : \"AWS-HMAC\",
+        \"AccessKeyId\" : \"ASIAEXAMPLEKEY\",
+        \"SecretAccessKey\" : \"examplesecret\",
+        \"Token\" : \"exampletoken\",
+        \"Expiration\" : \"2015-10-09T18:47:42Z\"
+    }";
+
+    pub const IDENTITY_DOCUMENT: &'static str = "{
+        \"privateIp\" : \"10.0.0.12\",
+        \"availabilityZone\" : \"us-west-2b\",
+        \"version\" : \"2010-08-31\",
+        \"instanceId\" : \"i-1234567890abcdef0\",
+        \"instanceType\" : \"m4.large\",
+        \"accountId\" : \"123456789012\",
+        \"imageId\" : \"ami-5fb8c835\",
+        \"pendingTime\" : \"2015-10-09T12:29:35Z\",
+        \"architecture\" : \"x86_64\",
+        \"region\" : \"us-west-2\"
+    }";
+
+    /// Serves enough of the instance metadata service for our tests.  With `supports_v2` the token
+    /// endpoint works and metadata requires the token, otherwise the token endpoint returns 404 like
+    /// an IMDSv1-only service.  Returns the endpoint to use.
+    pub fn metadata_stub_server(supports_v2: bool) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = match stream {
+                    Ok(s) => s,
+                    Err(_) => continue,
+                };
+
+                let mut request = Vec::new();
+                let mut buffer = [0u8; 1024];
+                while !String::from_utf8_lossy(&request).contains("\r\n\r\n") {
+                    match stream.read(&mut buffer) {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => request.extend(buffer[..n].iter().cloned()),
+                    }
+                }
+                let request = String::from_utf8_lossy(&request).to_ascii_lowercase();
+                let has_token = request.contains("x-aws-ec2-metadata-token: stubtoken");
+                let path = request.split(' ').nth(1).unwrap_or("").to_string();
+
+                let (status, body) = if request.starts_with("put ") && path == "/latest/api/token" {
+                    if supports_v2 { ("200 OK", "stubtoken") } else { ("404 Not Found", "") }
+                } else if supports_v2 && !has_token {
+                    ("401 Unauthorized", "")
+                } else {
+                    match path.as_ref() {
+                        "/latest/meta-data/instance-id" => ("200 OK", "i-1234567890abcdef0"),
+                        "/latest/meta-data/instance-type" => ("200 OK", "m4.large"),
+                        "/latest/meta-data/placement/availability-zone" => ("200 OK", "us-west-2b"),
+                        "/latest/meta-data/iam/security-credentials/" => ("200 OK", "stub-role"),
+                        "/latest/meta-data/iam/security-credentials/stub-role" => ("200 OK", IAM_ROLE_CREDENTIALS),
+                        "/latest/dynamic/instance-identity/document" => ("200 OK", IDENTITY_DOCUMENT),
+                        _ => ("404 Not Found", ""),
+                    }
+                };
+
+                let response = format!("HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status, body.len(), body);
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+
+        endpoint
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::stub::*;
+    use regions::*;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn parse_instance_identity_document_happy_path() {
+        let document = super::parse_instance_identity_document(IDENTITY_DOCUMENT).unwrap();
+        assert_eq!(document.account_id, "123456789012");
+        assert_eq!(document.availability_zone, "us-west-2b");
+        assert_eq!(document.instance_id, "i-1234567890abcdef0");
+        assert_eq!(document.region, "us-west-2");
+    }
+
+    #[test]
+    fn parse_instance_identity_document_missing_region() {
+        let result = super::parse_instance_identity_document("{\"instanceId\": \"i-1234567890abcdef0\"}");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn metadata_accessors() {
+        let mut client = InstanceMetadataClient::with_endpoint(&metadata_stub_server(true));
+        client.set_allow_v1_fallback(false);
+
+        assert_eq!(client.instance_id().unwrap(), "i-1234567890abcdef0");
+        assert_eq!(client.instance_type().unwrap(), "m4.large");
+        assert_eq!(client.availability_zone().unwrap(), "us-west-2b");
+        assert_eq!(client.region().unwrap(), Region::UsWest2);
+        assert_eq!(client.iam_role_name().unwrap(), "stub-role");
+    }
+
+    #[test]
+    fn missing_user_data_is_none() {
+        let mut client = InstanceMetadataClient::with_endpoint(&metadata_stub_server(true));
+        assert_eq!(client.user_data().unwrap(), None);
+    }
+
+    #[test]
+    fn v1_fallback_disabled() {
+        let mut client = InstanceMetadataClient::with_endpoint(&metadata_stub_server(false));
+        client.set_allow_v1_fallback(false);
+        assert!(client.instance_id().is_err());
+
+        client.set_allow_v1_fallback(true);
+        assert_eq!(client.instance_id().unwrap(), "i-1234567890abcdef0");
+    }
+
+    #[test]
+    fn unreachable_endpoint_fails_within_the_connect_timeout() {
+        // nothing routes to TEST-NET-1, so a connect either fails at once or hangs until timed out
+        let mut client = InstanceMetadataClient::with_endpoint("http://192.0.2.1");
+        client.set_allow_v1_fallback(false);
+
+        let started = Instant::now();
+        assert!(client.instance_id().is_err());
+        let allowed = (super::METADATA_TIMEOUT_MILLISECONDS + 2 * super::METADATA_RETRY_DELAY_MILLISECONDS) * super::METADATA_ATTEMPTS as u64;
+        assert!(started.elapsed() < Duration::from_millis(allowed + 2000));
+    }
+}
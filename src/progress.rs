@@ -0,0 +1,218 @@
+//! Progress reporting for S3 transfers
+//!
+//! Give an `S3Helper` or a `transfer::TransferManager` a `ProgressListener` to hear how far uploads
+//! and downloads have got.  Returning `ProgressAction::Cancel` from the listener stops the transfer,
+//! which then fails with an error of kind `AWSErrorKind::Cancelled`, see `is_cancelled`.
+//!
+//! Downloads report bytes as they arrive and can be cancelled at any point.  Upload bodies are
+//! handed to the HTTP client whole, so uploads report once per request or part, and a cancelled
+//! upload stops before its next part.
+//!
+//! ```
+//! use std::sync::Arc;
+//! use rusoto::progress::*;
+//!
+//! let listener: Arc<ProgressListener> = Arc::new(|event: &ProgressEvent| {
+//!     if let ProgressEvent::Bytes { transferred, total: Some(total) } = *event {
+//!         println!("{} of {} bytes", transferred, total);
+//!     }
+//!     ProgressAction::Continue
+//! });
+//! ```
+
+use std::io::{self, Read};
+use std::sync::{Arc, Mutex};
+use error::{AWSError, AWSErrorKind};
+
+const CANCELLED: &'static str = "Transfer cancelled";
+
+/// How far a transfer has got.  `transferred` counts every byte so far; `total` is the size of the
+/// transfer when it's known up front.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProgressEvent {
+	/// More of the object has been sent or received.
+	Bytes { transferred: u64, total: Option<u64> },
+	/// A part of a multipart upload, or a range of a download, has finished.
+	PartCompleted { part_number: i32, transferred: u64, total: Option<u64> },
+}
+
+/// What a listener wants done with the transfer it was told about.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProgressAction {
+	Continue,
+	Cancel,
+}
+
+/// Told about the progress of a transfer.  Parallel transfers call it from their worker threads,
+/// one event at a time.
+pub trait ProgressListener: Send + Sync {
+	fn on_progress(&self, event: &ProgressEvent) -> ProgressAction;
+}
+
+impl<F> ProgressListener for F where F: Fn(&ProgressEvent) -> ProgressAction + Send + Sync {
+	fn on_progress(&self, event: &ProgressEvent) -> ProgressAction {
+		self(event)
+	}
+}
+
+/// Whether an error is a transfer being cancelled by its `ProgressListener`.
+pub fn is_cancelled(error: &AWSError) -> bool {
+	*error.kind() == AWSErrorKind::Cancelled
+}
+
+fn cancelled() -> AWSError {
+	AWSError::with_kind(CANCELLED, AWSErrorKind::Cancelled)
+}
+
+/// Counts the bytes of one transfer and reports them to its listener.  Clones share the count, so
+/// the workers of a parallel transfer each get one.
+#[derive(Clone)]
+pub struct ProgressTracker {
+	listener: Option<Arc<ProgressListener>>,
+	total: Option<u64>,
+	state: Arc<Mutex<ProgressState>>,
+}
+
+struct ProgressState {
+	transferred: u64,
+	cancelled: bool,
+}
+
+impl ProgressTracker {
+	/// Tracker reporting to `listener`, if there is one.  Without a listener every call is a no-op.
+	pub fn new(listener: Option<Arc<ProgressListener>>, total: Option<u64>) -> ProgressTracker {
+		ProgressTracker {
+			listener: listener,
+			total: total,
+			state: Arc::new(Mutex::new(ProgressState { transferred: 0, cancelled: false })),
+		}
+	}
+
+	/// Reports the start of the transfer, with nothing transferred yet.
+	pub fn start(&self) -> Result<(), AWSError> {
+		self.report(0, None)
+	}
+
+	/// Reports `bytes` more transferred.
+	pub fn add_bytes(&self, bytes: u64) -> Result<(), AWSError> {
+		self.report(bytes, None)
+	}
+
+	/// Reports a finished part that brought `bytes` more.
+	pub fn part_completed(&self, part_number: i32, bytes: u64) -> Result<(), AWSError> {
+		self.report(bytes, Some(part_number))
+	}
+
+	/// Fails once the listener has cancelled the transfer.
+	pub fn check(&self) -> Result<(), AWSError> {
+		if self.listener.is_some() && self.state.lock().unwrap().cancelled {
+			return Err(cancelled());
+		}
+		Ok(())
+	}
+
+	fn report(&self, bytes: u64, part_number: Option<i32>) -> Result<(), AWSError> {
+		let listener = match self.listener {
+			Some(ref listener) => listener,
+			None => return Ok(()),
+		};
+		// Held while the listener runs, so it sees events one at a time and in order.
+		let mut state = self.state.lock().unwrap();
+		if state.cancelled {
+			return Err(cancelled());
+		}
+		state.transferred += bytes;
+
+		let event = match part_number {
+			Some(part_number) => ProgressEvent::PartCompleted { part_number: part_number, transferred: state.transferred, total: self.total },
+			None => ProgressEvent::Bytes { transferred: state.transferred, total: self.total },
+		};
+		if listener.on_progress(&event) == ProgressAction::Cancel {
+			state.cancelled = true;
+			return Err(cancelled());
+		}
+		Ok(())
+	}
+}
+
+/// Reports bytes to a tracker as they're read.  Reads fail once the transfer is cancelled.
+pub struct ProgressReader<R> {
+	inner: R,
+	tracker: ProgressTracker,
+}
+
+impl<R: Read> ProgressReader<R> {
+	pub fn new(inner: R, tracker: ProgressTracker) -> ProgressReader<R> {
+		ProgressReader { inner: inner, tracker: tracker }
+	}
+}
+
+impl<R: Read> Read for ProgressReader<R> {
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+		let read = try!(self.inner.read(buf));
+		if read > 0 {
+			if let Err(why) = self.tracker.add_bytes(read as u64) {
+				return Err(io::Error::new(io::ErrorKind::Other, why));
+			}
+		}
+		Ok(read)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::io::{Cursor, Read};
+	use error::AWSError;
+	use std::sync::{Arc, Mutex};
+
+	fn recording_listener(cancel_after: u64) -> (Arc<ProgressListener>, Arc<Mutex<Vec<ProgressEvent>>>) {
+		let events = Arc::new(Mutex::new(Vec::new()));
+		let recorded = events.clone();
+		let listener: Arc<ProgressListener> = Arc::new(move |event: &ProgressEvent| {
+			recorded.lock().unwrap().push(event.clone());
+			match *event {
+				ProgressEvent::Bytes { transferred, .. } if transferred >= cancel_after => ProgressAction::Cancel,
+				_ => ProgressAction::Continue,
+			}
+		});
+		(listener, events)
+	}
+
+	#[test]
+	fn tracker_reports_running_totals() {
+		let (listener, events) = recording_listener(1000);
+		let tracker = ProgressTracker::new(Some(listener), Some(100));
+
+		tracker.start().unwrap();
+		tracker.add_bytes(40).unwrap();
+		tracker.clone().part_completed(2, 60).unwrap();
+
+		assert_eq!(*events.lock().unwrap(), vec![
+			ProgressEvent::Bytes { transferred: 0, total: Some(100) },
+			ProgressEvent::Bytes { transferred: 40, total: Some(100) },
+			ProgressEvent::PartCompleted { part_number: 2, transferred: 100, total: Some(100) },
+		]);
+	}
+
+	#[test]
+	fn reader_fails_once_cancelled() {
+		let (listener, _) = recording_listener(4);
+		let tracker = ProgressTracker::new(Some(listener), None);
+		let mut reader = ProgressReader::new(Cursor::new(b"hello world".to_vec()), tracker.clone());
+
+		let mut buffer = [0u8; 4];
+		let error = reader.read(&mut buffer).unwrap_err();
+		assert_eq!(error.to_string(), "Transfer cancelled");
+		// still a cancellation once the read error is converted back
+		assert!(is_cancelled(&AWSError::from(error)));
+		assert!(is_cancelled(&tracker.check().unwrap_err()));
+	}
+
+	#[test]
+	fn tracker_without_listener_does_nothing() {
+		let tracker = ProgressTracker::new(None, None);
+		assert!(tracker.add_bytes(10).is_ok());
+		assert!(tracker.check().is_ok());
+	}
+}
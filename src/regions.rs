@@ -5,7 +5,7 @@
 //! EG: UsEast1 to "us-east-1"
 
 /// AWS Region
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Region {
     UsEast1,
     UsWest1,
@@ -33,6 +33,22 @@ pub fn region_in_aws_format(region: &Region) -> String {
     }
 }
 
+/// Translates an AWS format region name into the region enum.  EG: "us-east-1" to UsEast1
+pub fn region_from_aws_format(region: &str) -> Option<Region> {
+    match region {
+        "us-east-1" => Some(Region::UsEast1),
+        "us-west-1" => Some(Region::UsWest1),
+        "us-west-2" => Some(Region::UsWest2),
+        "eu-west-1" => Some(Region::EuWest1),
+        "eu-central-1" => Some(Region::EuCentral1),
+        "ap-southeast-1" => Some(Region::ApSoutheast1),
+        "ap-northeast-1" => Some(Region::ApNortheast1),
+        "ap-southeast-2" => Some(Region::ApSoutheast2),
+        "sa-east-1" => Some(Region::SaEast1),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -76,4 +92,12 @@ mod tests {
             panic!("Couldn't map sa-east-1 enum right.");
         }
     }
+
+    #[test]
+    fn aws_strings_correctly_map_to_regions() {
+        assert_eq!(region_from_aws_format("us-east-1"), Some(Region::UsEast1));
+        assert_eq!(region_from_aws_format("eu-central-1"), Some(Region::EuCentral1));
+        assert_eq!(region_from_aws_format("sa-east-1"), Some(Region::SaEast1));
+        assert_eq!(region_from_aws_format("mars-north-1"), None);
+    }
 }
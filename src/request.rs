@@ -2,21 +2,95 @@
 //!
 //! Wraps the Hyper library to send PUT, POST, DELETE and GET requests.
 //!
+//! Clients call `RequestHook`s before each request is sent and after its response arrives, which is
+//! the place to add logging, metrics or tracing.  By default clients carry a `LoggingHook`, which
+//! reports requests through the `log` crate.
+//!
 
+use std::ascii::AsciiExt;
+use std::collections::HashMap;
+use std::io::Read;
+use std::sync::Arc;
 use hyper::Client;
-use hyper::client::Response;
+use hyper::client::{Body, Response};
 use hyper::client::RedirectPolicy;
 use hyper::header::Headers;
 use hyper::method::Method;
-use signature::SignedRequest;
+use time::{Duration, precise_time_ns};
+use error::AWSError;
+use signature::{SignedRequest, printable_header_value};
+
+/// A request about to be sent to AWS.
+#[derive(Debug)]
+pub struct RequestEvent<'a> {
+    pub method: &'a str,
+    pub uri: &'a str,
+    /// Header names and values, with secrets such as the authorization header redacted.
+    pub headers: Vec<(String, String)>,
+    /// Starts at 1 and goes up each time the request is re-sent, EG: following an S3 redirect.
+    pub attempt: u32,
+}
+
+/// The response AWS returned for a request.
+#[derive(Debug)]
+pub struct ResponseEvent {
+    pub status: u16,
+    /// Time from sending the request until the response headers arrived.
+    pub latency: Duration,
+}
+
+/// Instrumentation called around every request a client sends.
+pub trait RequestHook: Send + Sync {
+    /// Called right before the request goes out.
+    fn before_send(&self, _request: &RequestEvent) {}
+
+    /// Called once the response status and headers have been received.
+    fn after_receive(&self, _request: &RequestEvent, _response: &ResponseEvent) {}
+}
+
+/// Hooks attached to a client, shared with the requests it sends.
+pub type RequestHooks = Vec<Arc<RequestHook>>;
+
+/// Reports requests through the `log` crate: requests and responses at debug level, server errors
+/// as warnings.
+pub struct LoggingHook;
+
+impl RequestHook for LoggingHook {
+    fn before_send(&self, request: &RequestEvent) {
+        debug!("Sending {} {} (attempt {})", request.method, request.uri, request.attempt);
+        for &(ref name, ref value) in request.headers.iter() {
+            trace!("    {}: {}", name, value);
+        }
+    }
+
+    fn after_receive(&self, request: &RequestEvent, response: &ResponseEvent) {
+        if response.status >= 500 {
+            warn!("{} {} returned {} after {}ms (attempt {})", request.method, request.uri,
+                response.status, response.latency.num_milliseconds(), request.attempt);
+        } else {
+            debug!("{} {} returned {} after {}ms (attempt {})", request.method, request.uri,
+                response.status, response.latency.num_milliseconds(), request.attempt);
+        }
+    }
+}
+
+/// The hooks clients start out with.
+pub fn default_hooks() -> RequestHooks {
+    let logging: Arc<RequestHook> = Arc::new(LoggingHook);
+    vec![logging]
+}
 
 /// Takes a fully formed and signed request and executes it.
-pub fn send_request(signed_request: &SignedRequest) -> Response {
+///
+/// A request that can't be sent, EG: because the host can't be reached, is an error.  Hooks only
+/// hear about the responses that come back.
+pub fn send_request(signed_request: &SignedRequest) -> Result<Response, AWSError> {
     let hyper_method = match signed_request.get_method().as_ref() {
         "POST" => Method::Post,
         "PUT" => Method::Put,
         "DELETE" => Method::Delete,
         "GET" => Method::Get,
+        "HEAD" => Method::Head,
         _ => Method::Get, // make this unreachable! ?
     };
 
@@ -26,22 +100,204 @@ pub fn send_request(signed_request: &SignedRequest) -> Response {
         hyper_headers.set_raw(h.0.to_owned(), h.1.to_owned());
     }
 
-    let mut final_uri = format!("https://{}{}", signed_request.get_hostname(), signed_request.get_canonical_uri());
+    let mut final_uri = format!("{}://{}{}", signed_request.get_scheme(), signed_request.get_hostname(), signed_request.get_canonical_uri());
     if signed_request.get_canonical_query_string().len() > 0 {
         final_uri = final_uri + &format!("?{}", signed_request.get_canonical_query_string());
     }
 
-    // println!("Full request: \n method: {}\n final_uri: {}\n payload: {:?}\nHeaders:\n",
-    // 	hyper_method, final_uri, signed_request.get_payload());
-    // for h in hyper_headers.iter() {
-    //     println!("{}:{}", h.name(), h.value_string());
-    // }
+    let hooks = signed_request.get_hooks();
+    let event = RequestEvent {
+        method: signed_request.get_method(),
+        uri: &final_uri,
+        headers: signed_request.get_headers().iter()
+            .map(|(name, values)| (name.to_string(), printable_header_value(name, values)))
+            .collect(),
+        attempt: signed_request.get_attempt(),
+    };
+    for hook in hooks.iter() {
+        hook.before_send(&event);
+    }
 
     let mut client = Client::new();
     client.set_redirect_policy(RedirectPolicy::FollowNone);
 
-    match signed_request.get_payload() {
-        None => client.request(hyper_method, &final_uri).headers(hyper_headers).body("").send().unwrap(),
-        Some(payload_contents) => client.request(hyper_method, &final_uri).headers(hyper_headers).body(payload_contents).send().unwrap(),
+    let started = precise_time_ns();
+    let result = match (signed_request.get_payload(), signed_request.get_payload_stream()) {
+        (Some(payload_contents), _) => client.request(hyper_method, &final_uri).headers(hyper_headers).body(payload_contents).send(),
+        (None, Some(stream)) => {
+            let mut reader = match stream.rewind() {
+                Ok(reader) => reader,
+                Err(why) => return Err(AWSError::new(format!("Couldn't rewind the body of {} {}: {}", event.method, final_uri, why))),
+            };
+            let mut body = (&mut **reader).take(stream.len());
+            client.request(hyper_method, &final_uri).headers(hyper_headers).body(Body::SizedBody(&mut body, stream.len())).send()
+        }
+        (None, None) => client.request(hyper_method, &final_uri).headers(hyper_headers).body("").send(),
+    };
+    let response = match result {
+        Ok(response) => response,
+        Err(why) => return Err(AWSError::new(format!("Couldn't send {} {}: {}", event.method, final_uri, why))),
+    };
+
+    let response_event = ResponseEvent {
+        status: response.status.to_u16(),
+        latency: Duration::nanoseconds((precise_time_ns() - started) as i64),
+    };
+    for hook in hooks.iter() {
+        hook.after_receive(&event, &response_event);
+    }
+
+    Ok(response)
+}
+
+/// Value of a response header, looked up case-insensitively.
+pub fn header_value(headers: &Headers, name: &str) -> Option<String> {
+    for header in headers.iter() {
+        if header.name().eq_ignore_ascii_case(name) {
+            return Some(header.value_string());
+        }
+    }
+    None
+}
+
+/// Response headers whose names start with `prefix`, keyed by the rest of the name in lower case.
+/// EG: S3 user metadata sent as `x-amz-meta-*`.
+pub fn headers_with_prefix(headers: &Headers, prefix: &str) -> HashMap<String, String> {
+    let prefix = prefix.to_ascii_lowercase();
+    let mut found = HashMap::new();
+    for header in headers.iter() {
+        let name = header.name().to_ascii_lowercase();
+        if name.starts_with(&prefix) && name.len() > prefix.len() {
+            found.insert(name[prefix.len()..].to_string(), header.value_string());
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate chrono;
+
+    use super::*;
+    use std::io::{self, Cursor, Read, Seek, SeekFrom};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use self::chrono::{Duration, UTC};
+    use hyper::header::Headers;
+    use credentials::AWSCredentials;
+    use metadata::stub::metadata_stub_server;
+    use regions::Region;
+    use signature::{PayloadStream, SignedRequest};
+
+    struct RecordingHook {
+        calls: Mutex<Vec<String>>,
+    }
+
+    impl RequestHook for RecordingHook {
+        fn before_send(&self, request: &RequestEvent) {
+            self.calls.lock().unwrap().push(format!("before {} {}", request.method, request.attempt));
+        }
+
+        fn after_receive(&self, request: &RequestEvent, response: &ResponseEvent) {
+            self.calls.lock().unwrap().push(format!("after {} {}", request.method, response.status));
+        }
+    }
+
+    #[test]
+    fn hooks_default_to_logging() {
+        assert_eq!(default_hooks().len(), 1);
+    }
+
+    fn recording_request<'a>(hook: &Arc<RecordingHook>, address: &str) -> SignedRequest<'a> {
+        let recording: Arc<RequestHook> = hook.clone();
+        let mut request = SignedRequest::new("GET", "ec2", &Region::UsEast1, "/latest/meta-data/instance-id");
+        request.set_scheme("http");
+        request.set_hostname(Some(address.to_string()));
+        request.set_hooks(&[recording]);
+        request
+    }
+
+    fn credentials() -> AWSCredentials {
+        AWSCredentials::new("key", "secret", None, UTC::now() + Duration::minutes(10))
+    }
+
+    #[test]
+    fn hook_receives_events() {
+        let endpoint = metadata_stub_server(false);
+        let hook = Arc::new(RecordingHook { calls: Mutex::new(Vec::new()) });
+        let mut request = recording_request(&hook, endpoint.trim_left_matches("http://"));
+
+        let response = request.sign_and_send(&credentials()).unwrap();
+
+        assert_eq!(response.status.to_u16(), 200);
+        assert_eq!(*hook.calls.lock().unwrap(), vec!["before GET 1".to_string(), "after GET 200".to_string()]);
+    }
+
+    #[test]
+    fn unreachable_host_is_an_error() {
+        // nothing listens on a port once its listener is dropped
+        let address = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().to_string();
+        let hook = Arc::new(RecordingHook { calls: Mutex::new(Vec::new()) });
+        let mut request = recording_request(&hook, &address);
+
+        assert!(request.sign_and_send(&credentials()).is_err());
+        assert_eq!(*hook.calls.lock().unwrap(), vec!["before GET 1".to_string()]);
+    }
+
+    /// A body whose reader can only seek `seeks` times, EG: a pipe.
+    struct UnseekableBody {
+        body: Cursor<Vec<u8>>,
+        seeks: u32,
+    }
+
+    impl Read for UnseekableBody {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.body.read(buf)
+        }
+    }
+
+    impl Seek for UnseekableBody {
+        fn seek(&mut self, position: SeekFrom) -> io::Result<u64> {
+            if self.seeks == 0 {
+                return Err(io::Error::new(io::ErrorKind::Other, "Illegal seek"));
+            }
+            self.seeks -= 1;
+            self.body.seek(position)
+        }
+    }
+
+    #[test]
+    fn body_that_cant_rewind_is_an_error() {
+        let endpoint = metadata_stub_server(false);
+        let hook = Arc::new(RecordingHook { calls: Mutex::new(Vec::new()) });
+        // hashing the body takes two seeks
+        let mut body = UnseekableBody { body: Cursor::new(b"hello".to_vec()), seeks: 2 };
+        let mut request = recording_request(&hook, endpoint.trim_left_matches("http://"));
+        request.set_payload_stream(PayloadStream::new(&mut body).unwrap());
+
+        let error = request.sign_and_send(&credentials()).unwrap_err();
+        assert!(error.message().starts_with("Couldn't rewind the body of GET"), "{:?}", error);
+    }
+
+    #[test]
+    fn header_lookup_ignores_case() {
+        let mut headers = Headers::new();
+        headers.set_raw("Content-Length", vec![b"3221225472".to_vec()]);
+
+        assert_eq!(header_value(&headers, "content-length"), Some("3221225472".to_string()));
+        assert_eq!(header_value(&headers, "ETag"), None);
+    }
+
+    #[test]
+    fn headers_with_prefix_strips_prefix() {
+        let mut headers = Headers::new();
+        headers.set_raw("x-amz-meta-Project", vec![b"rusoto".to_vec()]);
+        headers.set_raw("x-amz-meta-owner", vec![b"ops".to_vec()]);
+        headers.set_raw("x-amz-version-id", vec![b"3".to_vec()]);
+
+        let metadata = headers_with_prefix(&headers, "x-amz-meta-");
+        assert_eq!(metadata.len(), 2);
+        assert_eq!(metadata.get("project"), Some(&"rusoto".to_string()));
+        assert_eq!(metadata.get("owner"), Some(&"ops".to_string()));
     }
 }
@@ -5,6 +5,8 @@
 //!
 
 #![allow(unused_variables, unused_mut)]
+extern crate chrono;
+
 use credentials::*;
 use xml::*;
 use signature::*;
@@ -12,30 +14,58 @@ use params::*;
 use error::*;
 use xmlutil::*;
 use regions::*;
+use request::*;
+use progress::*;
+use listing::*;
+use checksum::{self, ChecksumAlgorithm, ChecksumReader};
+use upload::UploadOptions;
+use conditional::{self, Conditional, Conditions, ObjectMetadata};
+use util::zero_memory;
+use std::sync::Arc;
 use std::str::FromStr;
 use std::io::BufReader;
 use hyper::client::Response;
-use std::io::Read;
+use hyper::header::Headers;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use time::precise_time_ns;
 use std::ascii::AsciiExt;
+use std::fmt;
+use self::chrono::{DateTime, UTC};
+use std::cmp;
 use openssl::crypto::hash::Type::MD5;
 use openssl::crypto::hash::hash;
+use openssl::crypto::rand::rand_bytes;
 use serialize::base64::{ToBase64, STANDARD};
+use serialize::hex::ToHex;
 
 // include the code generated from the SQS botocore templates
 include!(concat!(env!("CARGO_MANIFEST_DIR"), "/codegen/s3.rs"));
 
-const CHUNK_TO_READ: usize = 5000;
 const S3_MINIMUM_PART_SIZE: usize = 5242880;
+/// How many times a request follows S3 to the region a bucket lives in before giving up.
+const MAXIMUM_REDIRECTS: u32 = 3;
+/// The largest object CopyObject copies in one request.
+const S3_MAXIMUM_COPY_SIZE: u64 = 5 * 1024 * 1024 * 1024;
+const S3_COPY_PART_SIZE: u64 = 512 * 1024 * 1024;
+const S3_MAXIMUM_PARTS: u64 = 10000;
+/// The most keys one DeleteObjects request deletes.
+const S3_MAXIMUM_DELETE_KEYS: usize = 1000;
+const METADATA_DIRECTIVE_REPLACE: &'static str = "REPLACE";
+const SSE_CUSTOMER_KEY_LENGTH: usize = 32;
 // need to sort this out, but having issues going declaring a String here, not a str.
 // static S3_REDUCED_REDUNDANCY: &'static str = "REDUCED_REDUNDANCY";
 
 /// Wraps the generated S3 client with a higher level interface
 pub struct S3Helper<'a> {
-	client: S3Client<'a>
+	client: S3Client<'a>,
+	progress: Option<Arc<ProgressListener>>,
+	checksum_algorithm: Option<ChecksumAlgorithm>,
 }
 
 /// Canned ACL for S3
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum CannedAcl {
     Private,
     PublicRead,
@@ -45,11 +75,703 @@ pub enum CannedAcl {
 	BucketOwnerFullControl,
 }
 
+/// An object being downloaded: its metadata plus its contents, read lazily from the response
+pub struct GetObjectStream {
+	/// Response headers for the object. `body` is left empty, read the contents from the stream instead.
+	pub output: GetObjectOutput,
+	body: Response,
+}
+
+impl Read for GetObjectStream {
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+		self.body.read(buf)
+	}
+}
+
+/// A customer-provided encryption key for SSE-C: S3 encrypts the object with it on upload and
+/// needs it again for every read.  S3 doesn't keep the key, so losing it loses the object.
+///
+/// `Debug` output leaves the key out, and the key is overwritten with zeros when dropped.
+pub struct CustomerEncryptionKey {
+	key: Vec<u8>,
+}
+
+impl CustomerEncryptionKey {
+	/// Wraps a 256-bit AES key.
+	pub fn new(key: &[u8]) -> Result<CustomerEncryptionKey, AWSError> {
+		if key.len() != SSE_CUSTOMER_KEY_LENGTH {
+			return Err(AWSError::new(format!("SSE-C keys are {} bytes long, not {}", SSE_CUSTOMER_KEY_LENGTH, key.len())));
+		}
+		Ok(CustomerEncryptionKey { key: key.to_vec() })
+	}
+
+	/// Generates a random key.
+	pub fn generate() -> CustomerEncryptionKey {
+		CustomerEncryptionKey { key: rand_bytes(SSE_CUSTOMER_KEY_LENGTH) }
+	}
+
+	pub fn key(&self) -> &[u8] {
+		&self.key
+	}
+
+	/// Value of the `x-amz-server-side-encryption-customer-algorithm` header
+	pub fn algorithm(&self) -> String {
+		"AES256".to_string()
+	}
+
+	/// Value of the `x-amz-server-side-encryption-customer-key` header: the key, base64 encoded
+	pub fn key_base64(&self) -> String {
+		self.key.to_base64(STANDARD)
+	}
+
+	/// Value of the `x-amz-server-side-encryption-customer-key-MD5` header: the base64 encoded MD5
+	/// of the key, which S3 uses to check the key arrived intact
+	pub fn key_md5(&self) -> String {
+		hash(MD5, &self.key).to_base64(STANDARD)
+	}
+}
+
+impl fmt::Debug for CustomerEncryptionKey {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "CustomerEncryptionKey {{ key_md5: {} }}", self.key_md5())
+	}
+}
+
+impl Drop for CustomerEncryptionKey {
+	fn drop(&mut self) {
+		zero_memory(&mut self.key);
+	}
+}
+
+/// Requests that can carry a customer-provided encryption key: the key the object is, or is to
+/// be, encrypted with.
+pub trait SseCustomerKeyFields {
+	/// Sets the algorithm, key and key MD5 fields.
+	fn set_sse_customer_key(&mut self, key: &CustomerEncryptionKey);
+}
+
+/// Copy requests, whose source object may be encrypted with a customer-provided key of its own.
+pub trait CopySourceSseCustomerKeyFields {
+	/// Sets the copy source algorithm, key and key MD5 fields.
+	fn set_copy_source_sse_customer_key(&mut self, key: &CustomerEncryptionKey);
+}
+
+/// How requests name the bucket they're for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AddressingStyle {
+	/// Virtual-hosted when the bucket name can be a host name under HTTPS, path-style otherwise.
+	/// Names with dots are sent path-style since they don't match S3's wildcard certificate.
+	Auto,
+	/// The bucket is part of the host name, EG: `https://my-bucket.s3.amazonaws.com/photo.jpg`
+	VirtualHosted,
+	/// The bucket is the first segment of the path, EG: `https://s3.amazonaws.com/my-bucket/photo.jpg`
+	PathStyle,
+}
+
+/// Which S3 endpoint requests go to, and how buckets are addressed there
+#[derive(Debug, Clone, PartialEq)]
+pub struct S3Endpoint {
+	pub addressing_style: AddressingStyle,
+	/// Use the dual-stack endpoints, reachable over IPv4 and IPv6
+	pub dualstack: bool,
+	/// Use S3 Transfer Acceleration.  The bucket must have acceleration enabled and a DNS compatible
+	/// name without dots, and is always addressed virtual-hosted.
+	pub accelerate: bool,
+}
+
+impl Default for S3Endpoint {
+	fn default() -> S3Endpoint {
+		S3Endpoint { addressing_style: AddressingStyle::Auto, dualstack: false, accelerate: false }
+	}
+}
+
+impl S3Endpoint {
+	/// Host serving buckets in `region`, before any bucket name is added to it.
+	pub fn hostname(&self, region: &Region) -> String {
+		match (self.accelerate, self.dualstack) {
+			(true, true) => "s3-accelerate.dualstack.amazonaws.com".to_string(),
+			(true, false) => "s3-accelerate.amazonaws.com".to_string(),
+			(false, true) => format!("s3.dualstack.{}.amazonaws.com", region_in_aws_format(region)),
+			(false, false) => match *region {
+				Region::UsEast1 => "s3.amazonaws.com".to_string(),
+				_ => format!("s3-{}.amazonaws.com", region_in_aws_format(region)),
+			},
+		}
+	}
+
+	/// Host for requests that aren't about a bucket, EG: ListBuckets.  Those can't be accelerated.
+	pub fn service_hostname(&self, region: &Region) -> String {
+		S3Endpoint { accelerate: false, ..self.clone() }.hostname(region)
+	}
+
+	/// Whether requests for `bucket` name it in the host rather than the path.
+	pub fn uses_virtual_host(&self, bucket: &str) -> Result<bool, AWSError> {
+		if self.accelerate {
+			if self.addressing_style == AddressingStyle::PathStyle {
+				return Err(AWSError::new("S3 Transfer Acceleration can't be used with path-style addressing"));
+			}
+			if !is_virtual_hostable_bucket_name(bucket) {
+				return Err(AWSError::new(format!("Bucket {} can't be used with S3 Transfer Acceleration: its name isn't DNS compatible or has dots", bucket)));
+			}
+			return Ok(true);
+		}
+		Ok(match self.addressing_style {
+			AddressingStyle::Auto => is_virtual_hostable_bucket_name(bucket),
+			AddressingStyle::VirtualHosted => true,
+			AddressingStyle::PathStyle => false,
+		})
+	}
+
+	/// Points a request at this endpoint.  The request's path must be path-style, "/{bucket}" or
+	/// "/{bucket}/{key}"; the bucket moves into the host name for virtual-hosted requests.
+	pub fn address(&self, request: &mut SignedRequest, bucket: &str) -> Result<(), AWSError> {
+		if try!(self.uses_virtual_host(bucket)) {
+			let path = virtual_host_path(request.get_path(), bucket);
+			request.set_path(&path);
+		}
+		let region = *request.get_region();
+		self.move_to_region(request, bucket, region)
+	}
+
+	/// Points an addressed request at the endpoint for `region`, and signs it for that region.
+	pub fn move_to_region(&self, request: &mut SignedRequest, bucket: &str, region: Region) -> Result<(), AWSError> {
+		let hostname = self.hostname(&region);
+		request.set_region(region);
+		if try!(self.uses_virtual_host(bucket)) {
+			request.set_hostname(Some(format!("{}.{}", bucket, hostname)));
+		} else {
+			request.set_hostname(Some(hostname));
+		}
+		Ok(())
+	}
+}
+
+impl<'a> S3Client<'a> {
+	/// The region `bucket` lives in.  S3 is asked the first time, and the answer is remembered.
+	pub fn get_bucket_region(&mut self, bucket: &str) -> Result<Region, AWSError> {
+		if let Some(region) = self.bucket_regions.get(bucket) {
+			return Ok(*region);
+		}
+		let region = try!(self.discover_bucket_region(bucket));
+		self.bucket_regions.insert(bucket.to_string(), region);
+		Ok(region)
+	}
+
+	/// Tells the client which region `bucket` lives in, so requests go straight there.
+	pub fn set_bucket_region(&mut self, bucket: &str, region: Region) {
+		self.bucket_regions.insert(bucket.to_string(), region);
+	}
+
+	/// Asks S3 where `bucket` lives: HeadBucket names the region in a header whether or not we may
+	/// read the bucket, GetBucketLocation is the fallback.  Both go to us-east-1, which answers for
+	/// buckets in every region.
+	fn discover_bucket_region(&mut self, bucket: &str) -> Result<Region, AWSError> {
+		let mut request = SignedRequest::new("HEAD", "s3", &Region::UsEast1, &bucket_path(bucket));
+		try!(self.endpoint.address(&mut request, bucket));
+		request.set_hooks(&self.hooks);
+		let response = try!(request.sign_and_send(try!(self.creds.get_credentials())));
+		if let Some(region) = try!(bucket_region_header(&response.headers)) {
+			return Ok(region);
+		}
+
+		let mut request = SignedRequest::new("GET", "s3", &Region::UsEast1, &bucket_path(bucket));
+		try!(self.endpoint.address(&mut request, bucket));
+		request.add_param("location", "");
+		request.set_hooks(&self.hooks);
+		let response = try!(request.sign_and_send(try!(self.creds.get_credentials())));
+		match response.status.to_u16() {
+			200...299 => {
+				let mut reader = EventReader::new(response);
+				let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+				stack.next(); // xml start tag
+				let output = try!(GetBucketLocationOutputParser::parse_xml("LocationConstraint", &mut stack));
+				bucket_region_from_location_constraint(&output.location_constraint)
+			}
+			status => Err(AWSError::new(format!("Couldn't find the region of bucket {}: GetBucketLocation returned {}", bucket, status))),
+		}
+	}
+
+	/// Signs and sends a request about `bucket`.  When S3 answers that the bucket lives in another
+	/// region (301 PermanentRedirect, 400 AuthorizationHeaderMalformed or 307 TemporaryRedirect),
+	/// the request is re-signed for that region and sent again, up to `MAXIMUM_REDIRECTS` times.  A
+	/// 307 within the right region, sent while a new bucket's DNS entry spreads, is followed to the
+	/// host it names.  A 304, 404 or 412 comes back as an error `conditional` recognises.
+	fn send(&mut self, request: &mut SignedRequest, bucket: &str) -> Result<Response, AWSError> {
+		if let Some(region) = self.bucket_regions.get(bucket).cloned() {
+			if region != *request.get_region() {
+				try!(self.endpoint.move_to_region(request, bucket, region));
+			}
+		}
+		let mut redirects = 0;
+		loop {
+			let response = try!(request.sign_and_send(try!(self.creds.get_credentials())));
+			let status = response.status.to_u16();
+			if redirects == MAXIMUM_REDIRECTS || !(status == 301 || status == 307 || status == 400) {
+				return status_result(response, request.get_path());
+			}
+
+			let region = match try!(bucket_region_header(&response.headers)) {
+				Some(region) => Some(region),
+				None if status == 301 => Some(try!(self.discover_bucket_region(bucket))),
+				None => None,
+			};
+			match region {
+				Some(region) if region != *request.get_region() => {
+					debug!("Bucket {} is in {}, resending the request there", bucket, region_in_aws_format(&region));
+					self.bucket_regions.insert(bucket.to_string(), region);
+					try!(self.endpoint.move_to_region(request, bucket, region));
+				}
+				_ if status == 307 => {
+					let hostname = try!(extract_s3_redirect_location(response));
+					if hostname.starts_with(&format!("{}.", bucket)) && !try!(self.endpoint.uses_virtual_host(bucket)) {
+						let path = virtual_host_path(request.get_path(), bucket);
+						request.set_path(&path);
+					}
+					request.set_hostname(Some(hostname));
+				}
+				_ => return status_result(response, request.get_path()),
+			}
+			redirects += 1;
+			request.next_attempt();
+		}
+	}
+
+	// The operations listed as handwritten in codegen/s3-customizations.json, which the generator
+	// leaves out, and their streaming variants.
+
+	/// Uploads a part in a multipart upload.
+	/// **Note:** After you initiate multipart upload and upload one or more parts, you must either complete or abort multipart upload in order to stop getting charged for storage of the uploaded parts. Only after you either complete or abort multipart upload, Amazon S3 frees up the parts storage and stops charging you for the parts storage.
+	pub fn upload_part(&mut self, input: &UploadPartRequest) -> Result<UploadPartOutput, AWSError> {
+		let mut request = SignedRequest::new("PUT", "s3", &self.region, &object_path(&input.bucket, &input.key));
+		try!(self.endpoint.address(&mut request, &input.bucket));
+		request.set_payload(input.body);
+		add_upload_part_headers(&mut request, input);
+
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
+		upload_part_result(result, &input.key)
+	}
+
+	/// Uploads a part whose body is streamed from `body` rather than `input.body`.
+	/// `Content-MD5` is sent from the body's digest unless `input.content_md5` is set.
+	pub fn upload_part_stream<'p>(&mut self, input: &UploadPartRequest, body: PayloadStream<'p>) -> Result<UploadPartOutput, AWSError> where 'a: 'p {
+		let mut request = SignedRequest::new("PUT", "s3", self.region, &object_path(&input.bucket, &input.key));
+		try!(self.endpoint.address(&mut request, &input.bucket));
+		if input.content_md5.is_none() {
+			request.add_header("Content-MD5", &body.content_md5());
+		}
+		request.set_payload_stream(body);
+		add_upload_part_headers(&mut request, input);
+
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
+		upload_part_result(result, &input.key)
+	}
+
+	/// Adds an object to a bucket.
+	pub fn put_object(&mut self, input: &PutObjectRequest) -> Result<PutObjectOutput, AWSError> {
+		let uri = object_path(&input.bucket, &input.key);
+		let mut request = SignedRequest::new("PUT", "s3", &self.region, &uri);
+		try!(add_put_object_headers(&mut request, input));
+
+		try!(self.endpoint.address(&mut request, &input.bucket));
+		request.set_payload(input.body);
+
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
+		put_object_result(result, &input.key)
+	}
+
+	/// Uploads an object whose body is streamed from `body` rather than `input.body`.
+	/// `Content-MD5` is sent from the body's digest unless `input.content_md5` is set.
+	pub fn put_object_stream<'p>(&mut self, input: &PutObjectRequest, body: PayloadStream<'p>) -> Result<PutObjectOutput, AWSError> where 'a: 'p {
+		let uri = object_path(&input.bucket, &input.key);
+		let mut request = SignedRequest::new("PUT", "s3", self.region, &uri);
+		try!(add_put_object_headers(&mut request, input));
+		if input.content_md5.is_none() {
+			request.add_header("Content-MD5", &body.content_md5());
+		}
+
+		try!(self.endpoint.address(&mut request, &input.bucket));
+		request.set_payload_stream(body);
+
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
+		put_object_result(result, &input.key)
+	}
+
+	/// Creates a new bucket.
+	/// All requests go to the us-east-1/us-standard endpoint, but can create buckets anywhere.
+	pub fn create_bucket(&mut self, input: &CreateBucketRequest) -> Result<CreateBucketOutput, AWSError> {
+		let region = Region::UsEast1;
+		let mut create_config : Vec<u8>;
+		let mut request = SignedRequest::new("PUT", "s3", &region, &bucket_path(&input.bucket));
+		try!(self.endpoint.address(&mut request, &input.bucket));
+
+		if needs_create_bucket_config(&self.region) {
+			create_config = create_bucket_config_xml(&self.region);
+			request.set_payload(Some(&create_config));
+		}
+
+		match input.acl {
+			None => (),
+			Some(ref canned_acl) => request.add_header("x-amz-acl", &canned_acl_in_aws_format(&canned_acl)),
+		}
+
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
+		let status = result.status.to_u16();
+
+		match status {
+			200 => {
+				let mut create_result = CreateBucketOutput::default();
+				try!(CreateBucketOutputParser::parse_headers(&result.headers, &mut create_result));
+				Ok(create_result)
+			}
+			_ => {
+				Err(AWSError::new("error in create_bucket"))
+			}
+		}
+	}
+
+	/// Completes a multipart upload by assembling previously uploaded parts.
+	pub fn complete_multipart_upload(&mut self, input: &CompleteMultipartUploadRequest) -> Result<CompleteMultipartUploadOutput, AWSError> {
+		let mut request = SignedRequest::new("POST", "s3", &self.region, &object_path(&input.bucket, &input.key));
+
+		let mut params = Params::new();
+		params.put("uploadId", &input.upload_id.to_string());
+		request.set_params(params);
+
+		try!(self.endpoint.address(&mut request, &input.bucket));
+
+		request.set_payload(input.multipart_upload);
+
+		request.set_hooks(&self.hooks);
+		let mut result = try!(self.send(&mut request, &input.bucket));
+		let status = result.status.to_u16();
+
+		match status {
+			200 => {
+				let headers = result.headers.clone();
+				let mut reader = EventReader::new(result);
+				let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+				stack.next(); // xml start tag
+
+				let mut complete_result = try!(CompleteMultipartUploadOutputParser::parse_xml("CompleteMultipartUploadResult", &mut stack));
+				try!(CompleteMultipartUploadOutputParser::parse_headers(&headers, &mut complete_result));
+				Ok(complete_result)
+			}
+			_ => {
+				let mut body = String::new();
+			    result.read_to_string(&mut body).unwrap();
+				Err(AWSError::new("error in complete_multipart_upload"))
+			}
+		}
+	}
+
+	/// Deletes the bucket. All objects (including all object versions and Delete
+	/// Markers) in the bucket must be deleted before the bucket itself can be
+	/// deleted.
+	pub fn delete_bucket(&mut self, input: &DeleteBucketRequest, region: &Region) -> Result<(), AWSError> {
+		let mut request = SignedRequest::new("DELETE", "s3", region, &bucket_path(&input.bucket));
+
+		try!(self.endpoint.address(&mut request, &input.bucket));
+
+		request.set_hooks(&self.hooks);
+		let mut result = try!(self.send(&mut request, &input.bucket));
+		let status = result.status.to_u16();
+		match status {
+			204 => {
+				Ok(())
+			}
+			_ => {
+				let mut body = String::new();
+			    result.read_to_string(&mut body).unwrap();
+				debug!("Response body: {}", body);
+				Err(AWSError::new(format!("delete bucket error, status was {}", status)))
+			}
+		}
+	}
+
+	/// This operation lists in-progress multipart uploads.
+	pub fn list_multipart_uploads(&mut self, input: &ListMultipartUploadsRequest) -> Result<ListMultipartUploadsOutput, AWSError> {
+		let mut request = SignedRequest::new("GET", "s3", &self.region, &bucket_path(&input.bucket));
+
+		let mut params = Params::new();
+		params.put("uploads", "");
+		if let Some(ref delimiter) = input.delimiter {
+			params.put("delimiter", delimiter);
+		}
+		if let Some(ref encoding_type) = input.encoding_type {
+			params.put("encoding-type", encoding_type);
+		}
+		if let Some(ref key_marker) = input.key_marker {
+			params.put("key-marker", key_marker);
+		}
+		if let Some(ref max_uploads) = input.max_uploads {
+			params.put("max-uploads", &max_uploads.to_string());
+		}
+		if let Some(ref prefix) = input.prefix {
+			params.put("prefix", prefix);
+		}
+		if let Some(ref upload_id_marker) = input.upload_id_marker {
+			params.put("upload-id-marker", upload_id_marker);
+		}
+		request.set_params(params);
+
+		try!(self.endpoint.address(&mut request, &input.bucket));
+
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
+		let status = result.status.to_u16();
+		let mut reader = EventReader::new(result);
+		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+		stack.next(); // xml start tag
+
+		match status {
+			200 => {
+				Ok(try!(ListMultipartUploadsOutputParser::parse_xml("ListMultipartUploadsResult", &mut stack)))
+			}
+			_ => {
+				Err(AWSError::new("error"))
+			}
+		}
+	}
+
+	/// Aborts a multipart upload.
+	/// To verify that all parts have been removed, so you don't get charged for the
+	/// part storage, you should call the List Parts operation and ensure the parts
+	/// list is empty.
+	pub fn abort_multipart_upload(&mut self, input: &AbortMultipartUploadRequest) -> Result<AbortMultipartUploadOutput, AWSError> {
+		let mut request = SignedRequest::new("DELETE", "s3", &self.region, &object_path(&input.bucket, &input.key));
+
+		let mut params = Params::new();
+		params.put("uploadId", &input.upload_id.to_string());
+		request.set_params(params);
+
+		try!(self.endpoint.address(&mut request, &input.bucket));
+
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
+		let status = result.status.to_u16();
+		let mut reader = EventReader::new(result);
+		let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+		stack.next(); // xml start tag
+
+		match status {
+			204 => {
+				Ok(AbortMultipartUploadOutput::default())
+			}
+			_ => { Err(AWSError::new(format!("error, got return code {}", status))) }
+		}
+	}
+
+	/// Lists the parts that have been uploaded for a specific multipart upload.
+	pub fn list_parts(&mut self, input: &ListPartsRequest) -> Result<ListPartsOutput, AWSError> {
+		let mut request = SignedRequest::new("GET", "s3", &self.region, &object_path(&input.bucket, &input.key));
+
+		let mut params = Params::new();
+		params.put("uploadId", &input.upload_id.to_string());
+		if let Some(ref max_parts) = input.max_parts {
+			params.put("max-parts", &max_parts.to_string());
+		}
+		if let Some(ref marker) = input.part_number_marker {
+			params.put("part-number-marker", &marker.to_string());
+		}
+		request.set_params(params);
+
+		try!(self.endpoint.address(&mut request, &input.bucket));
+
+		request.set_hooks(&self.hooks);
+		let mut result = try!(self.send(&mut request, &input.bucket));
+		let status = result.status.to_u16();
+
+		match status {
+			200 => {
+				let headers = result.headers.clone();
+				let mut reader = EventReader::new(result);
+				let mut stack = XmlResponseFromAws::new(reader.events().peekable());
+				stack.next(); // xml start tag
+
+				let mut list_result = try!(ListPartsOutputParser::parse_xml("ListPartsResult", &mut stack));
+				try!(ListPartsOutputParser::parse_headers(&headers, &mut list_result));
+				Ok(list_result)
+			}
+			_ => {
+				let mut body = String::new();
+			    result.read_to_string(&mut body).unwrap();
+			    debug!("Error response body: {}", body);
+
+				Err(AWSError::new("error in list_parts"))
+			}
+		}
+	}
+
+	/// Removes the null version (if there is one) of an object and inserts a delete
+	/// marker, which becomes the latest version of the object. If there isn't a null
+	/// version, Amazon S3 does not remove any objects.
+	pub fn delete_object(&mut self, input: &DeleteObjectRequest) -> Result<DeleteObjectOutput, AWSError> {
+		let uri = object_path(&input.bucket, &input.key);
+		let mut request = SignedRequest::new("DELETE", "s3", &self.region, &uri);
+		let mut params = Params::new();
+
+		try!(self.endpoint.address(&mut request, &input.bucket));
+
+		params.put("Action", "DeleteObject");
+		DeleteObjectRequestWriter::write_params(&mut params, "", &input);
+		request.set_params(params);
+		request.set_hooks(&self.hooks);
+		let result = try!(self.send(&mut request, &input.bucket));
+		let status = result.status.to_u16();
+
+		match status {
+			204 => {
+				let mut delete_result = DeleteObjectOutput::default();
+				try!(DeleteObjectOutputParser::parse_headers(&result.headers, &mut delete_result));
+				Ok(delete_result)
+			}
+			_ => { Err(AWSError::new("delete object error")) }
+		}
+	}
+
+	/// Retrieves an object, reading its whole body into `GetObjectOutput::body`.
+	pub fn get_object(&mut self, input: &GetObjectRequest) -> Result<GetObjectOutput, AWSError> {
+		let mut stream = try!(self.get_object_stream(input));
+		let mut s3_object = stream.output;
+		if let Err(why) = stream.body.read_to_end(&mut s3_object.body) {
+			return Err(AWSError::new(format!("Couldn't read response body: {}", why)));
+		}
+		Ok(s3_object)
+	}
+
+	/// Retrieves an object without buffering it: the returned `GetObjectStream` holds the
+	/// parsed response headers and reads the object body straight off the connection.
+	pub fn get_object_stream(&mut self, input: &GetObjectRequest) -> Result<GetObjectStream, AWSError> {
+		let uri = object_path(&input.bucket, &input.key);
+		let mut request = SignedRequest::new("GET", "s3", &self.region, &uri);
+		let mut params = Params::new();
+
+		try!(self.endpoint.address(&mut request, &input.bucket));
+
+		if let Some(ref obj) = input.if_match {
+			request.add_header("If-Match", obj);
+		}
+		if let Some(ref obj) = input.if_modified_since {
+			request.add_header("If-Modified-Since", obj);
+		}
+		if let Some(ref obj) = input.if_none_match {
+			request.add_header("If-None-Match", obj);
+		}
+		if let Some(ref obj) = input.if_unmodified_since {
+			request.add_header("If-Unmodified-Since", obj);
+		}
+		if let Some(ref obj) = input.range {
+			request.add_header("Range", obj);
+		}
+		if let Some(ref obj) = input.sse_customer_algorithm {
+			request.add_header("x-amz-server-side-encryption-customer-algorithm", obj);
+		}
+		if let Some(ref obj) = input.sse_customer_key {
+			request.add_header("x-amz-server-side-encryption-customer-key", obj);
+		}
+		if let Some(ref obj) = input.sse_customer_key_md5 {
+			request.add_header("x-amz-server-side-encryption-customer-key-MD5", obj);
+		}
+		if let Some(ref obj) = input.request_payer {
+			request.add_header("x-amz-request-payer", obj);
+		}
+		if let Some(ref obj) = input.checksum_mode {
+			request.add_header("x-amz-checksum-mode", obj);
+		}
+		if let Some(ref obj) = input.version_id {
+			params.put("versionId", obj);
+		}
+		if let Some(ref obj) = input.response_cache_control {
+			params.put("response-cache-control", obj);
+		}
+		if let Some(ref obj) = input.response_content_disposition {
+			params.put("response-content-disposition", obj);
+		}
+		if let Some(ref obj) = input.response_content_encoding {
+			params.put("response-content-encoding", obj);
+		}
+		if let Some(ref obj) = input.response_content_language {
+			params.put("response-content-language", obj);
+		}
+		if let Some(ref obj) = input.response_content_type {
+			params.put("response-content-type", obj);
+		}
+		if let Some(ref obj) = input.response_expires {
+			params.put("response-expires", obj);
+		}
+
+		request.set_params(params);
+		request.set_hooks(&self.hooks);
+		let mut result = try!(self.send(&mut request, &input.bucket));
+		let status = result.status.to_u16();
+
+		match status {
+			200...299 => {
+				let mut s3_object = GetObjectOutput::default();
+				try!(GetObjectOutputParser::parse_headers(&result.headers, &mut s3_object));
+				Ok(GetObjectStream { output: s3_object, body: result })
+			}
+			_ => {
+				warn!("Error: Status code was {}", status);
+				let mut body = String::new();
+			    result.read_to_string(&mut body).unwrap();
+			    debug!("Error response body: {}", body);
+				Err(AWSError::new("error in get_object"))
+			}
+		}
+	}
+}
+
 impl<'a> S3Helper<'a> {
 
 	/// Creates a new S3 helper
 	pub fn new<CP: AWSCredentialsProvider + 'a>(credentials: CP, region:&'a Region) -> S3Helper<'a> {
-		S3Helper { client: S3Client::new(credentials, region) }
+		S3Helper { client: S3Client::new(credentials, region), progress: None, checksum_algorithm: None }
+	}
+
+	/// Sends a checksum with single part uploads, and asks S3 for the checksums of downloads.
+	///
+	/// Uploads are always sent with `Content-MD5` and checked against their ETag, and downloads
+	/// are checked against their ETag when it's an MD5.  A checksum also covers objects encrypted
+	/// with KMS or customer-provided keys, whose ETags aren't MD5s.
+	pub fn set_checksum_algorithm(&mut self, algorithm: Option<ChecksumAlgorithm>) {
+		self.checksum_algorithm = algorithm;
+	}
+
+	/// Reports the progress of uploads and downloads to `listener`, which can also cancel them.
+	pub fn set_progress_listener(&mut self, listener: Arc<ProgressListener>) {
+		self.progress = Some(listener);
+	}
+
+	fn progress_tracker(&self, total: Option<u64>) -> ProgressTracker {
+		ProgressTracker::new(self.progress.clone(), total)
+	}
+
+	/// Chooses between virtual-hosted and path-style requests.  `Auto`, the default, uses
+	/// path-style only for bucket names that can't be host names.
+	pub fn set_addressing_style(&mut self, addressing_style: AddressingStyle) {
+		self.client.set_addressing_style(addressing_style);
+	}
+
+	/// Sets the endpoint options, EG: dualstack or S3 Transfer Acceleration, all at once.
+	pub fn set_endpoint(&mut self, endpoint: S3Endpoint) {
+		self.client.set_endpoint(endpoint);
+	}
+
+	/// Adds a hook called around every request the helper sends, EG: for metrics or tracing
+	pub fn add_request_hook(&mut self, hook: Arc<RequestHook>) {
+		self.client.add_hook(hook);
+	}
+
+	/// The region a bucket lives in.  Requests follow buckets to their region on their own; this
+	/// is for when the region itself is wanted.
+	pub fn get_bucket_region(&mut self, bucket_name: &str) -> Result<Region, AWSError> {
+		self.client.get_bucket_region(bucket_name)
 	}
 
 	/// Lists buckets
@@ -84,7 +806,7 @@ impl<'a> S3Helper<'a> {
 		result
 	}
 
-	/// Deletes specified bucket
+	/// Deletes specified bucket, which must be empty: see `empty_bucket`.
 	pub fn delete_bucket(&mut self, bucket_name: &str, region: &Region) -> Result<(), AWSError> {
 		let mut request = DeleteBucketRequest::default();
 		request.bucket = bucket_name.to_string();
@@ -94,11 +816,121 @@ impl<'a> S3Helper<'a> {
 
 	/// Download a named object from bucket
 	pub fn get_object(&mut self, bucket_name: &str, object_name: &str) ->  Result<GetObjectOutput, AWSError> {
+		let request = self.get_object_request(bucket_name, object_name);
+		self.get_object_with_request(&request)
+	}
+
+	/// Download an object stored encrypted with a customer-provided key.
+	pub fn get_object_with_customer_key(&mut self, bucket_name: &str, object_name: &str,
+		key: &CustomerEncryptionKey) ->  Result<GetObjectOutput, AWSError> {
+
+		let mut request = self.get_object_request(bucket_name, object_name);
+		request.set_sse_customer_key(key);
+		self.get_object_with_request(&request)
+	}
+
+	/// Downloads an object: the caller specifies the whole request.
+	///
+	/// Whole objects are checked against their checksum or ETag, see `verify_download`.
+	pub fn get_object_with_request(&mut self, request: &GetObjectRequest) ->  Result<GetObjectOutput, AWSError> {
+		let mut stream = try!(self.client.get_object_stream(request));
+		let tracker = self.progress_tracker(Some(stream.output.content_length as u64));
+		try!(tracker.start());
+
+		let algorithm = download_checksum(&stream.output).map(|(algorithm, _)| algorithm);
+		let mut body = Vec::new();
+		let (md5_hex, checksum) = {
+			let mut reader = ChecksumReader::new(ProgressReader::new(&mut stream.body, tracker), algorithm);
+			try!(reader.read_to_end(&mut body));
+			(reader.md5_hex(), reader.checksum())
+		};
+		try!(verify_download(&request.key, &stream.output, &md5_hex, checksum.as_ref().map(|checksum| &checksum[..])));
+		let mut output = stream.output;
+		output.body = body;
+		Ok(output)
+	}
+
+	/// Downloads an object only if `conditions` are met, EG: `Conditions::if_none_match(e_tag)` to
+	/// download it only if it has changed since it was last fetched.
+	pub fn get_object_if(&mut self, bucket_name: &str, object_name: &str,
+		conditions: &Conditions) -> Result<Conditional<GetObjectOutput>, AWSError> {
+
+		let mut request = self.get_object_request(bucket_name, object_name);
+		conditions.apply_to_get_object(&mut request);
+		Conditional::from_result(self.get_object_with_request(&request))
+	}
+
+	/// Looks up an object's size, last modified date, ETag and metadata without downloading it.
+	pub fn head_object(&mut self, bucket_name: &str, object_name: &str) -> Result<ObjectMetadata, AWSError> {
+		let mut request = HeadObjectRequest::default();
+		request.key = object_name.to_string();
+		request.bucket = bucket_name.to_string();
+		let output = try!(self.client.head_object(&request));
+		ObjectMetadata::from_head(output)
+	}
+
+	/// Whether there is an object named `object_name` in the bucket.
+	///
+	/// Without permission to list the bucket S3 reports a missing object as access denied, which is
+	/// an error rather than `false`.
+	pub fn object_exists(&mut self, bucket_name: &str, object_name: &str) -> Result<bool, AWSError> {
+		match self.head_object(bucket_name, object_name) {
+			Ok(_) => Ok(true),
+			Err(ref why) if conditional::is_not_found(why) => Ok(false),
+			Err(why) => Err(why),
+		}
+	}
+
+	fn get_object_request(&self, bucket_name: &str, object_name: &str) -> GetObjectRequest {
 		let mut request = GetObjectRequest::default();
 		request.key = object_name.to_string();
 		request.bucket = bucket_name.to_string();
-		let result = self.client.get_object(&request);
-		result
+		if self.checksum_algorithm.is_some() {
+			request.checksum_mode = Some("ENABLED".to_string());
+		}
+		request
+	}
+
+	/// Download a named object from bucket as a stream, without buffering it in memory.
+	///
+	/// The stream isn't checked: read it through a `checksum::ChecksumReader` and call
+	/// `verify_download` at the end for that.
+	pub fn get_object_stream(&mut self, bucket_name: &str, object_name: &str) ->  Result<GetObjectStream, AWSError> {
+		let request = self.get_object_request(bucket_name, object_name);
+		self.client.get_object_stream(&request)
+	}
+
+	/// Download a named object from bucket straight to a file.
+	///
+	/// The object is streamed into a temporary file next to `path`, which is renamed over `path`
+	/// only once the whole object has been written and checked, so `path` never holds a partial or
+	/// corrupt download.
+	pub fn download_to_file(&mut self, bucket_name: &str, object_name: &str, path: &Path) ->  Result<GetObjectOutput, AWSError> {
+		let mut stream = try!(self.get_object_stream(bucket_name, object_name));
+		let content_length = stream.output.content_length;
+		let tracker = self.progress_tracker(Some(content_length as u64));
+		try!(tracker.start());
+		let temp_path = download_temp_path(path);
+
+		let algorithm = download_checksum(&stream.output).map(|(algorithm, _)| algorithm);
+		let written = {
+			let mut reader = ChecksumReader::new(ProgressReader::new(&mut stream.body, tracker), algorithm);
+			let written = write_stream_to_file(&mut reader, &temp_path)
+				.and_then(|written| check_download_length(written, content_length));
+			written.map(|_| (reader.md5_hex(), reader.checksum()))
+		};
+		let result = written
+			.and_then(|(md5_hex, checksum)| verify_download(object_name, &stream.output, &md5_hex,
+				checksum.as_ref().map(|checksum| &checksum[..])))
+			.and_then(|_| fs::rename(&temp_path, path).map_err(AWSError::from));
+
+		match result {
+			Ok(_) => Ok(stream.output),
+			Err(why) => {
+				let _ = fs::remove_file(&temp_path);
+				Err(why)
+			}
+		}
 	}
 
 	/// Upload an object to specified bucket
@@ -106,6 +938,59 @@ impl<'a> S3Helper<'a> {
 		self.put_object_with_optional_reduced_redundancy(bucket_name, object_name, object_as_bytes, false)
 	}
 
+	/// Upload an object to specified bucket with the content type, metadata and other headers in
+	/// `options`.  A content type is guessed from the object name's extension unless one is set.
+	pub fn put_object_with_options(&mut self, bucket_name: &str, object_name: &str, object_as_bytes: &Vec<u8>,
+		options: &UploadOptions) ->  Result<PutObjectOutput, AWSError> {
+
+		let mut request = PutObjectRequest::default();
+		request.key = object_name.to_string();
+		request.bucket = bucket_name.to_string();
+		request.body = Some(object_as_bytes);
+		options.apply_to_put_object(&mut request, Path::new(object_name));
+		self.put_object_with_request(&mut request)
+	}
+
+	/// Upload a file to specified bucket, streaming it from disk instead of loading it into memory.
+	/// Its content type is guessed from the file's extension.
+	///
+	/// The file is sent in a single PUT, so it must be smaller than S3's 5 GB object size limit.
+	pub fn upload_file(&mut self, bucket_name: &str, object_name: &str, path: &Path) ->  Result<PutObjectOutput, AWSError> {
+		self.upload_file_with_options(bucket_name, object_name, path, &UploadOptions::new())
+	}
+
+	/// Upload a file to specified bucket with the content type, metadata and other headers in
+	/// `options`, streaming it from disk like `upload_file`.
+	pub fn upload_file_with_options(&mut self, bucket_name: &str, object_name: &str, path: &Path,
+		options: &UploadOptions) ->  Result<PutObjectOutput, AWSError> {
+
+		let mut file = try!(File::open(path));
+		let mut request = PutObjectRequest::default();
+		request.key = object_name.to_string();
+		request.bucket = bucket_name.to_string();
+		options.apply_to_put_object(&mut request, path);
+		if let Some(algorithm) = self.checksum_algorithm {
+			let checksum = {
+				let mut reader = ChecksumReader::new(&mut file, Some(algorithm));
+				try!(io::copy(&mut reader, &mut io::sink()));
+				reader.checksum().unwrap()
+			};
+			try!(file.seek(SeekFrom::Start(0)));
+			add_checksum(&mut request, algorithm, checksum);
+		}
+		let body = try!(PayloadStream::new(&mut file));
+
+		let length = body.len();
+		let md5_hex = body.md5_hex();
+		let tracker = self.progress_tracker(Some(length));
+		try!(tracker.start());
+		let output = try!(self.client.put_object_stream(&request, body));
+		// the upload is done, too late to cancel it
+		let _ = tracker.add_bytes(length);
+		try!(verify_upload(object_name, &output, &md5_hex));
+		Ok(output)
+	}
+
 	/// Helper: uploads object to specified bucket using reduced redudancy storage settings
 	pub fn put_object_with_reduced_redundancy(&mut self, bucket_name: &str, object_name: &str, object_as_bytes: &Vec<u8>) ->  Result<PutObjectOutput, AWSError> {
 		self.put_object_with_optional_reduced_redundancy(bucket_name, object_name, object_as_bytes, true)
@@ -139,6 +1024,21 @@ impl<'a> S3Helper<'a> {
 		result
 	}
 
+	/// Uploads object to specified S3 bucket, encrypted at rest with a customer-provided key.  The
+	/// same key is needed to download it.
+	pub fn put_object_with_customer_key(&mut self, bucket_name: &str, object_name: &str,
+		object_as_bytes: &Vec<u8>, key: &CustomerEncryptionKey) ->  Result<PutObjectOutput, AWSError> {
+
+		let mut request = PutObjectRequest::default();
+		request.key = object_name.to_string();
+		request.bucket = bucket_name.to_string();
+		request.body = Some(object_as_bytes);
+		request.set_sse_customer_key(key);
+
+		let result = self.put_object_with_request(&mut request);
+		result
+	}
+
 	/// Uploads object to specified S3 bucket using AWS KMS for key management of encryption at rest.
 	pub fn put_object_with_kms_encryption(&mut self, bucket_name: &str, object_name: &str,
 		object_as_bytes: &Vec<u8>, key_id: &str) ->  Result<PutObjectOutput, AWSError> {
@@ -156,49 +1056,94 @@ impl<'a> S3Helper<'a> {
 
 	/// Uploads object: lets sender specify options.
 	/// The most generic of put_object: caller specifies the whole request.
+	///
+	/// `Content-MD5` is filled in if the request doesn't have it, as is a checksum if one is set
+	/// with `set_checksum_algorithm`.  The upload is checked against the ETag S3 returns.
 	pub fn put_object_with_request(&mut self, request: &mut PutObjectRequest) -> Result<PutObjectOutput, AWSError> {
-		// This may be where we do some basic sanity checking: ensure we have:
-		// bucket name, region, object id, payload.
+		let body = request.body.unwrap_or(&[]);
 
 		// content_md5 hashing for everyone!
-		let hash = hash(MD5, request.body.unwrap()).to_base64(STANDARD);
+		let md5 = hash(MD5, body);
+		if request.content_md5.is_none() {
+			request.content_md5 = Some(md5.to_base64(STANDARD));
+		}
+		if let Some(algorithm) = self.checksum_algorithm {
+			add_checksum(request, algorithm, algorithm.checksum(body));
+		}
 
-		self.client.put_object(&request)
+		let length = body.len() as u64;
+		let tracker = self.progress_tracker(Some(length));
+		try!(tracker.start());
+		let output = try!(self.client.put_object(&request));
+		// the upload is done, too late to cancel it
+		let _ = tracker.add_bytes(length);
+		try!(verify_upload(&request.key, &output, &md5.to_hex()));
+		Ok(output)
+	}
+
+	/// Uploads an object only if `conditions` are met, EG: `Conditions::if_absent()` so as not to
+	/// overwrite an existing object, or `Conditions::if_match(e_tag)` so as not to overwrite changes
+	/// made since it was read.
+	pub fn put_object_if(&mut self, bucket_name: &str, object_name: &str, object_as_bytes: &Vec<u8>,
+		conditions: &Conditions) -> Result<Conditional<PutObjectOutput>, AWSError> {
+
+		let mut request = PutObjectRequest::default();
+		request.key = object_name.to_string();
+		request.bucket = bucket_name.to_string();
+		request.body = Some(object_as_bytes);
+		try!(conditions.apply_to_put_object(&mut request));
+		Conditional::from_result(self.put_object_with_request(&mut request))
 	}
 
 	// TODO: does this make a copy of the object_as_reader or just transfers ownership to this?
 	/// Uploads a multi-part object to specified bucket.  Allows for large file uploads.
+	///
+	/// Parts are uploaded one at a time; `transfer::TransferManager` uploads files in parallel.
 	pub fn put_multipart_object<T: Read>(&mut self, bucket_name: &str, object_name: &str,
 		object_as_reader: &mut T) -> Result<PutObjectOutput, AWSError> { // TODO: return type correct?
+		self.put_multipart_object_with_options(bucket_name, object_name, object_as_reader, &UploadOptions::new())
+	}
 
-		// TODO: make helper function for object PUT requests that handles encryption, reduced redudancy, etc...
+	/// Uploads a multi-part object to specified bucket with the content type, metadata and other
+	/// headers in `options`.  A content type is guessed from the object name's extension unless one
+	/// is set.
+	pub fn put_multipart_object_with_options<T: Read>(&mut self, bucket_name: &str, object_name: &str,
+		object_as_reader: &mut T, options: &UploadOptions) -> Result<PutObjectOutput, AWSError> {
 
 		let mut multipart_upload_request = CreateMultipartUploadRequest::default();
 		multipart_upload_request.key = object_name.to_string();
 		multipart_upload_request.bucket = bucket_name.to_string();
+		options.apply_to_multipart_upload(&mut multipart_upload_request, Path::new(object_name));
 
 		// compiler warns about this line, it's not seeing its use later in this function:
 		let mut upload_id : String;
 
 		match self.client.create_multipart_upload(&multipart_upload_request) {
 			Err(why) => {
-				println!("Couldn't create multipart upload request: {:?}", why);
-				return Err(AWSError::new("oops"));
+				warn!("Couldn't create multipart upload request: {:?}", why);
+				return Err(why);
 			}
 			Ok(response) => upload_id = response.upload_id.to_string(),
 		}
 
 		let mut buffered_reader = BufReader::new(object_as_reader);
 		let mut parts_list : Vec<String>;
+		let tracker = self.progress_tracker(None);
 
-		match self.upload_chunks(&mut buffered_reader, &bucket_name, &upload_id, &object_name) {
-			Err(why) => return Err(AWSError::new("oops in upload_chunks")),
+		match self.upload_chunks(&mut buffered_reader, &bucket_name, &upload_id, &object_name, &tracker) {
+			Err(why) => {
+				self.abort_failed_upload(bucket_name, object_name, &upload_id);
+				return Err(why);
+			}
 			Ok(parts) => parts_list = parts,
 		}
 
 		let item_list : Vec<u8>;
 		match multipart_upload_finish_xml(&parts_list) {
-			Err(why) => return Err(AWSError::new("oops in multipart_upload_finish_xml")),
+			Err(why) => {
+				self.abort_failed_upload(bucket_name, object_name, &upload_id);
+				return Err(why);
+			}
 			Ok(parts_in_xml) => item_list = parts_in_xml,
 		}
 		let mut complete_upload = CompleteMultipartUploadRequest::default();
@@ -209,126 +1154,726 @@ impl<'a> S3Helper<'a> {
 
 		complete_upload.multipart_upload = Some(&item_list);
 
-		match self.client.complete_multipart_upload(&complete_upload) {
+		let completed = match self.client.complete_multipart_upload(&complete_upload) {
 			Err(why) => {
-				println!("Couldn't mark multipart upload as complete: {:?}", why);
-				return Err(AWSError::new("oops in complete multipart upload"));
+				warn!("Couldn't mark multipart upload as complete: {:?}", why);
+				self.abort_failed_upload(bucket_name, object_name, &upload_id);
+				return Err(why);
 			},
-			Ok(_) => (), // TODO: return object output
+			Ok(output) => output,
+		};
+		if checksum::etag_has_md5(&completed.server_side_encryption, "") {
+			try!(checksum::check_multipart_etag(object_name, &completed.e_tag, &parts_list));
+		}
+
+		let mut output = PutObjectOutput::default();
+		output.e_tag = completed.e_tag;
+		output.version_id = completed.version_id;
+		output.expiration = completed.expiration;
+		output.server_side_encryption = completed.server_side_encryption;
+		output.ssekms_key_id = completed.ssekms_key_id;
+		Ok(output)
+	}
+
+	/// Aborts a multipart upload that failed, so the parts that made it aren't left behind.  The
+	/// upload's own error is the one worth returning, so one from aborting is only logged.
+	fn abort_failed_upload(&mut self, bucket_name: &str, object_name: &str, upload_id: &str) {
+		if let Err(abort_error) = self.abort_multipart_upload(bucket_name, object_name, upload_id) {
+			warn!("Couldn't abort multipart upload {}: {:?}", upload_id, abort_error);
+		}
+	}
+
+	fn upload_chunks<T: Read>(&mut self, buffered_reader: &mut BufReader<T>,
+			bucket_name: &str, upload_id: &str, object_name: &str, tracker: &ProgressTracker) -> Result<Vec<String>, AWSError> {
+
+		let mut s3_chunk : Vec<u8> = Vec::with_capacity(S3_MINIMUM_PART_SIZE);
+		let mut parts : Vec<String> = Vec::new();
+		let mut part_number = 1;
+		try!(tracker.start());
+		loop {
+			s3_chunk.clear();
+			if let Err(why) = buffered_reader.by_ref().take(S3_MINIMUM_PART_SIZE as u64).read_to_end(&mut s3_chunk) {
+				warn!("Got Error in reading from buffer: {:?}", why);
+				return Err(AWSError::from(why));
+			}
+			// A short read means the reader is done.  Skip the empty trailing part unless the object is empty.
+			if s3_chunk.is_empty() && !parts.is_empty() {
+				break;
+			}
+
+			match self.upload_a_part(&s3_chunk, &part_number, &bucket_name, &upload_id, &object_name) {
+				Err(why) => {
+					warn!("Got error uploading a part: {:?}", why);
+					return Err(why);
+				}
+				Ok(response) => {
+					parts.push(response);
+				}
+			}
+			try!(tracker.part_completed(part_number, s3_chunk.len() as u64));
+			if s3_chunk.len() < S3_MINIMUM_PART_SIZE {
+				break;
+			}
+			part_number += 1;
+		}
+		Ok(parts)
+	}
+
+	fn upload_a_part(&mut self, buffer: &Vec<u8>, part_number: &i32,
+			bucket_name: &str, upload_id: &str, object_name: &str) -> Result<String, AWSError> {
+
+		let mut upload_part_request = UploadPartRequest::default();
+		upload_part_request.body = Some(&buffer);
+
+		let md5 = hash(MD5, buffer);
+		upload_part_request.content_md5 = Some(md5.to_base64(STANDARD));
+
+		upload_part_request.bucket = bucket_name.to_string();
+		upload_part_request.upload_id = upload_id.to_string();
+		upload_part_request.part_number = part_number.clone();
+		upload_part_request.key = object_name.to_string();
+
+		match self.client.upload_part(&upload_part_request) {
+			Err(why) => {
+				warn!("Error uploading part: {:?}", why);
+				return Err(why);
+			},
+			Ok(response) => {
+				if checksum::etag_has_md5(&response.server_side_encryption, &response.sse_customer_algorithm) {
+					try!(checksum::check_etag(object_name, &response.e_tag, &md5.to_hex()));
+				}
+				return Ok(response.e_tag);
+			}
+		}
+	}
+
+	/// Lists multipart uploads not yet completed for specified bucket
+	pub fn list_multipart_uploads_for_bucket(&mut self, bucket_name: &str) -> Result<ListMultipartUploadsOutput, AWSError> {
+		let mut request = ListMultipartUploadsRequest::default();
+		request.bucket = bucket_name.to_string();
+
+		match self.client.list_multipart_uploads(&request) {
+			Err(why) => Err(AWSError::new(format!("Couldn't do list_multipart_uploads: {:?}", why))),
+			Ok(result) => Ok(result),
+		}
+	}
+
+	/// Deletes specified object from specified bucket.
+	pub fn delete_object(&mut self, bucket_name: &str, object_name: &str) ->  Result<DeleteObjectOutput, AWSError> {
+		let mut request = DeleteObjectRequest::default();
+		request.key = object_name.to_string();
+		request.bucket = bucket_name.to_string();
+		self.client.delete_object(&request)
+	}
+
+	/// Deletes objects from a bucket, sending up to 1000 keys per DeleteObjects request.
+	///
+	/// S3 deletes each key on its own: the ones it couldn't delete are in the output's `errors`,
+	/// with the reason.  The call itself only fails if a whole request does.
+	pub fn delete_objects(&mut self, bucket_name: &str, keys: &[String]) -> Result<DeleteObjectsOutput, AWSError> {
+		let objects = keys.iter().map(|key| ObjectIdentifier { key: key.clone(), version_id: None }).collect();
+		self.delete_object_versions(bucket_name, objects)
+	}
+
+	/// Deletes objects, or particular versions of them, like `delete_objects`.
+	pub fn delete_object_versions(&mut self, bucket_name: &str, objects: Vec<ObjectIdentifier>) -> Result<DeleteObjectsOutput, AWSError> {
+		self.delete_in_batches(bucket_name, objects, false)
+	}
+
+	/// Deletes everything in a bucket, so the bucket itself can be deleted: every object, with
+	/// `include_versions` every object version and delete marker instead, and every multipart
+	/// upload in progress.  Buckets that ever had versioning turned on need `include_versions`.
+	///
+	/// Goes through the whole bucket, then fails if any key couldn't be deleted.
+	pub fn empty_bucket(&mut self, bucket_name: &str, include_versions: bool) -> Result<(), AWSError> {
+		let mut errors = Vec::new();
+		if include_versions {
+			let versions = ObjectVersionPager::new(versions_request(bucket_name, ""));
+			errors.extend(try!(self.delete_listed(bucket_name, versions, |version| {
+				ObjectIdentifier { key: version.key, version_id: non_empty(&version.version_id) }
+			})));
+			let delete_markers = DeleteMarkerPager::new(versions_request(bucket_name, ""));
+			errors.extend(try!(self.delete_listed(bucket_name, delete_markers, |marker| {
+				ObjectIdentifier { key: marker.key, version_id: non_empty(&marker.version_id) }
+			})));
+		} else {
+			let mut request = ListObjectsV2Request::default();
+			request.bucket = bucket_name.to_string();
+			request.encoding_type = Some("url".to_string());
+			errors.extend(try!(self.delete_listed(bucket_name, ObjectV2Pager::new(request), |object| {
+				ObjectIdentifier { key: object.key, version_id: None }
+			})));
+		}
+
+		let mut request = ListMultipartUploadsRequest::default();
+		request.bucket = bucket_name.to_string();
+		let mut uploads = MultipartUploadPager::new(request);
+		loop {
+			let (page, more_pages) = try!(uploads.next_page(&mut self.client, None));
+			for upload in page {
+				try!(self.abort_multipart_upload(bucket_name, &upload.key, &upload.upload_id));
+			}
+			if !more_pages {
+				break;
+			}
+		}
+
+		check_deleted(bucket_name, &errors)
+	}
+
+	/// Deletes each page of a listing as it arrives, returning the keys that couldn't be deleted.
+	fn delete_listed<P: ListPager, F: Fn(P::Item) -> ObjectIdentifier>(&mut self, bucket_name: &str, mut pager: P,
+			identify: F) -> Result<Errors, AWSError> {
+		let mut errors = Vec::new();
+		loop {
+			let (page, more_pages) = try!(pager.next_page(&mut self.client, None));
+			let objects = page.into_iter().map(|item| identify(item)).collect();
+			errors.extend(try!(self.delete_in_batches(bucket_name, objects, true)).errors);
+			if !more_pages {
+				return Ok(errors);
+			}
+		}
+	}
+
+	/// Deletes objects up to 1000 at a time.  In quiet mode S3 leaves the deleted keys out of the
+	/// response, and only reports errors.
+	fn delete_in_batches(&mut self, bucket_name: &str, mut objects: Vec<ObjectIdentifier>, quiet: bool) -> Result<DeleteObjectsOutput, AWSError> {
+		let mut output = DeleteObjectsOutput::default();
+		while !objects.is_empty() {
+			let rest = objects.split_off(cmp::min(objects.len(), S3_MAXIMUM_DELETE_KEYS));
+			let mut request = DeleteObjectsRequest::default();
+			request.bucket = bucket_name.to_string();
+			request.delete.objects = objects;
+			if quiet {
+				request.delete.quiet = Some(true);
+			}
+			let batch = try!(self.client.delete_objects(&request));
+			output.deleted.extend(batch.deleted);
+			output.errors.extend(batch.errors);
+			output.request_charged = batch.request_charged;
+			objects = rest;
+		}
+		Ok(output)
+	}
+
+	/// Copies an object within S3, keeping its metadata.  The data doesn't pass through this
+	/// process.  Objects over 5 GB are copied in parts.
+	pub fn copy_object(&mut self, source_bucket: &str, source_key: &str, bucket_name: &str, object_name: &str) -> Result<CopyObjectOutput, AWSError> {
+		let mut request = CopyObjectRequest::default();
+		request.bucket = bucket_name.to_string();
+		request.key = object_name.to_string();
+		self.copy_object_with_request(source_bucket, source_key, &mut request)
+	}
+
+	/// Copies an object within S3, giving the copy `metadata` instead of the source's metadata.
+	pub fn copy_object_with_metadata(&mut self, source_bucket: &str, source_key: &str, bucket_name: &str, object_name: &str,
+		metadata: Metadata) -> Result<CopyObjectOutput, AWSError> {
+
+		let mut request = CopyObjectRequest::default();
+		request.bucket = bucket_name.to_string();
+		request.key = object_name.to_string();
+		request.metadata = Some(metadata);
+		request.metadata_directive = Some(METADATA_DIRECTIVE_REPLACE.to_string());
+		self.copy_object_with_request(source_bucket, source_key, &mut request)
+	}
+
+	/// Copies an object within S3: the most generic of the copies, the caller specifies the whole
+	/// request except the copy source.  With a `REPLACE` metadata directive the copy's metadata and
+	/// content headers come from the request, otherwise from the source.
+	///
+	/// Sources over 5 GB, too large for CopyObject, are copied with a multipart upload whose parts
+	/// are copied with UploadPartCopy.
+	pub fn copy_object_with_request(&mut self, source_bucket: &str, source_key: &str, request: &mut CopyObjectRequest) -> Result<CopyObjectOutput, AWSError> {
+		request.copy_source = copy_source(source_bucket, source_key);
+
+		let mut head_request = HeadObjectRequest::default();
+		head_request.bucket = source_bucket.to_string();
+		head_request.key = source_key.to_string();
+		head_request.if_match = request.copy_source_if_match.clone();
+		head_request.sse_customer_algorithm = request.copy_source_sse_customer_algorithm.clone();
+		head_request.sse_customer_key = request.copy_source_sse_customer_key.clone();
+		head_request.sse_customer_key_md5 = request.copy_source_sse_customer_key_md5.clone();
+		let source = try!(self.client.head_object(&head_request));
+		let size = source.content_length as u64;
+
+		if size <= S3_MAXIMUM_COPY_SIZE {
+			return self.client.copy_object(request);
+		}
+		self.copy_object_in_parts(request, &source)
+	}
+
+	fn copy_object_in_parts(&mut self, request: &CopyObjectRequest, source: &HeadObjectOutput) -> Result<CopyObjectOutput, AWSError> {
+		let mut create_request = CreateMultipartUploadRequest::default();
+		create_request.bucket = request.bucket.clone();
+		create_request.key = request.key.clone();
+		create_request.acl = request.acl.as_ref().map(|acl| acl.to_string());
+		create_request.grant_full_control = request.grant_full_control.clone();
+		create_request.grant_read = request.grant_read.clone();
+		create_request.grant_read_acp = request.grant_read_acp.clone();
+		create_request.grant_write_acp = request.grant_write_acp.clone();
+		create_request.storage_class = request.storage_class.clone();
+		create_request.server_side_encryption = request.server_side_encryption.clone();
+		create_request.ssekms_key_id = request.ssekms_key_id.clone();
+		create_request.sse_customer_algorithm = request.sse_customer_algorithm.clone();
+		create_request.sse_customer_key = request.sse_customer_key.clone();
+		create_request.sse_customer_key_md5 = request.sse_customer_key_md5.clone();
+		create_request.request_payer = request.request_payer.clone();
+		if request.metadata_directive.as_ref().map(|directive| &directive[..]) == Some(METADATA_DIRECTIVE_REPLACE) {
+			create_request.metadata = request.metadata.clone();
+			create_request.content_type = request.content_type.clone();
+			create_request.cache_control = request.cache_control.clone();
+			create_request.content_disposition = request.content_disposition.clone();
+			create_request.content_encoding = request.content_encoding.clone();
+			create_request.content_language = request.content_language.clone();
+			create_request.expires = request.expires.clone();
+			create_request.website_redirect_location = request.website_redirect_location.clone();
+		} else {
+			// unlike CopyObject, a multipart upload doesn't take anything from the source
+			create_request.metadata = Some(source.metadata.clone());
+			create_request.content_type = non_empty(&source.content_type);
+			create_request.cache_control = non_empty(&source.cache_control);
+			create_request.content_disposition = non_empty(&source.content_disposition);
+			create_request.content_encoding = non_empty(&source.content_encoding);
+			create_request.content_language = non_empty(&source.content_language);
+			create_request.expires = source.expires.as_ref().map(conditional::format_http_date);
+			create_request.website_redirect_location = non_empty(&source.website_redirect_location);
+		}
+		let upload_id = try!(self.client.create_multipart_upload(&create_request)).upload_id;
+
+		let tracker = self.progress_tracker(Some(source.content_length as u64));
+		match self.copy_parts(request, source, &upload_id, &tracker) {
+			Err(why) => {
+				self.abort_failed_upload(&request.bucket, &request.key, &upload_id);
+				Err(why)
+			}
+			Ok(parts) => {
+				let parts_xml = try!(multipart_upload_finish_xml(&parts));
+				let mut complete_request = CompleteMultipartUploadRequest::default();
+				complete_request.bucket = request.bucket.clone();
+				complete_request.key = request.key.clone();
+				complete_request.upload_id = upload_id.clone();
+				complete_request.multipart_upload = Some(&parts_xml);
+				let completed = try!(self.client.complete_multipart_upload(&complete_request));
+
+				let mut output = CopyObjectOutput::default();
+				output.copy_object_result.e_tag = completed.e_tag;
+				output.copy_source_version_id = source.version_id.clone();
+				output.expiration = completed.expiration;
+				output.server_side_encryption = completed.server_side_encryption;
+				output.ssekms_key_id = completed.ssekms_key_id;
+				Ok(output)
+			}
+		}
+	}
+
+	fn copy_parts(&mut self, request: &CopyObjectRequest, source: &HeadObjectOutput, upload_id: &str,
+		tracker: &ProgressTracker) -> Result<Vec<String>, AWSError> {
+
+		let mut parts = Vec::new();
+		try!(tracker.start());
+		for (index, &(first, last)) in copy_part_ranges(source.content_length as u64).iter().enumerate() {
+			let part_number = index as i32 + 1;
+			let mut part_request = UploadPartCopyRequest::default();
+			part_request.bucket = request.bucket.clone();
+			part_request.key = request.key.clone();
+			part_request.upload_id = upload_id.to_string();
+			part_request.part_number = part_number;
+			part_request.copy_source = request.copy_source.clone();
+			part_request.copy_source_range = Some(format!("bytes={}-{}", first, last));
+			// every part must come from the same version of the source
+			part_request.copy_source_if_match = Some(source.e_tag.clone());
+			part_request.copy_source_if_none_match = request.copy_source_if_none_match.clone();
+			part_request.copy_source_if_modified_since = request.copy_source_if_modified_since.clone();
+			part_request.copy_source_if_unmodified_since = request.copy_source_if_unmodified_since.clone();
+			part_request.copy_source_sse_customer_algorithm = request.copy_source_sse_customer_algorithm.clone();
+			part_request.copy_source_sse_customer_key = request.copy_source_sse_customer_key.clone();
+			part_request.copy_source_sse_customer_key_md5 = request.copy_source_sse_customer_key_md5.clone();
+			part_request.sse_customer_algorithm = request.sse_customer_algorithm.clone();
+			part_request.sse_customer_key = request.sse_customer_key.clone();
+			part_request.sse_customer_key_md5 = request.sse_customer_key_md5.clone();
+			part_request.request_payer = request.request_payer.clone();
+
+			let output = try!(self.client.upload_part_copy(&part_request));
+			parts.push(output.copy_part_result.e_tag);
+			try!(tracker.part_completed(part_number, last - first + 1));
+		}
+		Ok(parts)
+	}
+
+	/// Copies an object within S3 where the source, the copy or both are encrypted with
+	/// customer-provided keys.  Passing a new key for the copy re-encrypts it under that key.
+	pub fn copy_object_with_customer_keys(&mut self, source_bucket: &str, source_key: &str,
+		source_encryption_key: Option<&CustomerEncryptionKey>, bucket_name: &str, object_name: &str,
+		encryption_key: Option<&CustomerEncryptionKey>) -> Result<CopyObjectOutput, AWSError> {
+
+		let mut request = CopyObjectRequest::default();
+		request.bucket = bucket_name.to_string();
+		request.key = object_name.to_string();
+		if let Some(key) = source_encryption_key {
+			request.set_copy_source_sse_customer_key(key);
+		}
+		if let Some(key) = encryption_key {
+			request.set_sse_customer_key(key);
+		}
+		self.copy_object_with_request(source_bucket, source_key, &mut request)
+	}
+
+	/// Moves an object within S3: copies it, checks the copy is complete, then deletes the source.
+	///
+	/// The copy must have the source's size and, when S3 derives both ETags from the same MD5, its
+	/// ETag.  Otherwise it must be the object the copy created, with the ETag the copy returned.
+	pub fn move_object(&mut self, source_bucket: &str, source_key: &str, bucket_name: &str, object_name: &str) -> Result<CopyObjectOutput, AWSError> {
+		let mut head_request = HeadObjectRequest::default();
+		head_request.bucket = source_bucket.to_string();
+		head_request.key = source_key.to_string();
+		let source = try!(self.client.head_object(&head_request));
+
+		// copy exactly the version looked at, so a concurrent overwrite isn't lost
+		let mut request = CopyObjectRequest::default();
+		request.bucket = bucket_name.to_string();
+		request.key = object_name.to_string();
+		request.copy_source_if_match = Some(source.e_tag.clone());
+		let output = try!(self.copy_object_with_request(source_bucket, source_key, &mut request));
+
+		let mut copy_request = HeadObjectRequest::default();
+		copy_request.bucket = bucket_name.to_string();
+		copy_request.key = object_name.to_string();
+		let copy = try!(self.client.head_object(&copy_request));
+		if copy.content_length != source.content_length {
+			return Err(AWSError::new(format!("Copy of {}/{} has {} bytes instead of {}, not deleting the source",
+				source_bucket, source_key, copy.content_length, source.content_length)));
+		}
+		let expected_e_tag = expected_copy_e_tag(&source, &copy, &output);
+		if copy.e_tag != expected_e_tag {
+			return Err(AWSError::new(format!("Copy of {}/{} has ETag {} instead of {}, not deleting the source",
+				source_bucket, source_key, copy.e_tag, expected_e_tag)));
+		}
+
+		try!(self.delete_object(source_bucket, source_key));
+		Ok(output)
+	}
+
+	/// Abort multipart upload.
+	pub fn abort_multipart_upload(&mut self, bucket_name: &str, object_name: &str, upload_id: &str) ->  Result<AbortMultipartUploadOutput, AWSError> {
+		let mut request = AbortMultipartUploadRequest::default();
+		request.key = object_name.to_string();
+		request.bucket = bucket_name.to_string();
+		request.upload_id = upload_id.to_string();
+		self.client.abort_multipart_upload(&request)
+	}
+
+	/// List parts from a multiupload request.
+	pub fn multipart_upload_list_parts(&mut self, bucket_name: &str, object_name: &str, upload_id: &str) ->  Result<ListPartsOutput, AWSError> {
+		let mut request = ListPartsRequest::default();
+		request.key = object_name.to_string();
+		request.bucket = bucket_name.to_string();
+		request.upload_id = upload_id.to_string();
+		self.client.list_parts(&request)
+	}
+
+	/// Every object in a bucket whose key starts with `prefix`, fetched a page at a time.
+	pub fn list_all_objects<'h>(&'h mut self, bucket_name: &str, prefix: &str) -> ListIterator<'h, 'a, ObjectV2Pager> {
+		self.list_all_objects_after(bucket_name, prefix, "")
+	}
+
+	/// Every object in a bucket whose key starts with `prefix` and sorts after `start_after`.
+	pub fn list_all_objects_after<'h>(&'h mut self, bucket_name: &str, prefix: &str, start_after: &str) -> ListIterator<'h, 'a, ObjectV2Pager> {
+		let mut request = ListObjectsV2Request::default();
+		request.bucket = bucket_name.to_string();
+		request.prefix = non_empty(prefix);
+		request.start_after = non_empty(start_after);
+		request.encoding_type = Some("url".to_string());
+		ListIterator::new(&mut self.client, ObjectV2Pager::new(request))
+	}
+
+	/// Every version of the objects in a bucket whose key starts with `prefix`.  Delete markers
+	/// aren't included; see `list_all_delete_markers`.
+	pub fn list_all_object_versions<'h>(&'h mut self, bucket_name: &str, prefix: &str) -> ListIterator<'h, 'a, ObjectVersionPager> {
+		ListIterator::new(&mut self.client, ObjectVersionPager::new(versions_request(bucket_name, prefix)))
+	}
+
+	/// Every delete marker in a bucket whose key starts with `prefix`.
+	pub fn list_all_delete_markers<'h>(&'h mut self, bucket_name: &str, prefix: &str) -> ListIterator<'h, 'a, DeleteMarkerPager> {
+		ListIterator::new(&mut self.client, DeleteMarkerPager::new(versions_request(bucket_name, prefix)))
+	}
+
+	/// Every multipart upload not yet completed in a bucket whose key starts with `prefix`.
+	pub fn list_all_multipart_uploads<'h>(&'h mut self, bucket_name: &str, prefix: &str) -> ListIterator<'h, 'a, MultipartUploadPager> {
+		let mut request = ListMultipartUploadsRequest::default();
+		request.bucket = bucket_name.to_string();
+		request.prefix = non_empty(prefix);
+		ListIterator::new(&mut self.client, MultipartUploadPager::new(request))
+	}
+
+	/// Every part uploaded so far for a multipart upload.
+	pub fn list_all_parts<'h>(&'h mut self, bucket_name: &str, object_name: &str, upload_id: &str) -> ListIterator<'h, 'a, PartPager> {
+		let mut request = ListPartsRequest::default();
+		request.bucket = bucket_name.to_string();
+		request.key = object_name.to_string();
+		request.upload_id = upload_id.to_string();
+		ListIterator::new(&mut self.client, PartPager::new(request))
+	}
+}
+
+fn add_put_object_headers(request: &mut SignedRequest, input: &PutObjectRequest) -> Result<(), AWSError> {
+	if let Some(ref acl) = input.acl {
+		request.add_header("x-amz-acl", &canned_acl_in_aws_format(acl));
+	}
+	if let Some(ref obj) = input.cache_control {
+		request.add_header("Cache-Control", obj);
+	}
+	if let Some(ref obj) = input.content_disposition {
+		request.add_header("Content-Disposition", obj);
+	}
+	if let Some(ref obj) = input.content_encoding {
+		request.add_header("Content-Encoding", obj);
+	}
+	if let Some(ref obj) = input.content_language {
+		request.add_header("Content-Language", obj);
+	}
+	if let Some(ref obj) = input.content_type {
+		request.add_header("Content-Type", obj);
+	}
+	if let Some(ref obj) = input.expires {
+		request.add_header("Expires", obj);
+	}
+	if let Some(ref obj) = input.grant_full_control {
+		request.add_header("x-amz-grant-full-control", obj);
+	}
+	if let Some(ref obj) = input.grant_read {
+		request.add_header("x-amz-grant-read", obj);
+	}
+	if let Some(ref obj) = input.grant_read_acp {
+		request.add_header("x-amz-grant-read-acp", obj);
+	}
+	if let Some(ref obj) = input.grant_write_acp {
+		request.add_header("x-amz-grant-write-acp", obj);
+	}
+	if let Some(ref obj) = input.website_redirect_location {
+		request.add_header("x-amz-website-redirect-location", obj);
+	}
+	if let Some(ref obj) = input.request_payer {
+		request.add_header("x-amz-request-payer", obj);
+	}
+	if let Some(ref obj) = input.tagging {
+		request.add_header("x-amz-tagging", obj);
+	}
+	if let Some(ref obj) = input.if_match {
+		request.add_header("If-Match", obj);
+	}
+	if let Some(ref obj) = input.if_none_match {
+		request.add_header("If-None-Match", obj);
+	}
+	match input.storage_class {
+		Some(ref class) => {
+			request.add_header("x-amz-storage-class", class);
+		}
+		None => (),
+	}
+
+	match input.server_side_encryption {
+		Some(ref sse) => {
+			if sse.to_string().to_ascii_lowercase() == "aes256" {
+				request.add_header("x-amz-server-side-encryption", &sse);
+			} else {
+				match input.ssekms_key_id {
+					Some(ref key_id) => request.add_header("x-amz-server-side-encryption-aws-kms-key-id", &key_id),
+					None => return Err(AWSError::new("KMS key specified but no key id provided.")),
+				}
+				request.add_header("x-amz-server-side-encryption", "aws:kms");
+			}
 		}
-
-		Ok(PutObjectOutput::default())
+		None => (),
 	}
 
-	fn upload_chunks<T: Read>(&mut self, buffered_reader: &mut BufReader<T>,
-			bucket_name: &str, upload_id: &str, object_name: &str) -> Result<Vec<String>, AWSError> {
-
-		let mut s3_chunk : Vec<u8> = Vec::with_capacity(S3_MINIMUM_PART_SIZE + CHUNK_TO_READ);
-		let mut buffer = [0u8; CHUNK_TO_READ];
+	match input.content_md5 {
+		Some(ref md5) => request.add_header("Content-MD5", &md5),
+		None => (),
+	}
+	add_sse_customer_headers(request, &input.sse_customer_algorithm, &input.sse_customer_key, &input.sse_customer_key_md5);
+	if let Some(ref obj) = input.checksum_crc32 {
+		request.add_header("x-amz-checksum-crc32", obj);
+	}
+	if let Some(ref obj) = input.checksum_crc32_c {
+		request.add_header("x-amz-checksum-crc32c", obj);
+	}
+	if let Some(ref obj) = input.checksum_sha1 {
+		request.add_header("x-amz-checksum-sha1", obj);
+	}
+	if let Some(ref obj) = input.checksum_sha256 {
+		request.add_header("x-amz-checksum-sha256", obj);
+	}
+	if let Some(ref metadata) = input.metadata {
+		for (key, value) in metadata.iter() {
+			request.add_header(&format!("x-amz-meta-{}", key), value);
+		}
+	}
+	Ok(())
+}
 
-		let mut total_bytes_for_debug = 0;
+/// Adds the headers carrying a customer-provided encryption key, when there is one.
+fn add_sse_customer_headers(request: &mut SignedRequest, algorithm: &Option<SSECustomerAlgorithm>,
+		key: &Option<SSECustomerKey>, key_md5: &Option<SSECustomerKeyMD5>) {
+	if let Some(ref obj) = *algorithm {
+		request.add_header("x-amz-server-side-encryption-customer-algorithm", obj);
+	}
+	if let Some(ref obj) = *key {
+		request.add_header("x-amz-server-side-encryption-customer-key", obj);
+	}
+	if let Some(ref obj) = *key_md5 {
+		request.add_header("x-amz-server-side-encryption-customer-key-MD5", obj);
+	}
+}
 
-		let mut parts : Vec<String> = Vec::new();
-		let mut part_number = 1;
-		let mut more_chunks_to_go = true;
-		while more_chunks_to_go {
-			match buffered_reader.read(&mut buffer) {
-				Err(why) => println!("Got Error in reading from buffer: {:?}", why),
-				Ok(bytes_read) => {
-					total_bytes_for_debug += bytes_read;
-					if bytes_read == 0 {
-						more_chunks_to_go = false;
-					}
-					let mut bytes_copied = 0;
-					// This still iterates over the buffer even if we're done with it.  Need to fix.
-					for i in buffer.iter() {
-						if bytes_copied < bytes_read
-						{
-							s3_chunk.push(i.clone()); // I think this copying is unavoidable
-							bytes_copied += 1;
-						}
-					}
+fn put_object_result(mut result: Response, key: &str) -> Result<PutObjectOutput, AWSError> {
+	let status = result.status.to_u16();
 
-					if s3_chunk.len() >= S3_MINIMUM_PART_SIZE || !more_chunks_to_go {
-						match self.upload_a_part(&s3_chunk, &part_number, &bucket_name, &upload_id, &object_name) {
-							Err(why) => {
-								println!("Got error uploading a part: {:?}", why);
-								return Err(AWSError::new("oops in upload_chunks"));
-							}
-							Ok(response) => {
-								parts.push(response);
-							}
-						}
-						s3_chunk.clear();
-						part_number += 1;
-					}
-				}
+	match status {
+		200 => {
+			let mut put_result = PutObjectOutput::default();
+			try!(PutObjectOutputParser::parse_headers(&result.headers, &mut put_result));
+			Ok(put_result)
+		}
+		_ => {
+			warn!("Error: Status code was {}", status);
+			let mut body = String::new();
+			result.read_to_string(&mut body).unwrap();
+			debug!("Error response body: {}", body);
+			if is_bad_digest(&body) {
+				return Err(checksum::upload_rejected(key));
 			}
+
+			Err(AWSError::new("error uploading object to S3"))
 		}
-		Ok(parts)
 	}
+}
 
-	fn upload_a_part(&mut self, buffer: &Vec<u8>, part_number: &i32,
-			bucket_name: &str, upload_id: &str, object_name: &str) -> Result<String, AWSError> {
+/// Whether S3 refused an upload because its data didn't match the `Content-MD5` or checksum sent
+/// with it.
+fn is_bad_digest(error_body: &str) -> bool {
+	error_body.contains("<Code>BadDigest</Code>")
+}
 
-		let mut upload_part_request = UploadPartRequest::default();
-		upload_part_request.body = Some(&buffer);
+fn add_upload_part_headers(request: &mut SignedRequest, input: &UploadPartRequest) {
+	match input.content_md5 {
+		Some(ref md5) => request.add_header("Content-MD5", &md5),
+		None => (),
+	}
+	add_sse_customer_headers(request, &input.sse_customer_algorithm, &input.sse_customer_key, &input.sse_customer_key_md5);
 
-		let hash = hash(MD5, upload_part_request.body.unwrap()).to_base64(STANDARD);
-		upload_part_request.content_md5 = Some(hash);
+	let mut params = Params::new();
+	params.put("partNumber", &format!("{}", input.part_number));
+	params.put("uploadId", &format!("{}", input.upload_id));
+	request.set_params(params);
+}
 
-		upload_part_request.bucket = bucket_name.to_string();
-		upload_part_request.upload_id = upload_id.to_string();
-		upload_part_request.part_number = part_number.clone();
-		upload_part_request.key = object_name.to_string();
+fn upload_part_result(mut result: Response, key: &str) -> Result<UploadPartOutput, AWSError> {
+	let status = result.status.to_u16();
 
-		match self.client.upload_part(&upload_part_request) {
-			Err(why) => {
-				println!("Error uploading part: {:?}", why);
-				return Err(AWSError::new("oops in upload_a_part"));
-			},
-			Ok(response) => {
-				return Ok(response.to_string());
+	match status {
+		200 => {
+			let mut upload_result = UploadPartOutput::default();
+			try!(UploadPartOutputParser::parse_headers(&result.headers, &mut upload_result));
+			if upload_result.e_tag.is_empty() {
+				return Err(AWSError::new("Couldn't find etag in response headers."));
+			}
+			Ok(upload_result)
+		}
+		_ => {
+			warn!("Error: Status code was {}", status);
+			let mut body = String::new();
+			result.read_to_string(&mut body).unwrap();
+			debug!("Error response body: {}", body);
+			if is_bad_digest(&body) {
+				return Err(checksum::upload_rejected(key));
 			}
+			Err(AWSError::new("error: didn't get a 200."))
 		}
 	}
+}
 
-	/// Lists multipart uploads not yet completed for specified bucket
-	pub fn list_multipart_uploads_for_bucket(&mut self, bucket_name: &str) -> Result<ListMultipartUploadsOutput, AWSError> {
-		let mut request = ListMultipartUploadsRequest::default();
-		request.bucket = bucket_name.to_string();
+/// The response, or for the status of a condition that wasn't met or of a missing object, the
+/// error `conditional` recognises.
+fn status_result(response: Response, path: &str) -> Result<Response, AWSError> {
+	match conditional::status_error(response.status.to_u16(), path) {
+		Some(why) => Err(why),
+		None => Ok(response),
+	}
+}
 
-		match self.client.list_multipart_uploads(&request) {
-			Err(why) => Err(AWSError::new(format!("Couldn't do list_multipart_uploads: {:?}", why))),
-			Ok(result) => Ok(result),
-		}
+/// The region named by S3's `x-amz-bucket-region` response header, if there is one.
+fn bucket_region_header(headers: &Headers) -> Result<Option<Region>, AWSError> {
+	match header_value(headers, "x-amz-bucket-region") {
+		Some(name) => match region_from_aws_format(&name) {
+			Some(region) => Ok(Some(region)),
+			None => Err(AWSError::new(format!("Bucket is in region {}, which isn't supported", name))),
+		},
+		None => Ok(None),
 	}
+}
 
-	/// Deletes specified object from specified bucket.
-	pub fn delete_object(&mut self, bucket_name: &str, object_name: &str) ->  Result<DeleteObjectOutput, AWSError> {
-		let mut request = DeleteObjectRequest::default();
-		request.key = object_name.to_string();
-		request.bucket = bucket_name.to_string();
-		self.client.delete_object(&request)
+/// Region of a bucket, given the location constraint GetBucketLocation returned for it.  Buckets
+/// in us-east-1 have no constraint, and old buckets in eu-west-1 say "EU".
+pub fn bucket_region_from_location_constraint(location_constraint: &str) -> Result<Region, AWSError> {
+	match location_constraint {
+		"" => Ok(Region::UsEast1),
+		"EU" => Ok(Region::EuWest1),
+		name => region_from_aws_format(name)
+			.ok_or(AWSError::new(format!("Bucket is in region {}, which isn't supported", name))),
+	}
+}
+
+/// Turns the objects a batch delete couldn't delete into an error naming the first of them.
+pub fn check_deleted(bucket_name: &str, errors: &[S3ClientError]) -> Result<(), AWSError> {
+	match errors.first() {
+		None => Ok(()),
+		Some(error) => Err(AWSError::new(format!("Couldn't delete {} objects from {}, EG: {}: {} {}",
+			errors.len(), bucket_name, error.key, error.code, error.message))),
 	}
+}
 
-	/// Abort multipart upload.
-	pub fn abort_multipart_upload(&mut self, bucket_name: &str, object_name: &str, upload_id: &str) ->  Result<AbortMultipartUploadOutput, AWSError> {
-		let mut request = AbortMultipartUploadRequest::default();
-		request.key = object_name.to_string();
-		request.bucket = bucket_name.to_string();
-		request.upload_id = upload_id.to_string();
-		self.client.abort_multipart_upload(&request)
+/// The ETag the copy of `source` should have.  An object copied in one piece keeps its MD5 ETag,
+/// unless either end is encrypted with KMS or a customer key; anything else is only known from
+/// what the copy returned.
+fn expected_copy_e_tag<'o>(source: &'o HeadObjectOutput, copy: &HeadObjectOutput, output: &'o CopyObjectOutput) -> &'o str {
+	let same_md5 = !checksum::is_multipart(&source.e_tag) && !checksum::is_multipart(&copy.e_tag)
+		&& checksum::etag_has_md5(&source.server_side_encryption, &source.sse_customer_algorithm)
+		&& checksum::etag_has_md5(&copy.server_side_encryption, &copy.sse_customer_algorithm);
+	if same_md5 {
+		&source.e_tag
+	} else {
+		&output.copy_object_result.e_tag
 	}
+}
 
-	/// List parts from a multiupload request.
-	pub fn multipart_upload_list_parts(&mut self, bucket_name: &str, object_name: &str, upload_id: &str) ->  Result<ListPartsOutput, AWSError> {
-		let mut request = ListPartsRequest::default();
-		request.key = object_name.to_string();
-		request.bucket = bucket_name.to_string();
-		request.upload_id = upload_id.to_string();
-		self.client.list_parts(&request)
+/// `x-amz-copy-source` value for an object: its bucket and key, URL-encoded.
+fn copy_source(bucket: &str, key: &str) -> String {
+	object_path(bucket, key)
+}
+
+/// Inclusive byte ranges of the parts a multipart copy of `size` bytes is split into: parts of
+/// `S3_COPY_PART_SIZE`, or larger when that would need more than S3's 10,000 parts.
+fn copy_part_ranges(size: u64) -> Vec<(u64, u64)> {
+	let part_size = cmp::max(S3_COPY_PART_SIZE, (size + S3_MAXIMUM_PARTS - 1) / S3_MAXIMUM_PARTS);
+	let mut ranges = Vec::new();
+	let mut first = 0;
+	while first < size {
+		let last = cmp::min(first + part_size, size) - 1;
+		ranges.push((first, last));
+		first = last + 1;
 	}
+	ranges
+}
+
+fn non_empty(value: &str) -> Option<String> {
+	if value.is_empty() { None } else { Some(value.to_string()) }
+}
+
+fn versions_request(bucket_name: &str, prefix: &str) -> ListObjectVersionsRequest {
+	let mut request = ListObjectVersionsRequest::default();
+	request.bucket = bucket_name.to_string();
+	request.prefix = non_empty(prefix);
+	request
 }
 
 /// Helper function to determine if a create config is needed.
@@ -385,8 +1930,218 @@ pub fn canned_acl_in_aws_format(canned_acl: &CannedAcl) -> String {
 	}
 }
 
+/// Path-style path of a bucket, EG: "/my-bucket"
+fn bucket_path(bucket: &str) -> String {
+	format!("/{}", encode_uri_strict(bucket))
+}
+
+/// Path-style path of an object, EG: "/my-bucket/photos/summer%202015.jpg"
+fn object_path(bucket: &str, key: &str) -> String {
+	format!("{}/{}", bucket_path(bucket), encode_uri_path(key))
+}
+
+/// The path of a virtual-hosted request, from its path-style path: the bucket segment goes.
+fn virtual_host_path(path: &str, bucket: &str) -> String {
+	let bucket_path = bucket_path(bucket);
+	if path == bucket_path {
+		return "/".to_string();
+	}
+	if path.starts_with(&bucket_path) && path[bucket_path.len()..].starts_with("/") {
+		return path[bucket_path.len()..].to_string();
+	}
+	path.to_string()
+}
+
+/// Whether a bucket name can be used as a DNS label: 3 to 63 lowercase letters, digits, dots and
+/// hyphens, starting and ending with a letter or digit, and not shaped like an IP address.
+pub fn is_dns_compatible_bucket_name(bucket: &str) -> bool {
+	if bucket.len() < 3 || bucket.len() > 63 {
+		return false;
+	}
+	let valid_characters = bucket.bytes().all(|byte| match byte {
+		b'a'...b'z' | b'0'...b'9' | b'.' | b'-' => true,
+		_ => false,
+	});
+	let alphanumeric = |byte: u8| match byte {
+		b'a'...b'z' | b'0'...b'9' => true,
+		_ => false,
+	};
+	let bytes = bucket.as_bytes();
+	let looks_like_ip = bucket.split('.').count() == 4 && bucket.split('.').all(|part| !part.is_empty() && part.bytes().all(|byte| byte >= b'0' && byte <= b'9'));
+
+	valid_characters && alphanumeric(bytes[0]) && alphanumeric(bytes[bytes.len() - 1])
+		&& !bucket.contains("..") && !bucket.contains(".-") && !bucket.contains("-.")
+		&& !looks_like_ip
+}
+
+/// Whether a bucket can be addressed virtual-hosted over HTTPS.  Dots would add levels to the host
+/// name, which S3's `*.s3.amazonaws.com` certificate doesn't cover.
+pub fn is_virtual_hostable_bucket_name(bucket: &str) -> bool {
+	is_dns_compatible_bucket_name(bucket) && !bucket.contains('.')
+}
+
+/// Decodes a value S3 sent with encoding-type=url, EG: "photos/summer+2015%2F" is "photos/summer 2015/".
+/// Malformed escapes are kept as they are.
+pub fn url_decode(value: &str) -> String {
+	let bytes = value.as_bytes();
+	let mut decoded = Vec::with_capacity(bytes.len());
+	let mut i = 0;
+	while i < bytes.len() {
+		match bytes[i] {
+			b'+' => decoded.push(b' '),
+			b'%' if i + 2 < bytes.len() && hex_value(bytes[i + 1]).is_some() && hex_value(bytes[i + 2]).is_some() => {
+				decoded.push(hex_value(bytes[i + 1]).unwrap() * 16 + hex_value(bytes[i + 2]).unwrap());
+				i += 2;
+			}
+			byte => decoded.push(byte),
+		}
+		i += 1;
+	}
+	String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn hex_value(digit: u8) -> Option<u8> {
+	match digit {
+		b'0'...b'9' => Some(digit - b'0'),
+		b'a'...b'f' => Some(digit - b'a' + 10),
+		b'A'...b'F' => Some(digit - b'A' + 10),
+		_ => None,
+	}
+}
+
+/// Decodes the keys and prefixes of a listing requested with encoding-type=url.
+pub fn decode_list_objects_v2_output(output: &mut ListObjectsV2Output) {
+	output.prefix = url_decode(&output.prefix);
+	output.delimiter = url_decode(&output.delimiter);
+	output.start_after = url_decode(&output.start_after);
+	for object in output.contents.iter_mut() {
+		object.key = url_decode(&object.key);
+	}
+	for common_prefix in output.common_prefixes.iter_mut() {
+		common_prefix.prefix = url_decode(&common_prefix.prefix);
+	}
+}
+
+/// Temporary file a download to `path` is written to before being renamed into place.
+/// It lives in the same directory so the final rename doesn't cross filesystems.
+pub fn download_temp_path(path: &Path) -> PathBuf {
+	let file_name = match path.file_name() {
+		Some(name) => name.to_string_lossy().into_owned(),
+		None => "download".to_string(),
+	};
+	path.with_file_name(format!(".{}.{}.part", file_name, precise_time_ns()))
+}
+
+fn write_stream_to_file<R: Read>(stream: &mut R, path: &Path) -> Result<u64, AWSError> {
+	let mut file = try!(File::create(path));
+	let written = try!(io::copy(stream, &mut file));
+	try!(file.flush());
+	try!(file.sync_all());
+	Ok(written)
+}
+
+/// Checks an object S3 stored is the data sent: the ETag of an object uploaded in one piece is its
+/// MD5, unless it's encrypted with a KMS or customer-provided key.
+fn verify_upload(object_name: &str, output: &PutObjectOutput, md5_hex: &str) -> Result<(), AWSError> {
+	if output.e_tag.is_empty() || !checksum::etag_has_md5(&output.server_side_encryption, &output.sse_customer_algorithm) {
+		return Ok(());
+	}
+	checksum::check_etag(object_name, &output.e_tag, md5_hex).map_err(AWSError::from)
+}
+
+/// Checks a downloaded object against the checksum S3 sent with it, or else against its ETag.
+/// `md5_hex` and `received_checksum` are of the data received.
+///
+/// Parts of objects can't be checked, and neither can objects sent without a checksum whose ETag
+/// isn't an MD5: multipart uploads and objects encrypted with a KMS or customer-provided key.
+pub fn verify_download(object_name: &str, output: &GetObjectOutput, md5_hex: &str, received_checksum: Option<&str>) -> Result<(), AWSError> {
+	if !output.content_range.is_empty() {
+		return Ok(());
+	}
+	if let (Some((algorithm, expected)), Some(actual)) = (download_checksum(output), received_checksum) {
+		return checksum::check_checksum(object_name, algorithm, expected, actual).map_err(AWSError::from);
+	}
+	if !output.e_tag.is_empty() && !checksum::is_multipart(&output.e_tag)
+			&& checksum::etag_has_md5(&output.server_side_encryption, &output.sse_customer_algorithm) {
+		return checksum::check_etag(object_name, &output.e_tag, md5_hex).map_err(AWSError::from);
+	}
+	debug!("Can't check the download of {}: it has no checksum and its ETag isn't an MD5", object_name);
+	Ok(())
+}
+
+/// The checksum of the whole object S3 sent with a download, if any.  Checksums of multipart
+/// uploads are of the parts' checksums, so can't be checked against the data.
+pub fn download_checksum(output: &GetObjectOutput) -> Option<(ChecksumAlgorithm, &str)> {
+	let checksums = [
+		(ChecksumAlgorithm::Crc32, &output.checksum_crc32),
+		(ChecksumAlgorithm::Crc32c, &output.checksum_crc32_c),
+		(ChecksumAlgorithm::Sha1, &output.checksum_sha1),
+		(ChecksumAlgorithm::Sha256, &output.checksum_sha256),
+	];
+	for &(algorithm, value) in checksums.iter() {
+		if !value.is_empty() && !checksum::is_multipart(value) {
+			return Some((algorithm, &value[..]));
+		}
+	}
+	None
+}
+
+/// Adds a checksum to an upload, unless it already has one.
+fn add_checksum(request: &mut PutObjectRequest, algorithm: ChecksumAlgorithm, checksum: String) {
+	if request.checksum_crc32.is_some() || request.checksum_crc32_c.is_some()
+			|| request.checksum_sha1.is_some() || request.checksum_sha256.is_some() {
+		return;
+	}
+	match algorithm {
+		ChecksumAlgorithm::Crc32 => request.checksum_crc32 = Some(checksum),
+		ChecksumAlgorithm::Crc32c => request.checksum_crc32_c = Some(checksum),
+		ChecksumAlgorithm::Sha1 => request.checksum_sha1 = Some(checksum),
+		ChecksumAlgorithm::Sha256 => request.checksum_sha256 = Some(checksum),
+	}
+}
+
+fn check_download_length(written: u64, content_length: i64) -> Result<(), AWSError> {
+	if content_length as u64 != written {
+		return Err(AWSError::new(format!("Download was truncated: expected {} bytes, got {}", content_length, written)));
+	}
+	Ok(())
+}
+
+impl fmt::Display for CannedAcl {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}", canned_acl_in_aws_format(self))
+	}
+}
+
+/// Implements one of the customer key traits by filling in the request's algorithm, key and key
+/// MD5 fields.
+macro_rules! customer_key_fields {
+	($fields:ident, $set:ident, $algorithm:ident, $key:ident, $key_md5:ident, $($request:ty),+) => {
+		$(
+			impl<'a> $fields for $request {
+				fn $set(&mut self, key: &CustomerEncryptionKey) {
+					self.$algorithm = Some(key.algorithm());
+					self.$key = Some(key.key_base64());
+					self.$key_md5 = Some(key.key_md5());
+				}
+			}
+		)+
+	}
+}
+
+customer_key_fields!(SseCustomerKeyFields, set_sse_customer_key,
+	sse_customer_algorithm, sse_customer_key, sse_customer_key_md5,
+	PutObjectRequest<'a>, GetObjectRequest, HeadObjectRequest, CreateMultipartUploadRequest,
+	UploadPartRequest<'a>, CopyObjectRequest, UploadPartCopyRequest);
+
+customer_key_fields!(CopySourceSseCustomerKeyFields, set_copy_source_sse_customer_key,
+	copy_source_sse_customer_algorithm, copy_source_sse_customer_key, copy_source_sse_customer_key_md5,
+	CopyObjectRequest, UploadPartCopyRequest);
+
 #[cfg(test)]
 mod tests {
+	extern crate chrono;
+
 	use xml::reader::*;
 	use std::io::BufReader;
 	use std::fs::File;
@@ -396,9 +2151,96 @@ mod tests {
 	use super::CompleteMultipartUploadOutputParser;
 	use super::ListMultipartUploadsOutputParser;
 	use super::ListPartsOutputParser;
+	use super::ListObjectsOutputParser;
+	use super::ListObjectVersionsOutputParser;
+	use super::ListObjectsV2OutputParser;
+	use super::GetBucketLocationOutputParser;
+	use super::bucket_region_header;
+	use super::decode_list_objects_v2_output;
+	use super::{object_path, virtual_host_path};
+	use super::{copy_source, copy_part_ranges, expected_copy_e_tag};
+	use signature::SignedRequest;
+	use checksum::{is_checksum_mismatch, ChecksumAlgorithm};
 	use super::*;
 	use xmlutil::*;
 	use regions::*;
+	use std::path::Path;
+	use hyper::header::Headers;
+	use self::chrono::{TimeZone, UTC};
+
+	#[test]
+	fn head_object_headers_parse_large_objects_and_metadata() {
+		let mut headers = Headers::new();
+		headers.set_raw("Content-Length", vec![b"5368709120".to_vec()]);
+		headers.set_raw("Last-Modified", vec![b"Wed, 12 Oct 2009 17:50:00 GMT".to_vec()]);
+		headers.set_raw("Expires", vec![b"0".to_vec()]);
+		headers.set_raw("ETag", vec![b"\"d41d8cd98f00b204e9800998ecf8427e\"".to_vec()]);
+		headers.set_raw("x-amz-meta-camera", vec![b"x100".to_vec()]);
+
+		let mut output = HeadObjectOutput::default();
+		HeadObjectOutputParser::parse_headers(&headers, &mut output).unwrap();
+
+		assert_eq!(output.content_length, 5368709120);
+		assert_eq!(output.e_tag, "\"d41d8cd98f00b204e9800998ecf8427e\"");
+		assert_eq!(output.metadata.get("camera"), Some(&"x100".to_string()));
+		assert_eq!(output.last_modified, Some(UTC.ymd(2009, 10, 12).and_hms(17, 50, 0)));
+		// not a date, but S3 hands back whatever the object was uploaded with
+		assert_eq!(output.expires, None);
+	}
+
+	#[test]
+	fn tagging_payload_xml() {
+		let mut tag = Tag::default();
+		tag.key = "project".to_string();
+		tag.value = "rusoto & co".to_string();
+		let mut tagging = Tagging::default();
+		tagging.tag_set = vec![tag];
+
+		let xml = with_xml_namespace(&TaggingWriter::write_xml("Tagging", &tagging), "http://s3.amazonaws.com/doc/2006-03-01/");
+		assert_eq!(xml, "<Tagging xmlns=\"http://s3.amazonaws.com/doc/2006-03-01/\"><TagSet><Tag><Key>project</Key><Value>rusoto &amp; co</Value></Tag></TagSet></Tagging>");
+	}
+
+	#[test]
+	fn moved_copy_checked_against_source_etag_when_both_are_md5s() {
+		let mut source = HeadObjectOutput::default();
+		source.e_tag = "\"5eb63bbbe01eeed093cb22bb8f5acdc3\"".to_string();
+		let mut copy = HeadObjectOutput::default();
+		copy.e_tag = "\"5eb63bbbe01eeed093cb22bb8f5acdc3\"".to_string();
+		let mut output = CopyObjectOutput::default();
+		output.copy_object_result.e_tag = "\"d41d8cd98f00b204e9800998ecf8427e\"".to_string();
+		assert_eq!(expected_copy_e_tag(&source, &copy, &output), "\"5eb63bbbe01eeed093cb22bb8f5acdc3\"");
+
+		// copying a multipart object in one piece gives it a new ETag
+		source.e_tag = "\"0732917abc3288784e318ac0aab1757a-2\"".to_string();
+		assert_eq!(expected_copy_e_tag(&source, &copy, &output), "\"d41d8cd98f00b204e9800998ecf8427e\"");
+
+		source.e_tag = copy.e_tag.clone();
+		copy.server_side_encryption = "aws:kms".to_string();
+		assert_eq!(expected_copy_e_tag(&source, &copy, &output), "\"d41d8cd98f00b204e9800998ecf8427e\"");
+	}
+
+	#[test]
+	fn unset_members_left_out_of_payload_xml() {
+		let mut versioning = VersioningConfiguration::default();
+		versioning.status = "Enabled".to_string();
+		let xml = VersioningConfigurationWriter::write_xml("VersioningConfiguration", &versioning);
+		assert_eq!(xml, "<VersioningConfiguration><Status>Enabled</Status></VersioningConfiguration>");
+
+		let mut expiration = LifecycleExpiration::default();
+		expiration.days = 30;
+		let xml = LifecycleExpirationWriter::write_xml("Expiration", &expiration);
+		assert_eq!(xml, "<Expiration><Days>30</Days></Expiration>");
+	}
+
+	#[test]
+	fn grantee_payload_xml_has_type_attribute() {
+		let mut grantee = Grantee::default();
+		grantee.foo_type = "CanonicalUser".to_string();
+		grantee.id = Some("abc123".to_string());
+
+		let xml = GranteeWriter::write_xml("Grantee", &grantee);
+		assert_eq!(xml, "<Grantee xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" xsi:type=\"CanonicalUser\"><ID>abc123</ID></Grantee>");
+	}
 
 	#[test]
 	fn list_buckets_happy_path() {
@@ -571,6 +2413,240 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn list_objects_contents_and_common_prefixes() {
+		let file = File::open("tests/sample-data/s3_list_objects.xml").unwrap();
+	    let file = BufReader::new(file);
+	    let mut my_parser  = EventReader::new(file);
+	    let my_stack = my_parser.events().peekable();
+	    let mut reader = XmlResponseFromFile::new(my_stack);
+		reader.next(); // xml start node
+		let result = ListObjectsOutputParser::parse_xml("ListBucketResult", &mut reader).unwrap();
+
+		assert!(result.is_truncated);
+		assert_eq!(result.next_marker, "photos/2015/");
+		assert_eq!(result.contents.len(), 1);
+		assert_eq!(result.contents[0].key, "photos/cat.jpg");
+		assert_eq!(result.contents[0].size, 5242880);
+		assert_eq!(result.contents[0].e_tag, "\"ddcaa99616d7cd06d0a5abfef6ccebbb\"");
+		assert_eq!(result.common_prefixes.len(), 1);
+		assert_eq!(result.common_prefixes[0].prefix, "photos/2015/");
+	}
+
+	#[test]
+	fn list_objects_v2_decodes_url_encoded_keys() {
+		let file = File::open("tests/sample-data/s3_list_objects_v2.xml").unwrap();
+	    let file = BufReader::new(file);
+	    let mut my_parser  = EventReader::new(file);
+	    let my_stack = my_parser.events().peekable();
+	    let mut reader = XmlResponseFromFile::new(my_stack);
+		reader.next(); // xml start node
+		let mut result = ListObjectsV2OutputParser::parse_xml("ListBucketResult", &mut reader).unwrap();
+		decode_list_objects_v2_output(&mut result);
+
+		assert!(result.is_truncated);
+		assert_eq!(result.key_count, 2);
+		assert_eq!(result.next_continuation_token, "1ueGcxLPRx1Tr/XYExHnhbYLgveDs2J/wm36Hy4vbOwM=");
+		assert_eq!(result.prefix, "photos/");
+		assert_eq!(result.contents[0].key, "photos/summer 2015/beach.jpg");
+		assert_eq!(result.contents[1].key, "photos/caf\u{e9}.jpg");
+	}
+
+	#[test]
+	fn url_decode_keeps_malformed_escapes() {
+		assert_eq!(url_decode("a%2Fb+c%2"), "a/b c%2");
+		assert_eq!(url_decode("100%zz"), "100%zz");
+	}
+
+	#[test]
+	fn list_object_versions_keeps_interleaved_delete_markers() {
+		let file = File::open("tests/sample-data/s3_list_object_versions.xml").unwrap();
+	    let file = BufReader::new(file);
+	    let mut my_parser  = EventReader::new(file);
+	    let my_stack = my_parser.events().peekable();
+	    let mut reader = XmlResponseFromFile::new(my_stack);
+		reader.next(); // xml start node
+		let result = ListObjectVersionsOutputParser::parse_xml("ListVersionsResult", &mut reader).unwrap();
+
+		assert_eq!(result.next_key_marker, "notes.txt");
+		assert_eq!(result.next_version_id_marker, "3HL4kqtJlcpXroDTDmJ.rmSpXd3dIbrHY");
+		assert_eq!(result.versions.len(), 2);
+		assert_eq!(result.versions[0].key, "cat.jpg");
+		assert_eq!(result.versions[1].version_id, "QUpfdndhfd8438MNFDN93jdnJFkdmqnh893");
+		assert!(!result.versions[1].is_latest);
+		assert_eq!(result.delete_markers.len(), 1);
+		assert_eq!(result.delete_markers[0].key, "notes.txt");
+		assert!(result.delete_markers[0].is_latest);
+	}
+
+	#[test]
+	fn bucket_names_that_can_be_host_names() {
+		assert!(is_dns_compatible_bucket_name("my-bucket"));
+		assert!(is_dns_compatible_bucket_name("logs.example.com"));
+		assert!(!is_dns_compatible_bucket_name("My_Bucket"));
+		assert!(!is_dns_compatible_bucket_name("ab"));
+		assert!(!is_dns_compatible_bucket_name("-bucket"));
+		assert!(!is_dns_compatible_bucket_name("bucket..name"));
+		assert!(!is_dns_compatible_bucket_name("192.168.5.4"));
+
+		assert!(is_virtual_hostable_bucket_name("my-bucket"));
+		assert!(!is_virtual_hostable_bucket_name("logs.example.com"));
+	}
+
+	#[test]
+	fn endpoint_hostnames() {
+		let mut endpoint = S3Endpoint::default();
+		assert_eq!(endpoint.hostname(&Region::UsEast1), "s3.amazonaws.com");
+		assert_eq!(endpoint.hostname(&Region::EuWest1), "s3-eu-west-1.amazonaws.com");
+
+		endpoint.dualstack = true;
+		assert_eq!(endpoint.hostname(&Region::EuWest1), "s3.dualstack.eu-west-1.amazonaws.com");
+
+		endpoint.accelerate = true;
+		assert_eq!(endpoint.hostname(&Region::EuWest1), "s3-accelerate.dualstack.amazonaws.com");
+		assert_eq!(endpoint.service_hostname(&Region::EuWest1), "s3.dualstack.eu-west-1.amazonaws.com");
+	}
+
+	#[test]
+	fn auto_addressing_falls_back_to_path_style() {
+		let region = Region::UsWest2;
+		let endpoint = S3Endpoint::default();
+
+		let mut request = SignedRequest::new("GET", "s3", &region, "/my-bucket/photos/cat.jpg");
+		endpoint.address(&mut request, "my-bucket").unwrap();
+		assert_eq!(request.get_hostname(), "my-bucket.s3-us-west-2.amazonaws.com");
+		assert_eq!(request.get_path(), "/photos/cat.jpg");
+
+		let mut request = SignedRequest::new("GET", "s3", &region, "/my-bucket");
+		endpoint.address(&mut request, "my-bucket").unwrap();
+		assert_eq!(request.get_path(), "/");
+
+		let mut request = SignedRequest::new("GET", "s3", &region, "/logs.example.com/today.log");
+		endpoint.address(&mut request, "logs.example.com").unwrap();
+		assert_eq!(request.get_hostname(), "s3-us-west-2.amazonaws.com");
+		assert_eq!(request.get_path(), "/logs.example.com/today.log");
+	}
+
+	#[test]
+	fn explicit_addressing_styles() {
+		let region = Region::UsEast1;
+		let mut endpoint = S3Endpoint::default();
+		endpoint.addressing_style = AddressingStyle::PathStyle;
+		let mut request = SignedRequest::new("GET", "s3", &region, "/my-bucket/cat.jpg");
+		endpoint.address(&mut request, "my-bucket").unwrap();
+		assert_eq!(request.get_hostname(), "s3.amazonaws.com");
+		assert_eq!(request.get_path(), "/my-bucket/cat.jpg");
+
+		endpoint.addressing_style = AddressingStyle::VirtualHosted;
+		let mut request = SignedRequest::new("GET", "s3", &region, "/logs.example.com/today.log");
+		endpoint.address(&mut request, "logs.example.com").unwrap();
+		assert_eq!(request.get_hostname(), "logs.example.com.s3.amazonaws.com");
+		assert_eq!(request.get_path(), "/today.log");
+	}
+
+	#[test]
+	fn customer_key_headers() {
+		assert!(CustomerEncryptionKey::new(b"too short").is_err());
+
+		let key = CustomerEncryptionKey::new(&[0x4b; 32]).unwrap();
+		let mut request = PutObjectRequest::default();
+		request.set_sse_customer_key(&key);
+		assert_eq!(request.sse_customer_algorithm, Some("AES256".to_string()));
+		assert_eq!(request.sse_customer_key, Some("S0tLS0tLS0tLS0tLS0tLS0tLS0tLS0tLS0tLS0tLS0s=".to_string()));
+		assert_eq!(request.sse_customer_key_md5, Some(key.key_md5()));
+		assert!(!format!("{:?}", key).contains(&key.key_base64()));
+
+		let mut copy = CopyObjectRequest::default();
+		copy.set_copy_source_sse_customer_key(&key);
+		assert_eq!(copy.copy_source_sse_customer_key, request.sse_customer_key);
+		assert_eq!(copy.sse_customer_key, None);
+	}
+
+	#[test]
+	fn copy_sources_are_encoded() {
+		assert_eq!(copy_source("my-bucket", "photos/2015/a cat+dog.jpg"), "/my-bucket/photos/2015/a%20cat%2Bdog.jpg");
+	}
+
+	#[test]
+	fn copy_part_ranges_cover_the_object() {
+		let gigabyte = 1024 * 1024 * 1024;
+		let ranges = copy_part_ranges(5 * gigabyte + 1);
+		assert_eq!(ranges.len(), 11);
+		assert_eq!(ranges[0], (0, 512 * 1024 * 1024 - 1));
+		assert_eq!(ranges[1].0, 512 * 1024 * 1024);
+		assert_eq!(ranges[10], (5 * gigabyte, 5 * gigabyte));
+
+		// 5 TB doesn't fit in 10,000 parts of 512 MB
+		let size = 5 * 1024 * gigabyte;
+		let ranges = copy_part_ranges(size);
+		assert!(ranges.len() <= 10000);
+		assert_eq!(ranges.last().unwrap().1, size - 1);
+	}
+
+	#[test]
+	fn moving_to_another_region_keeps_the_path() {
+		let region = Region::UsEast1;
+		let endpoint = S3Endpoint::default();
+		let mut request = SignedRequest::new("GET", "s3", &region, "/my-bucket/cat.jpg");
+		endpoint.address(&mut request, "my-bucket").unwrap();
+		endpoint.move_to_region(&mut request, "my-bucket", Region::EuCentral1).unwrap();
+		assert_eq!(request.get_hostname(), "my-bucket.s3-eu-central-1.amazonaws.com");
+		assert_eq!(request.get_path(), "/cat.jpg");
+		assert_eq!(*request.get_region(), Region::EuCentral1);
+
+		let mut request = SignedRequest::new("GET", "s3", &region, "/logs.example.com/today.log");
+		endpoint.address(&mut request, "logs.example.com").unwrap();
+		endpoint.move_to_region(&mut request, "logs.example.com", Region::ApSoutheast2).unwrap();
+		assert_eq!(request.get_hostname(), "s3-ap-southeast-2.amazonaws.com");
+		assert_eq!(request.get_path(), "/logs.example.com/today.log");
+	}
+
+	#[test]
+	fn bucket_region_from_response_header() {
+		let mut headers = Headers::new();
+		assert_eq!(bucket_region_header(&headers).unwrap(), None);
+
+		headers.set_raw("x-amz-bucket-region", vec![b"eu-west-1".to_vec()]);
+		assert_eq!(bucket_region_header(&headers).unwrap(), Some(Region::EuWest1));
+
+		headers.set_raw("x-amz-bucket-region", vec![b"mars-north-1".to_vec()]);
+		assert!(bucket_region_header(&headers).is_err());
+	}
+
+	#[test]
+	fn bucket_location_constraints() {
+		for &(path, region) in [("tests/sample-data/s3_get_bucket_location.xml", Region::EuWest1),
+				("tests/sample-data/s3_get_bucket_location_us_east_1.xml", Region::UsEast1)].iter() {
+			let file = File::open(path).unwrap();
+			let file = BufReader::new(file);
+			let mut my_parser  = EventReader::new(file);
+			let my_stack = my_parser.events().peekable();
+			let mut reader = XmlResponseFromFile::new(my_stack);
+			reader.next(); // xml start node
+			let result = GetBucketLocationOutputParser::parse_xml("LocationConstraint", &mut reader).unwrap();
+			assert_eq!(bucket_region_from_location_constraint(&result.location_constraint).unwrap(), region);
+		}
+		assert_eq!(bucket_region_from_location_constraint("ap-northeast-1").unwrap(), Region::ApNortheast1);
+		assert!(bucket_region_from_location_constraint("mars-north-1").is_err());
+	}
+
+	#[test]
+	fn acceleration_needs_a_host_name_bucket() {
+		let mut endpoint = S3Endpoint::default();
+		endpoint.accelerate = true;
+		assert!(endpoint.uses_virtual_host("my-bucket").unwrap());
+		assert!(endpoint.uses_virtual_host("logs.example.com").is_err());
+
+		endpoint.addressing_style = AddressingStyle::PathStyle;
+		assert!(endpoint.uses_virtual_host("my-bucket").is_err());
+	}
+
+	#[test]
+	fn object_paths_are_encoded() {
+		assert_eq!(object_path("my-bucket", "photos/summer 2015.jpg"), "/my-bucket/photos/summer%202015.jpg");
+		assert_eq!(virtual_host_path("/my-bucketlist/a", "my-bucket"), "/my-bucketlist/a");
+	}
+
 	#[test]
 	fn create_bucket_constrained_to_region() {
 		let region = Region::UsWest2;
@@ -606,4 +2682,69 @@ mod tests {
 			false => return,
 		}
 	}
+
+	#[test]
+	fn download_temp_file_is_next_to_destination() {
+		let temp = super::download_temp_path(Path::new("/data/objects/backup.tar"));
+		assert_eq!(temp.parent(), Some(Path::new("/data/objects")));
+		let name = temp.file_name().unwrap().to_string_lossy().into_owned();
+		assert!(name.starts_with(".backup.tar."));
+		assert!(name.ends_with(".part"));
+	}
+
+	#[test]
+	fn download_length_must_match_content_length() {
+		assert!(super::check_download_length(1024, 1024).is_ok());
+		assert!(super::check_download_length(1000, 1024).is_err());
+	}
+
+	#[test]
+	fn delete_objects_reports_deleted_keys_and_errors() {
+		let file = File::open("tests/sample-data/s3_delete_objects.xml").unwrap();
+	    let file = BufReader::new(file);
+	    let mut my_parser  = EventReader::new(file);
+	    let my_stack = my_parser.events().peekable();
+	    let mut reader = XmlResponseFromFile::new(my_stack);
+		reader.next(); // xml start node
+		let result = DeleteObjectsOutputParser::parse_xml("DeleteResult", &mut reader).unwrap();
+
+		assert_eq!(result.deleted.len(), 2);
+		assert_eq!(result.deleted[0].key, "sample1.txt");
+		assert!(result.deleted[1].delete_marker);
+		assert_eq!(result.deleted[1].delete_marker_version_id, "NeQt5xeFTfgPJD8B4CGWnkSLtluMr11s");
+		assert_eq!(result.errors.len(), 1);
+		assert_eq!(result.errors[0].key, "sample3.txt");
+		assert_eq!(result.errors[0].code, "AccessDenied");
+
+		assert_eq!(check_deleted("bucket", &result.errors).unwrap_err().message(),
+			"Couldn't delete 1 objects from bucket, EG: sample3.txt: AccessDenied Access Denied");
+		assert!(check_deleted("bucket", &[]).is_ok());
+	}
+
+	#[test]
+	fn downloads_are_checked_against_checksum_or_etag() {
+		let mut output = GetObjectOutput::default();
+		output.e_tag = "\"5eb63bbbe01eeed093cb22bb8f5acdc3\"".to_string();
+		assert!(verify_download("key", &output, "5eb63bbbe01eeed093cb22bb8f5acdc3", None).is_ok());
+		let mismatch = verify_download("key", &output, "d41d8cd98f00b204e9800998ecf8427e", None).unwrap_err();
+		assert!(is_checksum_mismatch(&mismatch));
+
+		// ranges of the object can't be checked
+		output.content_range = "bytes 0-4/11".to_string();
+		assert!(verify_download("key", &output, "d41d8cd98f00b204e9800998ecf8427e", None).is_ok());
+		output.content_range = String::new();
+
+		// KMS encrypted objects don't have MD5 ETags, but can have checksums
+		output.server_side_encryption = "aws:kms".to_string();
+		output.e_tag = "\"a9f0c1b3a0e8e0c5d1e3f2b4c6d8e0f2\"".to_string();
+		assert!(verify_download("key", &output, "5eb63bbbe01eeed093cb22bb8f5acdc3", None).is_ok());
+		output.checksum_crc32_c = "4waSgw==".to_string();
+		assert_eq!(Some((ChecksumAlgorithm::Crc32c, "4waSgw==")), download_checksum(&output));
+		assert!(verify_download("key", &output, "", Some("4waSgw==")).is_ok());
+		assert!(verify_download("key", &output, "", Some("AAAAAA==")).is_err());
+
+		// checksums of multipart uploads are checksums of checksums
+		output.checksum_crc32_c = "4waSgw==-3".to_string();
+		assert_eq!(None, download_checksum(&output));
+	}
 }
@@ -8,22 +8,28 @@
 
 extern crate regex;
 use credentials::AWSCredentials;
+use util::{zero_memory, zero_string};
 use hyper::client::Response;
 use hyper::status::StatusCode;
-use openssl::crypto::hash::Type::SHA256;
-use openssl::crypto::hash::hash;
+use openssl::crypto::hash::Type::{MD5, SHA256};
+use openssl::crypto::hash::{hash, Hasher};
 use openssl::crypto::hmac::hmac;
 use params::Params;
+use serialize::base64::{ToBase64, STANDARD};
 use serialize::hex::ToHex;
 use std::ascii::AsciiExt;
+use std::cell::{RefCell, RefMut};
 use std::collections::BTreeMap;
 use std::collections::btree_map::Entry;
+use std::fmt;
+use std::io::{self, Read, Seek, SeekFrom, Write};
 use std::str;
+use std::sync::Arc;
 use time::Tm;
 use time::now_utc;
 use url::percent_encoding::{percent_encode_to, FORM_URLENCODED_ENCODE_SET};
 use regions::*;
-use request::send_request;
+use request::{send_request, RequestHook, RequestHooks};
 use xmlutil::*;
 use error::*;
 use xml::reader::*;
@@ -31,37 +37,211 @@ use xml::reader::*;
 // use std::io::Read;
 
 const HTTP_TEMPORARY_REDIRECT: StatusCode = StatusCode::TemporaryRedirect;
+/// How many temporary redirects `sign_and_execute` follows before handing back the response.
+const MAXIMUM_TEMPORARY_REDIRECTS: u32 = 5;
+const PAYLOAD_HASH_BUFFER_SIZE: usize = 64 * 1024;
+
+/// A readable, seekable request body, EG: a `File`
+pub trait SeekableRead: Read + Seek {}
+
+impl<T: Read + Seek> SeekableRead for T {}
+
+/// A request body streamed from a reader instead of held in memory.
+///
+/// Creating one reads the body once to compute its length, SHA-256 (for the signature) and MD5
+/// (for `Content-MD5`), then seeks back.  The reader is rewound again every time the request is
+/// sent, so redirected requests resend the whole body.
+pub struct PayloadStream<'a> {
+	reader: RefCell<&'a mut (SeekableRead + 'a)>,
+	start: u64,
+	length: u64,
+	sha256: String,
+	md5: Vec<u8>,
+}
+
+impl <'a> PayloadStream<'a> {
+	/// Body made of everything from the reader's current position to its end
+	pub fn new(reader: &'a mut (SeekableRead + 'a)) -> io::Result<PayloadStream<'a>> {
+		PayloadStream::hash_reader(reader, None)
+	}
+
+	/// Body made of the next `length` bytes from the reader's current position
+	pub fn with_length(reader: &'a mut (SeekableRead + 'a), length: u64) -> io::Result<PayloadStream<'a>> {
+		PayloadStream::hash_reader(reader, Some(length))
+	}
+
+	fn hash_reader(reader: &'a mut (SeekableRead + 'a), length: Option<u64>) -> io::Result<PayloadStream<'a>> {
+		let start = try!(reader.seek(SeekFrom::Current(0)));
+		let mut sha256 = Hasher::new(SHA256);
+		let mut md5 = Hasher::new(MD5);
+		let mut buffer = vec![0u8; PAYLOAD_HASH_BUFFER_SIZE];
+		let mut total: u64 = 0;
+
+		loop {
+			let wanted = match length {
+				Some(length) if length - total < buffer.len() as u64 => (length - total) as usize,
+				_ => buffer.len(),
+			};
+			if wanted == 0 {
+				break;
+			}
+			let read = try!(reader.read(&mut buffer[..wanted]));
+			if read == 0 {
+				break;
+			}
+			try!(sha256.write_all(&buffer[..read]));
+			try!(md5.write_all(&buffer[..read]));
+			total += read as u64;
+		}
+
+		if let Some(length) = length {
+			if total != length {
+				return Err(io::Error::new(io::ErrorKind::UnexpectedEof,
+					format!("Payload ended after {} of {} bytes", total, length)));
+			}
+		}
+		try!(reader.seek(SeekFrom::Start(start)));
+
+		Ok(PayloadStream {
+			reader: RefCell::new(reader),
+			start: start,
+			length: total,
+			sha256: sha256.finish().to_hex().to_string(),
+			md5: md5.finish(),
+		})
+	}
+
+	/// Number of bytes in the body
+	pub fn len(&self) -> u64 {
+		self.length
+	}
+
+	/// Hex encoded SHA-256 of the body, as sent in `x-amz-content-sha256`
+	pub fn sha256(&self) -> &str {
+		&self.sha256
+	}
+
+	/// Base64 encoded MD5 of the body, as sent in `Content-MD5`
+	pub fn content_md5(&self) -> String {
+		self.md5.to_base64(STANDARD)
+	}
+
+	/// Hex encoded MD5 of the body: the ETag S3 reports for it when it's uploaded in one piece
+	/// without KMS encryption
+	pub fn md5_hex(&self) -> String {
+		self.md5.to_hex().to_string()
+	}
+
+	/// The underlying reader, positioned at the start of the body
+	pub fn rewind(&self) -> io::Result<RefMut<&'a mut (SeekableRead + 'a)>> {
+		let mut reader = self.reader.borrow_mut();
+		try!(reader.seek(SeekFrom::Start(self.start)));
+		Ok(reader)
+	}
+}
 
 /// A data structure for all the elements of an HTTP request that are involved in
 /// the Amazon Signature Version 4 signing process
-#[derive(Debug)]
+///
+/// `Debug` output leaves out the payload and redacts headers carrying secrets, so requests can be
+/// logged safely.  Those header values are overwritten with zeros when the request is dropped.
 pub struct SignedRequest<'a> {
 	method: String,
 	service: String,
-	region: &'a Region,
+	region: Region,
 	path: String,
 	headers: BTreeMap<String, Vec<Vec<u8>>>,
 	params: Params,
+	scheme: String,
 	hostname: Option<String>,
 	payload: Option<&'a [u8]>,
+	payload_stream: Option<PayloadStream<'a>>,
 	canonical_query_string: String,
 	canonical_uri: String,
+	hooks: RequestHooks,
+	attempt: u32,
+}
+
+impl <'a> fmt::Debug for SignedRequest<'a> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut headers = BTreeMap::new();
+		for (key, values) in self.headers.iter() {
+			headers.insert(key.to_string(), printable_header_value(key, values));
+		}
+		let payload = match (self.payload, &self.payload_stream) {
+			(Some(payload), _) => format!("Some({} bytes)", payload.len()),
+			(None, &Some(ref stream)) => format!("Some({} bytes, streamed)", stream.len()),
+			(None, &None) => "None".to_string(),
+		};
+
+		f.debug_struct("SignedRequest")
+			.field("method", &self.method)
+			.field("service", &self.service)
+			.field("region", &self.region)
+			.field("path", &self.path)
+			.field("headers", &headers)
+			.field("params", &self.params)
+			.field("scheme", &self.scheme)
+			.field("hostname", &self.hostname)
+			.field("payload", &payload)
+			.field("canonical_query_string", &self.canonical_query_string)
+			.field("canonical_uri", &self.canonical_uri)
+			.field("attempt", &self.attempt)
+			.finish()
+	}
+}
+
+impl <'a> Drop for SignedRequest<'a> {
+	fn drop(&mut self) {
+		for (key, values) in self.headers.iter_mut() {
+			if is_sensitive_header(key) {
+				for value in values.iter_mut() {
+					zero_memory(value);
+				}
+			}
+		}
+	}
+}
+
+/// Headers whose values must never be logged.
+pub fn is_sensitive_header(header: &str) -> bool {
+	["authorization", "x-amz-security-token", "x-amz-server-side-encryption-customer-key",
+		"x-amz-copy-source-server-side-encryption-customer-key"].contains(&&*header.to_ascii_lowercase())
+}
+
+/// Header value suitable for logs: secrets are replaced with a placeholder.
+pub fn printable_header_value(header: &str, values: &Vec<Vec<u8>>) -> String {
+	if is_sensitive_header(header) {
+		return "**redacted**".to_string();
+	}
+	let mut printable = String::new();
+	for value in values {
+		if printable.len() > 0 {
+			printable.push(',');
+		}
+		printable.push_str(&String::from_utf8_lossy(value));
+	}
+	printable
 }
 
 impl <'a> SignedRequest <'a> {
 	/// Default constructor
-	pub fn new<'b>(method: &str, service: &str, region: &'a Region, path: &str) -> SignedRequest<'a> {
+	pub fn new<'b>(method: &str, service: &str, region: &Region, path: &str) -> SignedRequest<'a> {
 		SignedRequest {
 			method: method.to_string(),
 			service: service.to_string(),
-			region: region,
+			region: *region,
 			path: path.to_string(),
 			headers: BTreeMap::new(),
 			params: Params::new(),
+			scheme: "https".to_string(),
 			hostname: None,
 			payload: None,
+			payload_stream: None,
 			canonical_query_string: String::new(),
 			canonical_uri: String::new(),
+			hooks: Vec::new(),
+			attempt: 1,
 		 }
 	}
 
@@ -69,10 +249,61 @@ impl <'a> SignedRequest <'a> {
 		self.hostname = hostname;
 	}
 
+	/// Sends over `http` rather than `https`, EG: to a local test server.
+	pub fn set_scheme(&mut self, scheme: &str) {
+		self.scheme = scheme.to_string();
+	}
+
+	pub fn get_scheme(&self) -> &str {
+		&self.scheme
+	}
+
+	pub fn get_region(&self) -> &Region {
+		&self.region
+	}
+
+	/// Signs the request for another region from now on.  The host name isn't changed.
+	pub fn set_region(&mut self, region: Region) {
+		self.region = region;
+	}
+
+	pub fn get_path(&self) -> &str {
+		&self.path
+	}
+
+	pub fn set_path(&mut self, path: &str) {
+		self.path = path.to_string();
+	}
+
 	pub fn set_payload(&mut self, payload: Option<&'a [u8]>) {
 		self.payload = payload;
 	}
 
+	/// Streams the body from a reader instead of sending an in-memory payload
+	pub fn set_payload_stream(&mut self, payload: PayloadStream<'a>) {
+		self.payload = None;
+		self.payload_stream = Some(payload);
+	}
+
+	/// Hooks to call around sending this request, usually the ones of the client sending it.
+	pub fn set_hooks(&mut self, hooks: &[Arc<RequestHook>]) {
+		self.hooks = hooks.to_vec();
+	}
+
+	pub fn get_hooks(&self) -> &[Arc<RequestHook>] {
+		&self.hooks
+	}
+
+	/// Which attempt at sending this request is being made, starting at 1.
+	pub fn get_attempt(&self) -> u32 {
+		self.attempt
+	}
+
+	/// Counts the next send as a new attempt, EG: when retrying after a redirect.
+	pub fn next_attempt(&mut self) {
+		self.attempt += 1;
+	}
+
 	pub fn get_method(&self) -> &str {
 		&self.method
 	}
@@ -89,6 +320,10 @@ impl <'a> SignedRequest <'a> {
 		self.payload
 	}
 
+	pub fn get_payload_stream(&self) -> Option<&PayloadStream<'a>> {
+		self.payload_stream.as_ref()
+	}
+
 	pub fn get_headers(&'a self) -> &'a BTreeMap<String, Vec<Vec<u8>>> {
 		&self.headers
 	}
@@ -103,7 +338,13 @@ impl <'a> SignedRequest <'a> {
 	// If the key exists in headers, set it to blank/unoccupied:
 	pub fn remove_header(&mut self, key: &str) {
 		let key_lower = key.to_ascii_lowercase().to_string();
-		self.headers.remove(&key_lower);
+		if let Some(mut values) = self.headers.remove(&key_lower) {
+			if is_sensitive_header(&key_lower) {
+				for value in values.iter_mut() {
+					zero_memory(value);
+				}
+			}
+		}
 	}
 
 	/// Add a value to the array of headers for the specified key.
@@ -135,7 +376,30 @@ impl <'a> SignedRequest <'a> {
 	/// Calculate the signature from the credentials provided and the request data
 	/// Add the calculated signature to the request headers and execute it
 	/// Return the hyper HTTP response
-	pub fn sign_and_execute(&mut self, creds: &AWSCredentials) -> Response {
+	///
+	/// Temporary redirects are followed up to a limit; the last redirect is returned after that.
+	pub fn sign_and_execute(&mut self, creds: &AWSCredentials) -> Result<Response, AWSError> {
+		loop {
+			let response = try!(self.sign_and_send(creds));
+			if response.status != HTTP_TEMPORARY_REDIRECT || self.attempt > MAXIMUM_TEMPORARY_REDIRECTS {
+				return Ok(response);
+			}
+			// extract location from response, modify request and re-sign and resend.
+			let new_hostname = try!(extract_s3_redirect_location(response));
+			self.set_hostname(Some(new_hostname.to_string()));
+			self.attempt += 1;
+		}
+	}
+
+	/// Signs the request and sends it once, without following redirects.
+	pub fn sign_and_send(&mut self, creds: &AWSCredentials) -> Result<Response, AWSError> {
+		self.sign(creds);
+		send_request(&self)
+	}
+
+	/// Adds the host, date, payload hash and authorization headers, replacing any left from an
+	/// earlier signing.
+	fn sign(&mut self, creds: &AWSCredentials) {
 		let hostname = match self.hostname {
 			Some(ref h) => h.to_string(),
 			None => build_hostname(&self.service, &self.region)
@@ -164,8 +428,23 @@ impl <'a> SignedRequest <'a> {
 
 		let canonical_request : String;
 
-		match self.payload {
-			None => {
+		let streamed = self.payload_stream.as_ref().map(|stream| (stream.sha256().to_string(), stream.len()));
+
+		match (self.payload, streamed) {
+			(None, Some((payload_hash, payload_length))) => {
+				canonical_request = format!("{}\n{}\n{}\n{}\n{}\n{}",
+					&self.method,
+					self.canonical_uri,
+					self.canonical_query_string,
+					canonical_headers,
+					signed_headers,
+					&payload_hash);
+				self.remove_header("x-amz-content-sha256");
+				self.add_header("x-amz-content-sha256", &payload_hash);
+				self.remove_header("content-length");
+				self.add_header("content-length", &format!("{}", payload_length));
+			}
+			(None, None) => {
 				canonical_request = format!("{}\n{}\n{}\n{}\n{}\n{}",
 					&self.method,
 					self.canonical_uri,
@@ -176,7 +455,7 @@ impl <'a> SignedRequest <'a> {
 				self.remove_header("x-amz-content-sha256");
 				self.add_header("x-amz-content-sha256", &to_hexdigest_from_string(""));
 			}
-			Some(payload) => {
+			(Some(payload), _) => {
 				// This is hashing the payload twice, booo:
 				canonical_request = format!("{}\n{}\n{}\n{}\n{}\n{}",
 					&self.method,
@@ -191,8 +470,9 @@ impl <'a> SignedRequest <'a> {
 				self.add_header("content-length", &format!("{}", payload.len()));
 			}
 		}
-		self.remove_header("content-type");
-		self.add_header("content-type", "application/octet-stream");
+		if !self.headers.contains_key("content-type") {
+			self.add_header("content-type", "application/octet-stream");
+		}
 
 		// use the hashed canonical request to build the string to sign
 		let hashed_canonical_request = to_hexdigest_from_string(&canonical_request);
@@ -200,39 +480,33 @@ impl <'a> SignedRequest <'a> {
 		let string_to_sign = string_to_sign(date, &hashed_canonical_request, &scope);
 
 		// construct the signing key and sign the string with it
-		let signing_key = signing_key(&creds.get_aws_secret_key(), date, &region_in_aws_format(&self.region), &self.service);
-		let signature = signature(&string_to_sign, signing_key);
+		let mut signing_key = signing_key(&creds.get_aws_secret_key(), date, &region_in_aws_format(&self.region), &self.service);
+		let signature = signature(&string_to_sign, &signing_key);
+		zero_memory(&mut signing_key);
 
 		// build the actual auth header
 		let auth_header = format!("AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
 	               &creds.get_aws_access_key_id(), scope, signed_headers, signature);
 	   self.remove_header("authorization");
 		self.add_header("authorization", &auth_header);
-
-		let response = send_request(&self);
-
-		if response.status == HTTP_TEMPORARY_REDIRECT {
-			// extract location from response, modify request and re-sign and resend.
-			let new_hostname = extract_s3_redirect_location(response).unwrap();
-			self.set_hostname(Some(new_hostname.to_string()));
-
-			// This does a lot of appending and not clearing/creation, so we'll have to do that ourselves:
-			return self.sign_and_execute(creds);
-		}
-
-		response
 	}
 }
 
-fn signature(string_to_sign: &str, signing_key: Vec<u8>) -> String {
-	hmac(SHA256, &signing_key, string_to_sign.as_bytes()).to_hex().to_string()
+fn signature(string_to_sign: &str, signing_key: &[u8]) -> String {
+	hmac(SHA256, signing_key, string_to_sign.as_bytes()).to_hex().to_string()
 }
 
 fn signing_key(secret: &str, date: Tm, region: &str, service: &str) -> Vec<u8> {
-	let k_date = hmac(SHA256, format!("AWS4{}", secret).as_bytes(), date.strftime("%Y%m%d").unwrap().to_string().as_bytes());
-	let k_region = hmac(SHA256, &k_date, region.as_bytes());
-	let k_service = hmac(SHA256, &k_region, service.as_bytes());
-	hmac(SHA256, &k_service, "aws4_request".as_bytes())
+	let mut secret_key = format!("AWS4{}", secret);
+	let mut k_date = hmac(SHA256, secret_key.as_bytes(), date.strftime("%Y%m%d").unwrap().to_string().as_bytes());
+	zero_string(&mut secret_key);
+	let mut k_region = hmac(SHA256, &k_date, region.as_bytes());
+	let mut k_service = hmac(SHA256, &k_region, service.as_bytes());
+	let signing_key = hmac(SHA256, &k_service, "aws4_request".as_bytes());
+	zero_memory(&mut k_date);
+	zero_memory(&mut k_region);
+	zero_memory(&mut k_service);
+	signing_key
 }
 
 /// Mark string as AWS4-HMAC-SHA256 hashed
@@ -247,13 +521,13 @@ fn signed_headers(headers: &BTreeMap<String, Vec<Vec<u8>>>) -> String {
 	let mut signed = String::new();
 
 	for (key,_) in headers.iter() {
-		if signed.len() > 0 {
-			signed.push(';')
-		}
-
 		if skipped_headers(key) {
 			continue;
 		}
+
+		if signed.len() > 0 {
+			signed.push(';')
+		}
 		signed.push_str(&key.to_ascii_lowercase());
 	}
     signed
@@ -316,6 +590,29 @@ fn build_canonical_query_string(params: &Params) -> String {
 	output
 }
 
+/// Percent-encode a URI path for AWS, leaving the '/' separators alone, EG: an S3 object key
+pub fn encode_uri_path(path: &str) -> String {
+	encode_uri(path, false)
+}
+
+/// Percent-encode a single URI path segment for AWS, including any '/', EG: an S3 bucket name
+pub fn encode_uri_strict(segment: &str) -> String {
+	encode_uri(segment, true)
+}
+
+// Everything but unreserved characters gets encoded, as Signature V4 expects
+fn encode_uri(uri: &str, encode_slash: bool) -> String {
+	let mut encoded = String::with_capacity(uri.len());
+	for &byte in uri.as_bytes().iter() {
+		match byte {
+			b'A'...b'Z' | b'a'...b'z' | b'0'...b'9' | b'-' | b'_' | b'.' | b'~' => encoded.push(byte as char),
+			b'/' if !encode_slash => encoded.push('/'),
+			_ => encoded.push_str(&format!("%{:02X}", byte)),
+		}
+	}
+	encoded
+}
+
 #[inline]
 fn byte_serialize(input: &str, output: &mut String) {
 	for &byte in input.as_bytes().iter() {
@@ -349,7 +646,7 @@ fn build_hostname(service: &str, region: &Region) -> String {
 }
 
 /// extract_s3_redirect_location takes a Hyper Response and attempts to pull out the temporary endpoint.
-fn extract_s3_redirect_location(response: Response) -> Result<String, AWSError> {
+pub fn extract_s3_redirect_location(response: Response) -> Result<String, AWSError> {
 	// Double checking this feels like belts and suspenders since we're checking the status code
 	// before calling this.  Remove this check?
 
@@ -400,13 +697,20 @@ fn extract_s3_temporary_endpoint_from_xml<'a, T: Peek + Next>(stack: &mut T) ->
 
 #[cfg(test)]
 mod tests {
+	extern crate chrono;
+
     use super::SignedRequest;
+	use super::PayloadStream;
+	use super::{encode_uri_path, encode_uri_strict};
 	use super::extract_s3_temporary_endpoint_from_xml;
 	use xmlutil::*;
 	use regions::*;
 	use std::io::BufReader;
 	use std::fs::File;
+	use std::io::{Cursor, Read, Seek, SeekFrom};
 	use xml::reader::*;
+	use credentials::AWSCredentials;
+	use self::chrono::{Duration, UTC};
 
 	#[test]
 	fn get_hostname_none_present() {
@@ -423,6 +727,26 @@ mod tests {
 		assert_eq!("test-hostname", request.get_hostname());
 	}
 
+	#[test]
+	fn debug_output_redacts_secrets() {
+		let region = Region::UsEast1;
+		let mut request = SignedRequest::new("GET", "s3", &region, "/");
+		request.add_header("Authorization", "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830, Signature=abcdef");
+		request.add_header("X-Amz-Security-Token", "supersecrettoken");
+		request.add_header("x-amz-server-side-encryption-customer-key", "c2VjcmV0a2V5");
+		request.add_header("x-amz-date", "20150830T123600Z");
+		let payload = b"top secret payload";
+		request.set_payload(Some(payload));
+
+		let output = format!("{:?}", request);
+		assert!(!output.contains("Signature=abcdef"));
+		assert!(!output.contains("supersecrettoken"));
+		assert!(!output.contains("c2VjcmV0a2V5"));
+		assert!(!output.contains("top secret payload"));
+		assert!(output.contains("20150830T123600Z"));
+		assert!(output.contains("18 bytes"));
+	}
+
 	#[test]
 	fn get_redirect_location_from_s3() {
 		let file = File::open("tests/sample-data/s3_temp_redirect.xml").unwrap();
@@ -441,4 +765,58 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn encode_uri_keeps_unreserved_characters() {
+		assert_eq!(encode_uri_path("photos/2015 summer/a+b~c.jpg"), "photos/2015%20summer/a%2Bb~c.jpg");
+		assert_eq!(encode_uri_strict("a/b c"), "a%2Fb%20c");
+	}
+
+	#[test]
+	fn payload_stream_hashes_body_and_rewinds() {
+		let mut reader = Cursor::new(b"hello world".to_vec());
+		let stream = PayloadStream::new(&mut reader).unwrap();
+		assert_eq!(11, stream.len());
+		assert_eq!("b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9", stream.sha256());
+		assert_eq!("XrY7u+Ae7tCTyyK7j1rNww==", stream.content_md5());
+
+		let mut body = String::new();
+		stream.rewind().unwrap().read_to_string(&mut body).unwrap();
+		assert_eq!("hello world", body);
+	}
+
+	#[test]
+	fn payload_stream_with_length_covers_only_its_window() {
+		let mut reader = Cursor::new(b"hello world, again".to_vec());
+		reader.seek(SeekFrom::Start(6)).unwrap();
+		let stream = PayloadStream::with_length(&mut reader, 5).unwrap();
+		assert_eq!(5, stream.len());
+		assert_eq!("486ea46224d1bb4fb680f34f7c9ad96a8f24ec88be73ea8e5a6c65260e9cb8a7", stream.sha256());
+
+		let mut reader = stream.rewind().unwrap();
+		assert_eq!(6, reader.seek(SeekFrom::Current(0)).unwrap());
+	}
+
+	#[test]
+	fn payload_stream_rejects_short_reader() {
+		let mut reader = Cursor::new(b"short".to_vec());
+		assert!(PayloadStream::with_length(&mut reader, 10).is_err());
+	}
+
+	#[test]
+	fn signing_again_skips_unsigned_headers_without_empty_entries() {
+		let region = Region::UsEast1;
+		let payload = b"hello world";
+		let creds = AWSCredentials::new("AKIDEXAMPLE", "secret", None, UTC::now() + Duration::seconds(600));
+		let mut request = SignedRequest::new("PUT", "s3", &region, "/bucket/key");
+		request.add_header("Cache-Control", "no-cache");
+		request.add_header("Content-MD5", "XrY7u+Ae7tCTyyK7j1rNww==");
+		request.set_payload(Some(payload));
+
+		// the first signing adds content-length, which the second one has to leave out
+		request.sign(&creds);
+		request.sign(&creds);
+
+		let authorization = String::from_utf8(request.headers["authorization"][0].clone()).unwrap();
+		assert!(authorization.contains("SignedHeaders=cache-control;content-md5;content-type;host;x-amz-content-sha256;x-amz-date,"));
+	}
 }
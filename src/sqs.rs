@@ -12,6 +12,8 @@ use error::*;
 use xmlutil::*;
 use std::str::FromStr;
 use regions::*;
+use request::*;
+use std::sync::Arc;
 
 // include the code generated from the SQS botocore templates
 include!(concat!(env!("CARGO_MANIFEST_DIR"), "/codegen/sqs.rs"));
@@ -27,6 +29,11 @@ impl<'a> SQSHelper<'a> {
 		SQSHelper { client: SQSClient::new(credentials, region) }
 	}
 
+	/// Adds a hook called around every request the helper sends, EG: for metrics or tracing
+	pub fn add_request_hook(&mut self, hook: Arc<RequestHook>) {
+		self.client.add_hook(hook);
+	}
+
 	/// Lists queues
 	pub fn list_queues(&mut self) -> Result<ListQueuesResult, AWSError> {
 		self.client.list_queues(&ListQueuesRequest::default())
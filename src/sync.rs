@@ -0,0 +1,664 @@
+//! Syncing a local directory with an S3 prefix, like `aws s3 sync`
+//!
+//! `SyncManager` lists both sides, pairs files with objects by their path relative to the
+//! directory or prefix, and transfers the new and changed ones several at a time.  By default a
+//! file and its object differ if their sizes do, or if the side being synced from was modified
+//! more recently; `CompareMode::ETag` compares their contents instead.  Files or objects missing
+//! from the side being synced from are left alone unless deletion is turned on.
+//!
+//! Include and exclude filters are globs matched against relative paths: `*` matches any run of
+//! characters, `/` included, and `?` any one character.  As with the AWS CLI everything is included
+//! to begin with and filters apply in the order they were added, so later ones win.
+//!
+//! A dry run transfers and deletes nothing, and just returns the actions it would have taken.
+//!
+//! ```no_run
+//! use std::path::Path;
+//! use rusoto::credentials::DefaultAWSCredentialsProviderChain;
+//! use rusoto::regions::Region;
+//! use rusoto::sync::SyncManager;
+//!
+//! let mut sync = SyncManager::new(DefaultAWSCredentialsProviderChain::new(), Region::UsEast1);
+//! sync.exclude("*.tmp").unwrap();
+//! sync.set_delete(true);
+//! for action in sync.upload(Path::new("site"), "my-bucket", "www/").unwrap() {
+//!     println!("{}", action);
+//! }
+//! ```
+
+extern crate chrono;
+
+use std::ascii::AsciiExt;
+use std::cmp;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+use self::chrono::{DateTime, TimeZone, UTC};
+use regex::{self, Regex};
+
+use checksum::{self, ChecksumReader};
+use credentials::{AWSCredentialsProvider, SharedCredentialsProvider};
+use error::AWSError;
+use regions::Region;
+use request::{RequestHook, RequestHooks};
+use s3::{self, S3Endpoint, S3Helper};
+use transfer::{self, TransferManager};
+use upload::UploadOptions;
+use workers::{run_workers, Worker};
+
+const DEFAULT_CONCURRENCY: usize = 4;
+/// Files larger than this are uploaded in parts by a `TransferManager`: S3's limit on a single PUT
+const MAXIMUM_SINGLE_UPLOAD: u64 = 5 * 1024 * 1024 * 1024;
+/// Preferred part sizes multipart ETags are checked against: `S3Helper`'s, and the default of
+/// `transfer::TransferManager` and the AWS CLI
+const ETAG_PART_SIZES: [u64; 2] = [5 * 1024 * 1024, 8 * 1024 * 1024];
+
+/// How to tell whether a file and its object differ, when they're the same size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CompareMode {
+	/// The side being synced from was modified more recently.  Only needs the listings.
+	SizeAndModified,
+	/// The file's MD5 doesn't match the object's ETag.  Reads every file the same size as its
+	/// object.  ETags of objects encrypted with KMS or customer-provided keys aren't MD5s, so
+	/// those objects always differ.
+	ETag,
+}
+
+/// A file or object, by its path relative to the directory or prefix being synced.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncEntry {
+	/// Path relative to the directory or prefix, with `/` separators
+	pub path: String,
+	/// Length in bytes
+	pub size: u64,
+	/// When it was last written, if known
+	pub modified: Option<DateTime<UTC>>,
+	/// The object's ETag, `None` for a file
+	pub e_tag: Option<String>,
+}
+
+/// Something a sync does, or would do in a dry run.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncAction {
+	Upload { path: PathBuf, key: String },
+	Download { key: String, path: PathBuf },
+	DeleteObject { key: String },
+	DeleteFile { path: PathBuf },
+}
+
+impl fmt::Display for SyncAction {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			SyncAction::Upload { ref path, ref key } => write!(f, "upload: {} to {}", path.display(), key),
+			SyncAction::Download { ref key, ref path } => write!(f, "download: {} to {}", key, path.display()),
+			SyncAction::DeleteObject { ref key } => write!(f, "delete: {}", key),
+			SyncAction::DeleteFile { ref path } => write!(f, "delete: {}", path.display()),
+		}
+	}
+}
+
+#[derive(Debug, Clone)]
+enum Filter {
+	Include(Regex),
+	Exclude(Regex),
+}
+
+/// What a thread needs to build its own `S3Helper` or `TransferManager`.
+#[derive(Clone)]
+struct HelperConfig {
+	provider: SharedCredentialsProvider,
+	region: Region,
+	hooks: RequestHooks,
+	endpoint: S3Endpoint,
+}
+
+impl HelperConfig {
+	fn helper(&self) -> S3Helper {
+		let mut s3 = S3Helper::new(self.provider.clone(), &self.region);
+		s3.set_endpoint(self.endpoint.clone());
+		for hook in self.hooks.iter() {
+			s3.add_request_hook(hook.clone());
+		}
+		s3
+	}
+
+	/// For files too large for a single upload, which go up in parallel parts.
+	fn transfer_manager(&self, options: &UploadOptions) -> TransferManager {
+		let mut transfers = TransferManager::new(self.provider.clone(), self.region);
+		transfers.set_endpoint(self.endpoint.clone());
+		transfers.set_upload_options(options.clone());
+		for hook in self.hooks.iter() {
+			transfers.add_request_hook(hook.clone());
+		}
+		transfers
+	}
+}
+
+/// Syncs local directories with S3 prefixes, transferring files on several threads at once.
+pub struct SyncManager {
+	config: HelperConfig,
+	compare_mode: CompareMode,
+	delete: bool,
+	dry_run: bool,
+	concurrency: usize,
+	filters: Vec<Filter>,
+	upload_options: UploadOptions,
+}
+
+impl SyncManager {
+	/// Compares by size and modification time, transfers four files at a time, and deletes nothing.
+	pub fn new<P: AWSCredentialsProvider + Send + 'static>(provider: P, region: Region) -> SyncManager {
+		SyncManager {
+			config: HelperConfig {
+				provider: SharedCredentialsProvider::new(provider),
+				region: region,
+				hooks: Vec::new(),
+				endpoint: S3Endpoint::default(),
+			},
+			compare_mode: CompareMode::SizeAndModified,
+			delete: false,
+			dry_run: false,
+			concurrency: DEFAULT_CONCURRENCY,
+			filters: Vec::new(),
+			upload_options: UploadOptions::new(),
+		}
+	}
+
+	/// How files and objects of the same size are compared.
+	pub fn set_compare_mode(&mut self, compare_mode: CompareMode) {
+		self.compare_mode = compare_mode;
+	}
+
+	/// Whether to delete files or objects that the side being synced from doesn't have.
+	pub fn set_delete(&mut self, delete: bool) {
+		self.delete = delete;
+	}
+
+	/// Whether to only report what would be done.
+	pub fn set_dry_run(&mut self, dry_run: bool) {
+		self.dry_run = dry_run;
+	}
+
+	/// Number of files transferred at once.
+	pub fn set_concurrency(&mut self, concurrency: usize) {
+		self.concurrency = cmp::max(concurrency, 1);
+	}
+
+	/// Content type, metadata and other headers for uploaded objects.
+	pub fn set_upload_options(&mut self, options: UploadOptions) {
+		self.upload_options = options;
+	}
+
+	/// Talk to a different S3 endpoint, EG: an S3 compatible store.
+	pub fn set_endpoint(&mut self, endpoint: S3Endpoint) {
+		self.config.endpoint = endpoint;
+	}
+
+	/// Adds a hook to the clients of every thread.
+	pub fn add_request_hook(&mut self, hook: Arc<RequestHook>) {
+		self.config.hooks.push(hook);
+	}
+
+	/// Syncs the paths matching `pattern` again, after an earlier exclude.
+	pub fn include(&mut self, pattern: &str) -> Result<(), AWSError> {
+		let regex = try!(glob_regex(pattern));
+		self.filters.push(Filter::Include(regex));
+		Ok(())
+	}
+
+	/// Leaves the paths matching `pattern` alone: they're neither transferred nor deleted.
+	pub fn exclude(&mut self, pattern: &str) -> Result<(), AWSError> {
+		let regex = try!(glob_regex(pattern));
+		self.filters.push(Filter::Exclude(regex));
+		Ok(())
+	}
+
+	/// Makes the objects under `prefix` match the files under `directory`, returning what was done.
+	pub fn upload(&self, directory: &Path, bucket_name: &str, prefix: &str) -> Result<Vec<SyncAction>, AWSError> {
+		let prefix = key_prefix(prefix);
+		let files = try!(self.local_entries(directory));
+		let objects = try!(self.remote_entries(bucket_name, &prefix));
+
+		let mut actions = Vec::new();
+		for (path, file) in files.iter() {
+			let local = local_path(directory, path);
+			let changed = match objects.get(path) {
+				Some(object) => try!(self.differ(file, object, &local, true)),
+				None => true,
+			};
+			if changed {
+				actions.push(SyncAction::Upload { path: local, key: format!("{}{}", prefix, path) });
+			}
+		}
+		if self.delete {
+			for path in objects.keys().filter(|path| !files.contains_key(*path)) {
+				actions.push(SyncAction::DeleteObject { key: format!("{}{}", prefix, path) });
+			}
+		}
+		try!(self.run(bucket_name, &actions));
+		Ok(actions)
+	}
+
+	/// Makes the files under `directory` match the objects under `prefix`, returning what was done.
+	/// The directory is created if need be.
+	pub fn download(&self, bucket_name: &str, prefix: &str, directory: &Path) -> Result<Vec<SyncAction>, AWSError> {
+		let prefix = key_prefix(prefix);
+		let objects = try!(self.remote_entries(bucket_name, &prefix));
+		let files = if directory.exists() {
+			try!(self.local_entries(directory))
+		} else {
+			BTreeMap::new()
+		};
+
+		let mut actions = Vec::new();
+		for (path, object) in objects.iter() {
+			if !is_contained(path) {
+				return Err(AWSError::new(format!("Can't download {}{}: it would be written outside {}",
+					prefix, path, directory.display())));
+			}
+			let local = local_path(directory, path);
+			let changed = match files.get(path) {
+				Some(file) => try!(self.differ(file, object, &local, false)),
+				None => true,
+			};
+			if changed {
+				actions.push(SyncAction::Download { key: format!("{}{}", prefix, path), path: local });
+			}
+		}
+		if self.delete {
+			for path in files.keys().filter(|path| !objects.contains_key(*path)) {
+				actions.push(SyncAction::DeleteFile { path: local_path(directory, path) });
+			}
+		}
+		try!(self.run(bucket_name, &actions));
+		Ok(actions)
+	}
+
+	/// Whether a file and its object differ.  `upload` is the direction of the sync.
+	fn differ(&self, file: &SyncEntry, object: &SyncEntry, path: &Path, upload: bool) -> Result<bool, AWSError> {
+		if file.size != object.size {
+			return Ok(true);
+		}
+		match self.compare_mode {
+			CompareMode::SizeAndModified if upload => Ok(modified_after(file, object)),
+			CompareMode::SizeAndModified => Ok(modified_after(object, file)),
+			CompareMode::ETag => match object.e_tag {
+				Some(ref e_tag) => Ok(!try!(etag_matches(path, file.size, e_tag))),
+				None => Ok(true),
+			},
+		}
+	}
+
+	fn included(&self, path: &str) -> bool {
+		let mut included = true;
+		for filter in self.filters.iter() {
+			match *filter {
+				Filter::Include(ref regex) if regex.is_match(path) => included = true,
+				Filter::Exclude(ref regex) if regex.is_match(path) => included = false,
+				_ => (),
+			}
+		}
+		included
+	}
+
+	/// The files under `directory`, following symbolic links, by relative path.  A directory reached
+	/// again through a link, EG: one back up the tree, is only listed the first time.
+	fn local_entries(&self, directory: &Path) -> Result<BTreeMap<String, SyncEntry>, AWSError> {
+		let mut entries = BTreeMap::new();
+		let mut visited = HashSet::new();
+		let mut directories = vec![directory.to_path_buf()];
+		while let Some(current) = directories.pop() {
+			if !visited.insert(try!(fs::canonicalize(&current))) {
+				continue;
+			}
+			for entry in try!(fs::read_dir(&current)) {
+				let entry_path = try!(entry).path();
+				let metadata = try!(fs::metadata(&entry_path));
+				if metadata.is_dir() {
+					directories.push(entry_path);
+					continue;
+				}
+				if !metadata.is_file() {
+					continue;
+				}
+				let path = try!(relative_path(directory, &entry_path));
+				if !self.included(&path) {
+					continue;
+				}
+				let modified = metadata.modified().ok()
+					.and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+					.map(|since_epoch| UTC.timestamp(since_epoch.as_secs() as i64, 0));
+				entries.insert(path.clone(), SyncEntry { path: path, size: metadata.len(), modified: modified, e_tag: None });
+			}
+		}
+		Ok(entries)
+	}
+
+	/// The objects under `prefix`, by relative path.  Keys ending in `/`, which consoles create as
+	/// folders, are skipped.
+	fn remote_entries(&self, bucket_name: &str, prefix: &str) -> Result<BTreeMap<String, SyncEntry>, AWSError> {
+		let mut s3 = self.config.helper();
+		let mut entries = BTreeMap::new();
+		for object in s3.list_all_objects(bucket_name, prefix) {
+			let object = try!(object);
+			if object.key.ends_with('/') || !object.key.starts_with(prefix) {
+				continue;
+			}
+			let path = object.key[prefix.len()..].to_string();
+			if !self.included(&path) {
+				continue;
+			}
+			entries.insert(path.clone(), SyncEntry {
+				path: path,
+				size: object.size as u64,
+				modified: parse_timestamp(&object.last_modified),
+				e_tag: Some(object.e_tag),
+			});
+		}
+		Ok(entries)
+	}
+
+	/// Transfers, then deletes, unless this is a dry run.
+	fn run(&self, bucket_name: &str, actions: &[SyncAction]) -> Result<(), AWSError> {
+		if self.dry_run {
+			for action in actions.iter() {
+				info!("(dry run) {}", action);
+			}
+			return Ok(());
+		}
+
+		let transfers: Vec<SyncAction> = actions.iter().filter(|action| match **action {
+			SyncAction::Upload { .. } | SyncAction::Download { .. } => true,
+			_ => false,
+		}).cloned().collect();
+		try!(self.run_transfers(bucket_name, transfers));
+
+		let keys: Vec<String> = actions.iter().filter_map(|action| match *action {
+			SyncAction::DeleteObject { ref key } => Some(key.clone()),
+			_ => None,
+		}).collect();
+		if !keys.is_empty() {
+			let output = try!(self.config.helper().delete_objects(bucket_name, &keys));
+			try!(s3::check_deleted(bucket_name, &output.errors));
+		}
+		for action in actions.iter() {
+			if let SyncAction::DeleteFile { ref path } = *action {
+				try!(fs::remove_file(path));
+			}
+		}
+		Ok(())
+	}
+
+	/// Runs the uploads and downloads on worker threads.  After the first failure workers stop
+	/// picking up new transfers, and the ones already running are waited for.
+	fn run_transfers(&self, bucket_name: &str, transfers: Vec<SyncAction>) -> Result<(), AWSError> {
+		let mut workers = Vec::new();
+		for _ in 0..cmp::min(self.concurrency, transfers.len()) {
+			workers.push(FileTransferrer {
+				config: self.config.clone(),
+				bucket: bucket_name.to_string(),
+				options: self.upload_options.clone(),
+			});
+		}
+		let jobs = transfers.into_iter().enumerate().map(|(number, action)| (number as i32, action)).collect();
+		try!(run_workers(workers, jobs, &mut |_, _| ()));
+		Ok(())
+	}
+}
+
+/// Uploads or downloads whole files.
+struct FileTransferrer {
+	config: HelperConfig,
+	bucket: String,
+	options: UploadOptions,
+}
+
+impl Worker for FileTransferrer {
+	/// The action, numbered in the order it was planned
+	type Job = (i32, SyncAction);
+	type Output = ();
+
+	fn job_number(job: &(i32, SyncAction)) -> i32 {
+		job.0
+	}
+
+	fn process(&mut self, job: (i32, SyncAction)) -> Result<(), AWSError> {
+		let (_, action) = job;
+		debug!("{}", action);
+		match action {
+			SyncAction::Upload { path, key } => {
+				if try!(fs::metadata(&path)).len() > MAXIMUM_SINGLE_UPLOAD {
+					try!(self.config.transfer_manager(&self.options).upload_file(&self.bucket, &key, &path));
+				} else {
+					try!(self.config.helper().upload_file_with_options(&self.bucket, &key, &path, &self.options));
+				}
+			}
+			SyncAction::Download { key, path } => {
+				if let Some(parent) = path.parent() {
+					try!(fs::create_dir_all(parent));
+				}
+				try!(self.config.helper().download_to_file(&self.bucket, &key, &path));
+			}
+			_ => (),
+		}
+		Ok(())
+	}
+}
+
+/// Whether `entry` was modified after `other`.  Without both times they're taken to differ.
+fn modified_after(entry: &SyncEntry, other: &SyncEntry) -> bool {
+	match (entry.modified.as_ref(), other.modified.as_ref()) {
+		(Some(modified), Some(other_modified)) => modified > other_modified,
+		_ => true,
+	}
+}
+
+/// Whether a file's data matches an object's ETag: its MD5, or for a multipart upload the MD5 of
+/// its parts' MD5s, tried with the usual part sizes.
+fn etag_matches(path: &Path, size: u64, e_tag: &str) -> Result<bool, AWSError> {
+	let e_tag = e_tag.trim_matches('"');
+	if !checksum::is_multipart(e_tag) {
+		return Ok(try!(file_etag(path, None)).eq_ignore_ascii_case(e_tag));
+	}
+	let parts = e_tag.rsplit('-').next().and_then(|parts| parts.parse::<u64>().ok()).unwrap_or(0);
+	for &preferred in ETAG_PART_SIZES.iter() {
+		// large files have larger parts, to stay within S3's 10,000
+		let part_size = transfer::part_size_for(size, preferred);
+		if (size + part_size - 1) / part_size == parts && try!(file_etag(path, Some(part_size))).eq_ignore_ascii_case(e_tag) {
+			return Ok(true);
+		}
+	}
+	Ok(false)
+}
+
+/// The ETag S3 would give a file uploaded in one piece, or in parts of `part_size`.
+fn file_etag(path: &Path, part_size: Option<u64>) -> Result<String, AWSError> {
+	let mut file = try!(File::open(path));
+	let part_size = match part_size {
+		Some(part_size) => part_size,
+		None => {
+			let mut reader = ChecksumReader::new(&mut file, None);
+			try!(io::copy(&mut reader, &mut io::sink()));
+			return Ok(reader.md5_hex());
+		}
+	};
+	let mut part_etags = Vec::new();
+	loop {
+		let mut reader = ChecksumReader::new((&mut file).take(part_size), None);
+		let read = try!(io::copy(&mut reader, &mut io::sink()));
+		if read == 0 && !part_etags.is_empty() {
+			break;
+		}
+		part_etags.push(reader.md5_hex());
+		if read < part_size {
+			break;
+		}
+	}
+	Ok(checksum::multipart_etag(&part_etags).unwrap())
+}
+
+/// Turns a glob into an anchored regular expression.
+fn glob_regex(pattern: &str) -> Result<Regex, AWSError> {
+	let mut expression = "^".to_string();
+	for c in pattern.chars() {
+		match c {
+			'*' => expression.push_str(".*"),
+			'?' => expression.push('.'),
+			_ => expression.push_str(&regex::quote(&c.to_string())),
+		}
+	}
+	expression.push('$');
+	Regex::new(&expression).map_err(|why| AWSError::new(format!("Invalid pattern {}: {}", pattern, why)))
+}
+
+/// A prefix keys are made of, ending in `/` unless it's empty.
+fn key_prefix(prefix: &str) -> String {
+	if prefix.is_empty() || prefix.ends_with('/') {
+		prefix.to_string()
+	} else {
+		format!("{}/", prefix)
+	}
+}
+
+/// A file's path relative to `directory`, with `/` separators.
+fn relative_path(directory: &Path, path: &Path) -> Result<String, AWSError> {
+	let relative = match path.strip_prefix(directory) {
+		Ok(relative) => relative,
+		Err(_) => return Err(AWSError::new(format!("{} isn't under {}", path.display(), directory.display()))),
+	};
+	let mut parts = Vec::new();
+	for component in relative.components() {
+		match component.as_os_str().to_str() {
+			Some(part) => parts.push(part.to_string()),
+			None => return Err(AWSError::new(format!("Can't sync {}: its name isn't UTF-8", path.display()))),
+		}
+	}
+	Ok(parts.join("/"))
+}
+
+/// Where a file with this relative path goes under `directory`.
+fn local_path(directory: &Path, path: &str) -> PathBuf {
+	path.split('/').fold(directory.to_path_buf(), |local, part| local.join(part))
+}
+
+/// Whether a relative path stays inside its directory: no empty, `.` or `..` parts, and not absolute.
+fn is_contained(path: &str) -> bool {
+	path.split('/').all(|part| !part.is_empty() && part != "." && part != "..")
+		&& Path::new(path).components().all(|component| match component {
+			Component::Normal(_) => true,
+			_ => false,
+		})
+}
+
+/// Parses the timestamps of S3 listings, EG: `2009-10-12T17:50:30.000Z`
+fn parse_timestamp(value: &str) -> Option<DateTime<UTC>> {
+	let seconds = value.split(|c: char| c == '.' || c == 'Z').next().unwrap_or("");
+	UTC.datetime_from_str(seconds, "%Y-%m-%dT%H:%M:%S").ok()
+}
+
+#[cfg(test)]
+mod tests {
+	extern crate chrono;
+
+	use super::*;
+	use super::{etag_matches, file_etag, glob_regex, is_contained, key_prefix, local_path, modified_after, parse_timestamp};
+	use self::chrono::{TimeZone, UTC};
+	use std::env;
+	use std::fs::{self, File};
+	use std::io::Write;
+	use std::path::{Path, PathBuf};
+	use credentials::DefaultAWSCredentialsProviderChain;
+	use regions::Region;
+
+	fn entry(path: &str, size: u64, modified: i64) -> SyncEntry {
+		SyncEntry { path: path.to_string(), size: size, modified: Some(UTC.timestamp(modified, 0)), e_tag: None }
+	}
+
+	#[test]
+	fn globs_match_whole_paths() {
+		let regex = glob_regex("*.log").unwrap();
+		assert!(regex.is_match("app.log"));
+		assert!(regex.is_match("logs/2016/app.log"));
+		assert!(!regex.is_match("app.log.gz"));
+		assert!(glob_regex("data/?.csv").unwrap().is_match("data/1.csv"));
+		assert!(!glob_regex("data/?.csv").unwrap().is_match("data/10.csv"));
+		assert!(glob_regex("a+b(c).txt").unwrap().is_match("a+b(c).txt"));
+	}
+
+	#[test]
+	fn later_filters_win() {
+		let mut sync = SyncManager::new(DefaultAWSCredentialsProviderChain::new(), Region::UsEast1);
+		sync.exclude("*").unwrap();
+		sync.include("*.html").unwrap();
+		sync.exclude("drafts/*").unwrap();
+		assert!(sync.included("index.html"));
+		assert!(!sync.included("style.css"));
+		assert!(!sync.included("drafts/post.html"));
+	}
+
+	#[test]
+	fn newer_sources_are_synced() {
+		let older = entry("a.txt", 10, 1000);
+		let newer = entry("a.txt", 10, 2000);
+		assert!(modified_after(&newer, &older));
+		assert!(!modified_after(&older, &newer));
+		assert!(!modified_after(&older, &older));
+
+		let unknown = SyncEntry { modified: None, ..older.clone() };
+		assert!(modified_after(&unknown, &older));
+	}
+
+	#[test]
+	fn file_etags_match_s3s() {
+		let path = env::temp_dir().join("rusoto-sync-file-etags.txt");
+		File::create(&path).unwrap().write_all(b"hello world").unwrap();
+
+		assert_eq!("5eb63bbbe01eeed093cb22bb8f5acdc3", file_etag(&path, None).unwrap());
+		assert_eq!("177e85e8bb233bd57a6aabda201a0c2c-3", file_etag(&path, Some(4)).unwrap());
+		assert!(etag_matches(&path, 11, "\"5EB63BBBE01EEED093CB22BB8F5ACDC3\"").unwrap());
+		assert!(!etag_matches(&path, 11, "\"d41d8cd98f00b204e9800998ecf8427e\"").unwrap());
+		fs::remove_file(&path).unwrap();
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn symlinked_directories_are_listed_once() {
+		use std::os::unix::fs::symlink;
+
+		let directory = env::temp_dir().join("rusoto-sync-symlink-loop");
+		let _ = fs::remove_dir_all(&directory);
+		fs::create_dir_all(directory.join("sub")).unwrap();
+		File::create(directory.join("a.txt")).unwrap().write_all(b"a").unwrap();
+		File::create(directory.join("sub").join("b.txt")).unwrap().write_all(b"b").unwrap();
+		symlink("..", directory.join("sub").join("loop")).unwrap();
+
+		let sync = SyncManager::new(DefaultAWSCredentialsProviderChain::new(), Region::UsEast1);
+		let entries = sync.local_entries(&directory).unwrap();
+		assert_eq!(vec!["a.txt", "sub/b.txt"], entries.keys().map(|path| path.as_str()).collect::<Vec<_>>());
+		fs::remove_dir_all(&directory).unwrap();
+	}
+
+	#[test]
+	fn keys_map_to_paths() {
+		assert_eq!("", key_prefix(""));
+		assert_eq!("www/", key_prefix("www"));
+		assert_eq!("www/", key_prefix("www/"));
+		let expected: PathBuf = ["site", "css", "main.css"].iter().collect();
+		assert_eq!(expected, local_path(Path::new("site"), "css/main.css"));
+
+		assert!(is_contained("css/main.css"));
+		assert!(!is_contained("../etc/passwd"));
+		assert!(!is_contained("css//main.css"));
+		assert!(!is_contained("/etc/passwd"));
+	}
+
+	#[test]
+	fn listing_timestamps_are_parsed() {
+		assert_eq!(Some(UTC.ymd(2009, 10, 12).and_hms(17, 50, 30)), parse_timestamp("2009-10-12T17:50:30.000Z"));
+		assert_eq!(None, parse_timestamp(""));
+	}
+}
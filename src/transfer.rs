@@ -0,0 +1,712 @@
+//! Managed S3 transfers
+//!
+//! `TransferManager` moves large objects as multipart uploads, sending several parts at once from a
+//! pool of worker threads.  Each worker has its own `S3Client`; they share one credentials provider.
+//!
+//! Interrupted uploads can be resumed with `resume_upload_file`, which only sends the parts S3
+//! doesn't already have.  Downloads fetch byte ranges of the object in parallel.  Set a
+//! `progress::ProgressListener` to follow transfers part by part, or to cancel them.
+//!
+//! Parts and completed uploads are checked against the ETags S3 returns for them, and downloads
+//! against the object's ETag when it's an MD5.  See the `checksum` module.
+//!
+//! ```no_run
+//! use std::path::Path;
+//! use rusoto::credentials::DefaultAWSCredentialsProviderChain;
+//! use rusoto::regions::Region;
+//! use rusoto::transfer::TransferManager;
+//!
+//! let mut transfers = TransferManager::new(DefaultAWSCredentialsProviderChain::new(), Region::UsEast1);
+//! transfers.set_concurrency(8);
+//! transfers.upload_file("my-bucket", "backups/db.tar", Path::new("/var/backups/db.tar")).unwrap();
+//! ```
+
+use std::cmp;
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use checksum::{self, ChecksumReader};
+use credentials::{AWSCredentialsProvider, SharedCredentialsProvider};
+use error::AWSError;
+use listing::{ListIterator, MultipartUploadPager, PartPager};
+use progress::{ProgressListener, ProgressTracker};
+use regions::Region;
+use request::{RequestHook, RequestHooks, default_hooks};
+use s3::*;
+use signature::PayloadStream;
+use upload::UploadOptions;
+use workers::{run_workers, Worker};
+
+/// S3 rejects parts smaller than this, except for the last one.
+pub const MINIMUM_PART_SIZE: u64 = 5 * 1024 * 1024;
+/// S3 rejects parts larger than this.
+pub const MAXIMUM_PART_SIZE: u64 = 5 * 1024 * 1024 * 1024;
+/// S3 rejects part numbers above this.
+pub const MAXIMUM_PARTS: u64 = 10000;
+
+const DEFAULT_PART_SIZE: u64 = 8 * 1024 * 1024;
+const DEFAULT_CONCURRENCY: usize = 4;
+const DEFAULT_RANGE_SIZE: u64 = 8 * 1024 * 1024;
+/// Times a download range is tried before the download fails.
+const RANGE_ATTEMPTS: u32 = 3;
+const MEBIBYTE: u64 = 1024 * 1024;
+
+/// A slice of the source object, uploaded as one part.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartPlan {
+	pub part_number: i32,
+	pub offset: u64,
+	pub length: u64,
+}
+
+/// Uploads and downloads objects in parts, on several threads at once.
+pub struct TransferManager {
+	provider: SharedCredentialsProvider,
+	region: Region,
+	hooks: RequestHooks,
+	endpoint: S3Endpoint,
+	part_size: u64,
+	range_size: u64,
+	concurrency: usize,
+	progress: Option<Arc<ProgressListener>>,
+	upload_options: UploadOptions,
+}
+
+impl TransferManager {
+	/// Transfers in 8 MB parts and ranges, four at a time.
+	pub fn new<P: AWSCredentialsProvider + Send + 'static>(provider: P, region: Region) -> TransferManager {
+		TransferManager {
+			provider: SharedCredentialsProvider::new(provider),
+			region: region,
+			hooks: default_hooks(),
+			endpoint: S3Endpoint::default(),
+			part_size: DEFAULT_PART_SIZE,
+			range_size: DEFAULT_RANGE_SIZE,
+			concurrency: DEFAULT_CONCURRENCY,
+			progress: None,
+			upload_options: UploadOptions::new(),
+		}
+	}
+
+	/// Preferred part size.  Sizes below S3's 5 MB minimum are raised to it, and the size grows
+	/// as needed to keep large objects within 10,000 parts.
+	pub fn set_part_size(&mut self, part_size: u64) {
+		self.part_size = part_size;
+	}
+
+	/// Content type, metadata and other headers for uploaded objects.  By default only the content
+	/// type is set, guessed from the file's extension.
+	pub fn set_upload_options(&mut self, options: UploadOptions) {
+		self.upload_options = options;
+	}
+
+	/// Size of the byte ranges downloads are fetched in.
+	pub fn set_range_size(&mut self, range_size: u64) {
+		self.range_size = cmp::max(range_size, 1);
+	}
+
+	/// Number of parts or ranges transferred at the same time.
+	pub fn set_concurrency(&mut self, concurrency: usize) {
+		self.concurrency = cmp::max(concurrency, 1);
+	}
+
+	/// Reports each finished part or range to `listener`, which can also cancel the transfer.
+	pub fn set_progress_listener(&mut self, listener: Arc<ProgressListener>) {
+		self.progress = Some(listener);
+	}
+
+	/// Endpoint and addressing style transfers use, EG: to send them through S3 Transfer Acceleration.
+	pub fn set_endpoint(&mut self, endpoint: S3Endpoint) {
+		self.endpoint = endpoint;
+	}
+
+	/// Adds a hook called around every request sent for a transfer.
+	pub fn add_request_hook(&mut self, hook: Arc<RequestHook>) {
+		self.hooks.push(hook);
+	}
+
+	/// Uploads a file as a multipart upload.
+	///
+	/// If any part fails the upload is aborted, so S3 doesn't keep (and bill for) the parts that
+	/// did make it.
+	pub fn upload_file(&self, bucket_name: &str, object_name: &str, path: &Path) -> Result<CompleteMultipartUploadOutput, AWSError> {
+		let object_size = try!(fs::metadata(path)).len();
+		let parts = plan_parts(object_size, part_size_for(object_size, self.part_size));
+
+		let mut client = new_client(self.provider.clone(), &self.region, &self.hooks, &self.endpoint);
+		let mut create_request = CreateMultipartUploadRequest::default();
+		create_request.bucket = bucket_name.to_string();
+		create_request.key = object_name.to_string();
+		self.upload_options.apply_to_multipart_upload(&mut create_request, path);
+		let upload_id = try!(client.create_multipart_upload(&create_request)).upload_id;
+
+		let jobs = parts.into_iter().map(|part| (part, None)).collect();
+		let tracker = ProgressTracker::new(self.progress.clone(), Some(object_size));
+		let result = tracker.start()
+			.and_then(|_| self.upload_parts(bucket_name, object_name, &upload_id, path, jobs, &tracker, &mut |_, _| ()))
+			.and_then(|etags| complete_upload(&mut client, bucket_name, object_name, &upload_id, &etags));
+
+		if result.is_err() {
+			abort_upload(&mut client, bucket_name, object_name, &upload_id);
+		}
+		result
+	}
+
+	/// Uploads a file as a multipart upload, picking up where an earlier attempt left off.
+	///
+	/// The upload to continue is the one recorded in `checkpoint`, if given, and otherwise the most
+	/// recently started upload in progress for `object_name`.  Parts already uploaded are checked
+	/// against the file by MD5 ETag, and only the missing or different ones are sent.  If there is
+	/// nothing to resume a new upload is started.
+	///
+	/// With a checkpoint file the upload id, part size and ETags of finished parts are saved as the
+	/// upload goes, and the file is removed once the upload completes.  Resuming from a checkpoint
+	/// takes the finished parts from it instead of listing them, so a checkpoint of an upload that
+	/// has since been aborted makes the resume fail: remove it to start over.  Failed uploads are
+	/// left in place to be resumed later: abort them with `S3Helper::abort_multipart_upload` to give
+	/// up.
+	pub fn resume_upload_file(&self, bucket_name: &str, object_name: &str, path: &Path,
+			checkpoint: Option<&Path>) -> Result<CompleteMultipartUploadOutput, AWSError> {
+		let object_size = try!(fs::metadata(path)).len();
+		let mut client = new_client(self.provider.clone(), &self.region, &self.hooks, &self.endpoint);
+
+		let saved = match checkpoint {
+			Some(checkpoint) => try!(UploadCheckpoint::load(checkpoint)),
+			None => None,
+		};
+		let mut resumed = saved.map(|saved| (saved.upload_id, saved.part_size, saved.parts));
+		if resumed.is_none() {
+			if let Some(upload_id) = try!(find_upload(&mut client, bucket_name, object_name)) {
+				let parts = try!(list_uploaded_parts(&mut client, bucket_name, object_name, &upload_id));
+				let part_size = match parts.iter().map(|part| part.size as u64).max() {
+					// every part but the last has the upload's part size, so the largest one does
+					Some(largest) if largest >= MINIMUM_PART_SIZE => largest,
+					_ => part_size_for(object_size, self.part_size),
+				};
+				let etags = parts.into_iter().map(|part| (part.part_number, part.e_tag)).collect();
+				resumed = Some((upload_id, part_size, etags));
+			}
+		}
+
+		let (upload_id, part_size, uploaded) = match resumed {
+			Some(resumed) => resumed,
+			None => {
+				let mut create_request = CreateMultipartUploadRequest::default();
+				create_request.bucket = bucket_name.to_string();
+				create_request.key = object_name.to_string();
+				self.upload_options.apply_to_multipart_upload(&mut create_request, path);
+				let upload_id = try!(client.create_multipart_upload(&create_request)).upload_id;
+				(upload_id, part_size_for(object_size, self.part_size), BTreeMap::new())
+			}
+		};
+
+		let mut state = UploadCheckpoint { upload_id: upload_id.clone(), part_size: part_size, parts: BTreeMap::new() };
+		if let Some(checkpoint) = checkpoint {
+			try!(state.save(checkpoint));
+		}
+
+		let jobs = plan_parts(object_size, part_size).into_iter().map(|plan| {
+			let etag = uploaded.get(&plan.part_number).cloned();
+			(plan, etag)
+		}).collect();
+
+		let tracker = ProgressTracker::new(self.progress.clone(), Some(object_size));
+		try!(tracker.start());
+		let etags = try!(self.upload_parts(bucket_name, object_name, &upload_id, path, jobs, &tracker, &mut |part_number, etag| {
+			if let Some(checkpoint) = checkpoint {
+				state.parts.insert(part_number, etag.to_string());
+				if let Err(why) = state.save(checkpoint) {
+					warn!("Couldn't save upload checkpoint {}: {:?}", checkpoint.display(), why);
+				}
+			}
+		}));
+
+		let output = try!(complete_upload(&mut client, bucket_name, object_name, &upload_id, &etags));
+		if let Some(checkpoint) = checkpoint {
+			let _ = fs::remove_file(checkpoint);
+		}
+		Ok(output)
+	}
+
+	/// Uploads parts of the file on the worker threads, returning their ETags in part order.
+	///
+	/// Each part may come with the ETag of a copy S3 already has: it's only uploaded again if the
+	/// file's data no longer matches.  `on_part` is called as each part finishes.
+	fn upload_parts(&self, bucket_name: &str, object_name: &str, upload_id: &str, path: &Path,
+			parts: Vec<(PartPlan, Option<String>)>, tracker: &ProgressTracker,
+			on_part: &mut FnMut(i32, &String)) -> Result<Vec<String>, AWSError> {
+		let mut workers = Vec::new();
+		for _ in 0..cmp::min(self.concurrency, parts.len()) {
+			workers.push(PartUploader {
+				target: self.target(bucket_name, object_name),
+				upload_id: upload_id.to_string(),
+				path: path.to_path_buf(),
+				progress: tracker.clone(),
+			});
+		}
+		let etags = try!(run_workers(workers, parts, on_part));
+		Ok(etags.into_iter().map(|(_, etag)| etag).collect())
+	}
+
+	/// Downloads an object to a file using concurrent ranged GETs.
+	///
+	/// Ranges are written straight into their place in a temporary file next to `path`, sized up
+	/// front to the object's length, and each range is retried on its own if it fails.  Every
+	/// response is checked against the range asked for, and ranges are only accepted from the
+	/// version of the object seen when the download started.  The file is renamed over `path` once
+	/// all of it has arrived and been checked against the object's ETag.
+	pub fn download_file(&self, bucket_name: &str, object_name: &str, path: &Path) -> Result<HeadObjectOutput, AWSError> {
+		let mut client = new_client(self.provider.clone(), &self.region, &self.hooks, &self.endpoint);
+		let mut head_request = HeadObjectRequest::default();
+		head_request.bucket = bucket_name.to_string();
+		head_request.key = object_name.to_string();
+		let object = try!(client.head_object(&head_request));
+
+		let temp_path = download_temp_path(path);
+		let result = self.download_ranges(bucket_name, object_name, &object, &temp_path)
+			.and_then(|_| verify_download_file(object_name, &object, &temp_path))
+			.and_then(|_| fs::rename(&temp_path, path).map_err(AWSError::from));
+
+		match result {
+			Ok(_) => Ok(object),
+			Err(why) => {
+				let _ = fs::remove_file(&temp_path);
+				Err(why)
+			}
+		}
+	}
+
+	fn download_ranges(&self, bucket_name: &str, object_name: &str, object: &HeadObjectOutput, temp_path: &Path) -> Result<(), AWSError> {
+		let object_size = object.content_length as u64;
+		{
+			let file = try!(File::create(temp_path));
+			try!(file.set_len(object_size));
+		}
+
+		let tracker = ProgressTracker::new(self.progress.clone(), Some(object_size));
+		try!(tracker.start());
+
+		let ranges: Vec<PartPlan> = plan_parts(object_size, self.range_size).into_iter()
+			.filter(|range| range.length > 0)
+			.collect();
+		let mut workers = Vec::new();
+		for _ in 0..cmp::min(self.concurrency, ranges.len()) {
+			workers.push(RangeDownloader {
+				target: self.target(bucket_name, object_name),
+				e_tag: object.e_tag.clone(),
+				object_size: object_size,
+				path: temp_path.to_path_buf(),
+				progress: tracker.clone(),
+			});
+		}
+		let downloaded = try!(run_workers(workers, ranges, &mut |_, _| ()));
+
+		let written = downloaded.values().fold(0, |total, written| total + written);
+		let file_size = try!(fs::metadata(temp_path)).len();
+		if written != object_size || file_size != object_size {
+			return Err(AWSError::new(format!("Download of {} is incomplete: expected {} bytes, got {}",
+				object_name, object_size, written)));
+		}
+		try!(try!(OpenOptions::new().write(true).open(temp_path)).sync_all());
+		Ok(())
+	}
+
+	fn target(&self, bucket_name: &str, object_name: &str) -> TransferTarget {
+		TransferTarget {
+			provider: self.provider.clone(),
+			region: self.region,
+			hooks: self.hooks.clone(),
+			endpoint: self.endpoint.clone(),
+			bucket: bucket_name.to_string(),
+			key: object_name.to_string(),
+		}
+	}
+}
+
+/// The object a transfer works on, and what a worker needs to build its own client for it.
+struct TransferTarget {
+	provider: SharedCredentialsProvider,
+	region: Region,
+	hooks: RequestHooks,
+	endpoint: S3Endpoint,
+	bucket: String,
+	key: String,
+}
+
+impl TransferTarget {
+	fn client(&self) -> S3Client {
+		new_client(self.provider.clone(), &self.region, &self.hooks, &self.endpoint)
+	}
+}
+
+/// Uploads parts of a file for a multipart upload.
+struct PartUploader {
+	target: TransferTarget,
+	upload_id: String,
+	path: PathBuf,
+	progress: ProgressTracker,
+}
+
+impl Worker for PartUploader {
+	/// The part, and the ETag of the copy S3 already has, if any
+	type Job = (PartPlan, Option<String>);
+	type Output = String;
+
+	fn job_number(job: &(PartPlan, Option<String>)) -> i32 {
+		job.0.part_number
+	}
+
+	fn process(&mut self, job: (PartPlan, Option<String>)) -> Result<String, AWSError> {
+		let (part, uploaded_etag) = job;
+		try!(self.progress.check());
+		let mut file = try!(File::open(&self.path));
+		try!(file.seek(SeekFrom::Start(part.offset)));
+		let body = try!(PayloadStream::with_length(&mut file, part.length));
+		let md5_hex = body.md5_hex();
+
+		if let Some(etag) = uploaded_etag {
+			if etag.trim_matches('"') == md5_hex {
+				debug!("Part {} of {} is already uploaded", part.part_number, self.target.key);
+				try!(self.progress.part_completed(part.part_number, part.length));
+				return Ok(etag);
+			}
+		}
+
+		let mut request = UploadPartRequest::default();
+		request.bucket = self.target.bucket.clone();
+		request.key = self.target.key.clone();
+		request.upload_id = self.upload_id.clone();
+		request.part_number = part.part_number;
+
+		let mut client = self.target.client();
+		match client.upload_part_stream(&request, body) {
+			Ok(output) => {
+				if checksum::etag_has_md5(&output.server_side_encryption, &output.sse_customer_algorithm) {
+					try!(checksum::check_etag(&self.target.key, &output.e_tag, &md5_hex));
+				}
+				try!(self.progress.part_completed(part.part_number, part.length));
+				Ok(output.e_tag)
+			}
+			Err(why) => {
+				warn!("Error uploading part {} of {}: {:?}", part.part_number, self.target.key, why);
+				Err(why)
+			}
+		}
+	}
+}
+
+/// Downloads byte ranges of an object into their place in a file.
+struct RangeDownloader {
+	target: TransferTarget,
+	/// Version of the object being downloaded: ranges of any other version are refused
+	e_tag: String,
+	object_size: u64,
+	path: PathBuf,
+	progress: ProgressTracker,
+}
+
+impl Worker for RangeDownloader {
+	type Job = PartPlan;
+	/// Bytes written
+	type Output = u64;
+
+	fn job_number(range: &PartPlan) -> i32 {
+		range.part_number
+	}
+
+	fn process(&mut self, range: PartPlan) -> Result<u64, AWSError> {
+		try!(self.progress.check());
+		let mut attempt = 1;
+		loop {
+			match self.download_range(&range) {
+				Ok(written) => {
+					try!(self.progress.part_completed(range.part_number, written));
+					return Ok(written);
+				}
+				Err(why) => {
+					if attempt >= RANGE_ATTEMPTS {
+						return Err(why);
+					}
+					warn!("Retrying bytes {}-{} of {} (attempt {}): {:?}", range.offset,
+						range.offset + range.length - 1, self.target.key, attempt, why);
+					attempt += 1;
+				}
+			}
+		}
+	}
+}
+
+impl RangeDownloader {
+	fn download_range(&self, range: &PartPlan) -> Result<u64, AWSError> {
+		let last_byte = range.offset + range.length - 1;
+		let mut request = GetObjectRequest::default();
+		request.bucket = self.target.bucket.clone();
+		request.key = self.target.key.clone();
+		request.range = Some(format!("bytes={}-{}", range.offset, last_byte));
+		request.if_match = Some(self.e_tag.clone());
+
+		let mut client = self.target.client();
+		let mut stream = try!(client.get_object_stream(&request));
+		try!(check_content_range(&stream.output.content_range, range.offset, last_byte, self.object_size));
+		if stream.output.content_length as u64 != range.length {
+			return Err(AWSError::new(format!("Asked for {} bytes of {}, response has {}", range.length,
+				self.target.key, stream.output.content_length)));
+		}
+
+		let mut file = try!(OpenOptions::new().write(true).open(&self.path));
+		try!(file.seek(SeekFrom::Start(range.offset)));
+		let written = try!(io::copy(&mut (&mut stream).take(range.length), &mut file));
+		if written != range.length {
+			return Err(AWSError::new(format!("Bytes {}-{} of {} were truncated after {} bytes", range.offset,
+				last_byte, self.target.key, written)));
+		}
+		Ok(written)
+	}
+}
+
+/// Checks a `Content-Range` response header, EG: `bytes 0-1023/4096`, is the range asked for.
+fn check_content_range(content_range: &str, first_byte: u64, last_byte: u64, object_size: u64) -> Result<(), AWSError> {
+	let expected = format!("bytes {}-{}/{}", first_byte, last_byte, object_size);
+	if content_range.trim() != expected {
+		return Err(AWSError::new(format!("Expected Content-Range {}, got {}", expected, content_range)));
+	}
+	Ok(())
+}
+
+fn new_client<'r>(provider: SharedCredentialsProvider, region: &'r Region, hooks: &RequestHooks, endpoint: &S3Endpoint) -> S3Client<'r> {
+	let mut client = S3Client::new(provider, region);
+	client.set_endpoint(endpoint.clone());
+	client.clear_hooks();
+	for hook in hooks.iter() {
+		client.add_hook(hook.clone());
+	}
+	client
+}
+
+fn complete_upload(client: &mut S3Client, bucket_name: &str, object_name: &str, upload_id: &str,
+		etags: &Vec<String>) -> Result<CompleteMultipartUploadOutput, AWSError> {
+	let parts_xml = try!(multipart_upload_finish_xml(etags));
+
+	let mut request = CompleteMultipartUploadRequest::default();
+	request.bucket = bucket_name.to_string();
+	request.key = object_name.to_string();
+	request.upload_id = upload_id.to_string();
+	request.multipart_upload = Some(&parts_xml);
+	let output = try!(client.complete_multipart_upload(&request));
+	if checksum::etag_has_md5(&output.server_side_encryption, "") {
+		try!(checksum::check_multipart_etag(object_name, &output.e_tag, etags));
+	}
+	Ok(output)
+}
+
+/// Checks a downloaded file against the object's ETag, when that's an MD5.  Objects uploaded in
+/// parts or encrypted with a KMS or customer-provided key can't be checked.
+fn verify_download_file(object_name: &str, object: &HeadObjectOutput, path: &Path) -> Result<(), AWSError> {
+	if object.e_tag.is_empty() || checksum::is_multipart(&object.e_tag)
+			|| !checksum::etag_has_md5(&object.server_side_encryption, &object.sse_customer_algorithm) {
+		return Ok(());
+	}
+	let mut reader = ChecksumReader::new(try!(File::open(path)), None);
+	try!(io::copy(&mut reader, &mut io::sink()));
+	checksum::check_etag(object_name, &object.e_tag, &reader.md5_hex()).map_err(AWSError::from)
+}
+
+fn abort_upload(client: &mut S3Client, bucket_name: &str, object_name: &str, upload_id: &str) {
+	let mut request = AbortMultipartUploadRequest::default();
+	request.bucket = bucket_name.to_string();
+	request.key = object_name.to_string();
+	request.upload_id = upload_id.to_string();
+	if let Err(why) = client.abort_multipart_upload(&request) {
+		warn!("Couldn't abort multipart upload {} of {}: {:?}", upload_id, object_name, why);
+	}
+}
+
+/// The most recently started multipart upload in progress for `object_name`, if any.
+fn find_upload(client: &mut S3Client, bucket_name: &str, object_name: &str) -> Result<Option<String>, AWSError> {
+	let mut request = ListMultipartUploadsRequest::default();
+	request.bucket = bucket_name.to_string();
+	request.prefix = Some(object_name.to_string());
+
+	let mut latest: Option<MultipartUpload> = None;
+	for upload in ListIterator::new(client, MultipartUploadPager::new(request)) {
+		let upload = try!(upload);
+		// the prefix also matches longer keys
+		let newer = match latest {
+			Some(ref latest) => upload.initiated > latest.initiated,
+			None => true,
+		};
+		if upload.key == object_name && newer {
+			latest = Some(upload);
+		}
+	}
+	Ok(latest.map(|upload| upload.upload_id))
+}
+
+/// Every part uploaded so far for a multipart upload.
+fn list_uploaded_parts(client: &mut S3Client, bucket_name: &str, object_name: &str, upload_id: &str) -> Result<Vec<Part>, AWSError> {
+	let mut request = ListPartsRequest::default();
+	request.bucket = bucket_name.to_string();
+	request.key = object_name.to_string();
+	request.upload_id = upload_id.to_string();
+
+	ListIterator::new(client, PartPager::new(request)).collect()
+}
+
+/// Progress of a resumable upload, saved to a file between attempts.
+///
+/// The file is plain text: the upload id and part size, then a line per finished part.
+#[derive(Debug, PartialEq)]
+pub struct UploadCheckpoint {
+	pub upload_id: String,
+	pub part_size: u64,
+	/// ETags of the finished parts, by part number
+	pub parts: BTreeMap<i32, String>,
+}
+
+impl UploadCheckpoint {
+	/// Reads a checkpoint file.  A missing file means there's no checkpoint yet.
+	pub fn load(path: &Path) -> Result<Option<UploadCheckpoint>, AWSError> {
+		if !path.exists() {
+			return Ok(None);
+		}
+		let mut contents = String::new();
+		try!(try!(File::open(path)).read_to_string(&mut contents));
+		UploadCheckpoint::parse(&contents).map(Some)
+	}
+
+	/// Writes the checkpoint to a temporary file and renames it over `path`, so an interrupted
+	/// save never leaves a truncated checkpoint behind.
+	pub fn save(&self, path: &Path) -> Result<(), AWSError> {
+		let mut temp_name = path.as_os_str().to_os_string();
+		temp_name.push(".tmp");
+		let temp_path = PathBuf::from(temp_name);
+		{
+			let mut file = try!(File::create(&temp_path));
+			try!(file.write_all(self.contents().as_bytes()));
+			try!(file.sync_all());
+		}
+		try!(fs::rename(&temp_path, path));
+		Ok(())
+	}
+
+	fn parse(contents: &str) -> Result<UploadCheckpoint, AWSError> {
+		let mut checkpoint = UploadCheckpoint { upload_id: String::new(), part_size: 0, parts: BTreeMap::new() };
+		for line in contents.lines() {
+			let fields: Vec<&str> = line.split_whitespace().collect();
+			if fields.is_empty() {
+				continue;
+			}
+			match (fields[0], fields.len()) {
+				("upload_id", 2) => checkpoint.upload_id = fields[1].to_string(),
+				("part_size", 2) => checkpoint.part_size = try!(fields[1].parse().map_err(|_| bad_checkpoint(line))),
+				("part", 3) => {
+					let part_number = try!(fields[1].parse().map_err(|_| bad_checkpoint(line)));
+					checkpoint.parts.insert(part_number, fields[2].to_string());
+				}
+				_ => return Err(bad_checkpoint(line)),
+			}
+		}
+		if checkpoint.upload_id.is_empty() || checkpoint.part_size == 0 {
+			return Err(AWSError::new("Upload checkpoint is missing its upload id or part size"));
+		}
+		Ok(checkpoint)
+	}
+
+	fn contents(&self) -> String {
+		let mut contents = format!("upload_id {}\npart_size {}\n", self.upload_id, self.part_size);
+		for (part_number, etag) in self.parts.iter() {
+			contents.push_str(&format!("part {} {}\n", part_number, etag));
+		}
+		contents
+	}
+}
+
+fn bad_checkpoint(line: &str) -> AWSError {
+	AWSError::new(format!("Unreadable upload checkpoint line: {}", line))
+}
+
+/// Part size to use for an object: the preferred size, kept within S3's limits and grown (in whole
+/// megabytes) when the object would otherwise need more than 10,000 parts.
+pub fn part_size_for(object_size: u64, preferred: u64) -> u64 {
+	let part_size = cmp::min(cmp::max(preferred, MINIMUM_PART_SIZE), MAXIMUM_PART_SIZE);
+	if (object_size + part_size - 1) / part_size <= MAXIMUM_PARTS {
+		return part_size;
+	}
+	let smallest = (object_size + MAXIMUM_PARTS - 1) / MAXIMUM_PARTS;
+	(smallest + MEBIBYTE - 1) / MEBIBYTE * MEBIBYTE
+}
+
+/// Splits an object into parts of `part_size` bytes; the last part holds what's left.  An empty
+/// object is a single empty part, since S3 can't complete an upload without any.
+pub fn plan_parts(object_size: u64, part_size: u64) -> Vec<PartPlan> {
+	let mut parts = Vec::new();
+	let mut offset = 0;
+	while offset < object_size || parts.is_empty() {
+		let length = cmp::min(part_size, object_size - offset);
+		parts.push(PartPlan { part_number: parts.len() as i32 + 1, offset: offset, length: length });
+		offset += length;
+	}
+	parts
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::collections::BTreeMap;
+
+	#[test]
+	fn part_size_is_raised_to_the_minimum() {
+		assert_eq!(MINIMUM_PART_SIZE, part_size_for(100, 1024));
+	}
+
+	#[test]
+	fn part_size_grows_to_stay_under_part_limit() {
+		let object_size = 200 * 1024 * 1024 * 1024;
+		let part_size = part_size_for(object_size, MINIMUM_PART_SIZE);
+		assert!(part_size > MINIMUM_PART_SIZE);
+		assert_eq!(0, part_size % (1024 * 1024));
+		assert!(plan_parts(object_size, part_size).len() as u64 <= MAXIMUM_PARTS);
+	}
+
+	#[test]
+	fn plan_has_no_empty_trailing_part() {
+		let parts = plan_parts(2 * MINIMUM_PART_SIZE, MINIMUM_PART_SIZE);
+		assert_eq!(2, parts.len());
+		assert_eq!(PartPlan { part_number: 2, offset: MINIMUM_PART_SIZE, length: MINIMUM_PART_SIZE }, parts[1]);
+
+		let parts = plan_parts(2 * MINIMUM_PART_SIZE + 10, MINIMUM_PART_SIZE);
+		assert_eq!(3, parts.len());
+		assert_eq!(10, parts[2].length);
+	}
+
+	#[test]
+	fn checkpoint_round_trips() {
+		let mut checkpoint = UploadCheckpoint { upload_id: "VXBsb2FkIElE".to_string(), part_size: MINIMUM_PART_SIZE, parts: BTreeMap::new() };
+		checkpoint.parts.insert(1, "\"b54357faf0632cce46e942fa68356b38\"".to_string());
+		checkpoint.parts.insert(2, "\"acbd18db4cc2f85cedef654fccc4a4d8\"".to_string());
+
+		assert_eq!(checkpoint, UploadCheckpoint::parse(&checkpoint.contents()).unwrap());
+	}
+
+	#[test]
+	fn checkpoint_rejects_garbage() {
+		assert!(UploadCheckpoint::parse("upload_id abc\npart_size lots\n").is_err());
+		assert!(UploadCheckpoint::parse("part 1 \"etag\"\n").is_err());
+	}
+
+	#[test]
+	fn content_range_must_match_request() {
+		assert!(super::check_content_range("bytes 0-1023/4096", 0, 1023, 4096).is_ok());
+		assert!(super::check_content_range("bytes 0-4095/4096", 0, 1023, 4096).is_err());
+		assert!(super::check_content_range("bytes 0-1023/8192", 0, 1023, 4096).is_err());
+		assert!(super::check_content_range("", 0, 1023, 4096).is_err());
+	}
+
+	#[test]
+	fn empty_object_is_one_empty_part() {
+		assert_eq!(vec![PartPlan { part_number: 1, offset: 0, length: 0 }], plan_parts(0, MINIMUM_PART_SIZE));
+	}
+}
@@ -0,0 +1,267 @@
+//! Options for S3 uploads: the headers stored with an object
+//!
+//! `UploadOptions` collects the `Content-Type`, `Cache-Control`, `Content-Disposition`, user
+//! metadata, canned ACL, tags and storage class of an object, and fills them into the requests
+//! `S3Helper` and `transfer::TransferManager` send.  Unless a content type is given it is guessed
+//! from the file's extension, falling back to `application/octet-stream`.
+
+use std::ascii::AsciiExt;
+use std::collections::HashMap;
+use std::path::Path;
+use s3::{canned_acl_in_aws_format, CannedAcl, CreateMultipartUploadRequest, PutObjectRequest};
+use signature::encode_uri_strict;
+
+/// Headers to store with an uploaded object.
+///
+/// Built up with chained calls, EG:
+///
+/// ```ignore
+/// let mut options = UploadOptions::new();
+/// options.cache_control("max-age=3600").metadata("build", "1234").tag("team", "web");
+/// ```
+#[derive(Debug, Clone)]
+pub struct UploadOptions {
+	content_type: Option<String>,
+	guess_content_type: bool,
+	cache_control: Option<String>,
+	content_disposition: Option<String>,
+	content_encoding: Option<String>,
+	content_language: Option<String>,
+	metadata: HashMap<String, String>,
+	acl: Option<CannedAcl>,
+	tags: Vec<(String, String)>,
+	storage_class: Option<String>,
+}
+
+impl Default for UploadOptions {
+	fn default() -> UploadOptions {
+		UploadOptions::new()
+	}
+}
+
+impl UploadOptions {
+	/// No headers, with the content type guessed from the file extension.
+	pub fn new() -> UploadOptions {
+		UploadOptions {
+			content_type: None,
+			guess_content_type: true,
+			cache_control: None,
+			content_disposition: None,
+			content_encoding: None,
+			content_language: None,
+			metadata: HashMap::new(),
+			acl: None,
+			tags: Vec::new(),
+			storage_class: None,
+		}
+	}
+
+	/// Sets the object's `Content-Type`, rather than guessing it.
+	pub fn content_type(&mut self, content_type: &str) -> &mut UploadOptions {
+		self.content_type = Some(content_type.to_string());
+		self
+	}
+
+	/// Whether to guess a `Content-Type` from the file extension when none is set.  On by default.
+	pub fn guess_content_type(&mut self, guess: bool) -> &mut UploadOptions {
+		self.guess_content_type = guess;
+		self
+	}
+
+	/// Sets the object's `Cache-Control`, EG: `max-age=3600`.
+	pub fn cache_control(&mut self, cache_control: &str) -> &mut UploadOptions {
+		self.cache_control = Some(cache_control.to_string());
+		self
+	}
+
+	/// Sets the object's `Content-Disposition`, EG: `attachment; filename="report.pdf"`.
+	pub fn content_disposition(&mut self, content_disposition: &str) -> &mut UploadOptions {
+		self.content_disposition = Some(content_disposition.to_string());
+		self
+	}
+
+	/// Sets the object's `Content-Encoding`, EG: `gzip` for a file compressed before upload.
+	pub fn content_encoding(&mut self, content_encoding: &str) -> &mut UploadOptions {
+		self.content_encoding = Some(content_encoding.to_string());
+		self
+	}
+
+	/// Sets the object's `Content-Language`.
+	pub fn content_language(&mut self, content_language: &str) -> &mut UploadOptions {
+		self.content_language = Some(content_language.to_string());
+		self
+	}
+
+	/// Adds user metadata, sent as an `x-amz-meta-` header.  S3 lowercases the name.
+	pub fn metadata(&mut self, name: &str, value: &str) -> &mut UploadOptions {
+		self.metadata.insert(name.to_string(), value.to_string());
+		self
+	}
+
+	/// Sets the object's canned ACL.
+	pub fn acl(&mut self, acl: CannedAcl) -> &mut UploadOptions {
+		self.acl = Some(acl);
+		self
+	}
+
+	/// Adds a tag to the object.
+	pub fn tag(&mut self, key: &str, value: &str) -> &mut UploadOptions {
+		self.tags.push((key.to_string(), value.to_string()));
+		self
+	}
+
+	/// Sets the object's storage class, EG: `STANDARD_IA`.
+	pub fn storage_class(&mut self, storage_class: &str) -> &mut UploadOptions {
+		self.storage_class = Some(storage_class.to_string());
+		self
+	}
+
+	/// The `Content-Type` for an object uploaded from `path`, or named `path` when uploaded from
+	/// memory: the one set, or else a guess from the extension.
+	pub fn content_type_for(&self, path: &Path) -> Option<String> {
+		match self.content_type {
+			Some(ref content_type) => Some(content_type.clone()),
+			None if self.guess_content_type => guess_content_type(path).map(|guess| guess.to_string()),
+			None => None,
+		}
+	}
+
+	/// The tags as an `x-amz-tagging` header value, if there are any.
+	pub fn tagging(&self) -> Option<String> {
+		if self.tags.is_empty() {
+			return None;
+		}
+		let pairs: Vec<String> = self.tags.iter()
+			.map(|&(ref key, ref value)| format!("{}={}", encode_uri_strict(key), encode_uri_strict(value)))
+			.collect();
+		Some(pairs.join("&"))
+	}
+
+	/// Fills the options into a PUT of an object read from `path`.
+	pub fn apply_to_put_object(&self, request: &mut PutObjectRequest, path: &Path) {
+		request.content_type = self.content_type_for(path);
+		request.cache_control = self.cache_control.clone();
+		request.content_disposition = self.content_disposition.clone();
+		request.content_encoding = self.content_encoding.clone();
+		request.content_language = self.content_language.clone();
+		if !self.metadata.is_empty() {
+			request.metadata = Some(self.metadata.clone());
+		}
+		request.acl = self.acl.clone();
+		request.tagging = self.tagging();
+		if self.storage_class.is_some() {
+			request.storage_class = self.storage_class.clone();
+		}
+	}
+
+	/// Fills the options into the start of a multipart upload of an object read from `path`.
+	pub fn apply_to_multipart_upload(&self, request: &mut CreateMultipartUploadRequest, path: &Path) {
+		request.content_type = self.content_type_for(path);
+		request.cache_control = self.cache_control.clone();
+		request.content_disposition = self.content_disposition.clone();
+		request.content_encoding = self.content_encoding.clone();
+		request.content_language = self.content_language.clone();
+		if !self.metadata.is_empty() {
+			request.metadata = Some(self.metadata.clone());
+		}
+		request.acl = self.acl.as_ref().map(canned_acl_in_aws_format);
+		request.tagging = self.tagging();
+		if self.storage_class.is_some() {
+			request.storage_class = self.storage_class.clone();
+		}
+	}
+}
+
+/// Guesses a file's MIME type from its extension.
+pub fn guess_content_type(path: &Path) -> Option<&'static str> {
+	let extension = match path.extension().and_then(|extension| extension.to_str()) {
+		Some(extension) => extension.to_ascii_lowercase(),
+		None => return None,
+	};
+	let content_type = match extension.as_ref() {
+		"html" | "htm" => "text/html",
+		"css" => "text/css",
+		"js" | "mjs" => "application/javascript",
+		"json" | "map" => "application/json",
+		"xml" => "application/xml",
+		"txt" | "log" => "text/plain",
+		"csv" => "text/csv",
+		"md" => "text/markdown",
+		"yaml" | "yml" => "application/x-yaml",
+		"png" => "image/png",
+		"jpg" | "jpeg" => "image/jpeg",
+		"gif" => "image/gif",
+		"svg" => "image/svg+xml",
+		"ico" => "image/x-icon",
+		"webp" => "image/webp",
+		"bmp" => "image/bmp",
+		"tif" | "tiff" => "image/tiff",
+		"woff" => "font/woff",
+		"woff2" => "font/woff2",
+		"ttf" => "font/ttf",
+		"otf" => "font/otf",
+		"eot" => "application/vnd.ms-fontobject",
+		"mp3" => "audio/mpeg",
+		"wav" => "audio/wav",
+		"ogg" => "audio/ogg",
+		"mp4" => "video/mp4",
+		"webm" => "video/webm",
+		"mov" => "video/quicktime",
+		"pdf" => "application/pdf",
+		"zip" => "application/zip",
+		"gz" | "tgz" => "application/gzip",
+		"tar" => "application/x-tar",
+		"bz2" => "application/x-bzip2",
+		"wasm" => "application/wasm",
+		_ => return None,
+	};
+	Some(content_type)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::path::Path;
+	use s3::{CannedAcl, PutObjectRequest};
+
+	#[test]
+	fn content_type_guessed_from_extension() {
+		assert_eq!(Some("text/html"), guess_content_type(Path::new("site/index.html")));
+		assert_eq!(Some("image/jpeg"), guess_content_type(Path::new("photos/CAT.JPG")));
+		assert_eq!(None, guess_content_type(Path::new("Makefile")));
+		assert_eq!(None, guess_content_type(Path::new("data.unknown")));
+	}
+
+	#[test]
+	fn set_content_type_wins_over_guess() {
+		let mut options = UploadOptions::new();
+		assert_eq!(Some("text/css".to_string()), options.content_type_for(Path::new("style.css")));
+		options.guess_content_type(false);
+		assert_eq!(None, options.content_type_for(Path::new("style.css")));
+		options.content_type("text/plain; charset=utf-8");
+		assert_eq!(Some("text/plain; charset=utf-8".to_string()), options.content_type_for(Path::new("style.css")));
+	}
+
+	#[test]
+	fn tags_are_url_encoded() {
+		let mut options = UploadOptions::new();
+		assert_eq!(None, options.tagging());
+		options.tag("team", "web").tag("owner", "a&b=c");
+		assert_eq!(Some("team=web&owner=a%26b%3Dc".to_string()), options.tagging());
+	}
+
+	#[test]
+	fn options_fill_put_object_request() {
+		let mut options = UploadOptions::new();
+		options.cache_control("max-age=60").metadata("build", "1234").acl(CannedAcl::PublicRead).storage_class("STANDARD_IA");
+		let mut request = PutObjectRequest::default();
+		options.apply_to_put_object(&mut request, Path::new("index.html"));
+
+		assert_eq!(Some("text/html".to_string()), request.content_type);
+		assert_eq!(Some("max-age=60".to_string()), request.cache_control);
+		assert_eq!(Some(CannedAcl::PublicRead), request.acl);
+		assert_eq!(Some("STANDARD_IA".to_string()), request.storage_class);
+		assert_eq!("1234", request.metadata.unwrap()["build"]);
+		assert_eq!(None, request.tagging);
+	}
+}
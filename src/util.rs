@@ -0,0 +1,37 @@
+//! Helpers shared by the other modules but not part of the public API
+
+use std::ptr;
+
+/// Overwrites the contents of a string holding secret material with zeros.
+pub fn zero_string(secret: &mut String) {
+	// zeros are valid UTF-8, so the String stays well formed
+	unsafe { zero_memory(secret.as_mut_vec()); }
+}
+
+/// Overwrites a buffer holding secret material with zeros.  Volatile writes keep the
+/// compiler from skipping them because the buffer is about to be freed.
+pub fn zero_memory(secret: &mut [u8]) {
+	for byte in secret.iter_mut() {
+		unsafe { ptr::write_volatile(byte, 0); }
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn zero_string_clears_contents() {
+		let mut secret = "supersecretkey".to_string();
+		zero_string(&mut secret);
+		assert_eq!(secret.len(), 14);
+		assert!(secret.bytes().all(|b| b == 0));
+	}
+
+	#[test]
+	fn zero_memory_clears_contents() {
+		let mut secret = vec![1u8, 2, 3];
+		zero_memory(&mut secret);
+		assert_eq!(vec![0u8, 0, 0], secret);
+	}
+}
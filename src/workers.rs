@@ -0,0 +1,89 @@
+//! A pool of worker threads for transfers
+//!
+//! `transfer::TransferManager` sends the parts of an object, and `sync::SyncManager` whole files,
+//! several at a time: each `Worker` runs on a thread of its own and takes jobs from a shared queue
+//! until there are none left or one of them fails.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+use error::AWSError;
+
+/// Work done on the worker threads, one job at a time.
+pub trait Worker: Send + 'static {
+	type Job: Send + 'static;
+	type Output: Send + 'static;
+
+	/// Number the job's output is collected under, EG: its part number
+	fn job_number(job: &Self::Job) -> i32;
+	fn process(&mut self, job: Self::Job) -> Result<Self::Output, AWSError>;
+}
+
+/// Runs `jobs` on the workers, each on a thread of its own, and collects the outputs by job
+/// number.  `on_done` is called as each job finishes.
+///
+/// After the first failure workers stop picking up new jobs, and jobs already running are waited
+/// for, so nothing is still being transferred once this returns.
+pub fn run_workers<W: Worker>(workers: Vec<W>, jobs: Vec<W::Job>,
+		on_done: &mut FnMut(i32, &W::Output)) -> Result<BTreeMap<i32, W::Output>, AWSError> {
+	let (job_sender, job_receiver) = mpsc::channel();
+	for job in jobs {
+		job_sender.send(job).unwrap();
+	}
+	drop(job_sender);
+
+	let jobs = Arc::new(Mutex::new(job_receiver));
+	let failed = Arc::new(AtomicBool::new(false));
+	let (result_sender, result_receiver) = mpsc::channel();
+
+	let mut threads = Vec::new();
+	for mut worker in workers {
+		let jobs = jobs.clone();
+		let failed = failed.clone();
+		let results = result_sender.clone();
+		threads.push(thread::spawn(move || {
+			while !failed.load(Ordering::SeqCst) {
+				let job = match jobs.lock().unwrap().recv() {
+					Ok(job) => job,
+					Err(_) => return,
+				};
+				let job_number = W::job_number(&job);
+				if results.send((job_number, worker.process(job))).is_err() {
+					return;
+				}
+			}
+		}));
+	}
+	drop(result_sender);
+
+	let mut outputs = BTreeMap::new();
+	let mut first_error = None;
+	for (job_number, result) in result_receiver {
+		match result {
+			Ok(output) => {
+				on_done(job_number, &output);
+				outputs.insert(job_number, output);
+			}
+			Err(why) => {
+				// stop the other workers from starting new jobs
+				failed.store(true, Ordering::SeqCst);
+				if first_error.is_none() {
+					first_error = Some(why);
+				}
+			}
+		}
+	}
+	for thread in threads {
+		if thread.join().is_err() && first_error.is_none() {
+			first_error = Some(AWSError::new("A worker thread panicked"));
+		}
+	}
+
+	match first_error {
+		Some(why) => Err(why),
+		None => Ok(outputs),
+	}
+}
@@ -159,6 +159,32 @@ pub fn end_element<T: Peek + Next>(element_name: &str, stack: &mut T)  -> Result
 	}
 }
 
+/// escape text for use in XML element contents or attribute values
+pub fn escape_xml(text: &str) -> String {
+	let mut escaped = String::with_capacity(text.len());
+	for c in text.chars() {
+		match c {
+			'&' => escaped.push_str("&amp;"),
+			'<' => escaped.push_str("&lt;"),
+			'>' => escaped.push_str("&gt;"),
+			'"' => escaped.push_str("&quot;"),
+			'\'' => escaped.push_str("&apos;"),
+			_ => escaped.push(c),
+		}
+	}
+	escaped
+}
+
+/// declare a default namespace on the root element of a serialized XML document
+pub fn with_xml_namespace(xml: &str, uri: &str) -> String {
+	match xml.find(|c: char| c == '>' || c == ' ' || c == '/') {
+		Some(end_of_name) if xml.starts_with("<") => {
+			format!("{} xmlns=\"{}\"{}", &xml[..end_of_name], escape_xml(uri), &xml[end_of_name..])
+		}
+		_ => xml.to_string(),
+	}
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -251,4 +277,14 @@ mod tests {
 	    }
 	}
 
+	#[test]
+	fn escape_xml_escapes_markup() {
+		assert_eq!(escape_xml("<Key>a & 'b'</Key>"), "&lt;Key&gt;a &amp; &apos;b&apos;&lt;/Key&gt;");
+	}
+
+	#[test]
+	fn with_xml_namespace_adds_to_root_element() {
+		assert_eq!(with_xml_namespace("<CORSConfiguration><CORSRule/></CORSConfiguration>", "http://s3.amazonaws.com/doc/2006-03-01/"),
+			"<CORSConfiguration xmlns=\"http://s3.amazonaws.com/doc/2006-03-01/\"><CORSRule/></CORSConfiguration>");
+	}
 }